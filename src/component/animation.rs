@@ -0,0 +1,181 @@
+use super::insert_or_merge;
+use crate::property::TimingFunction;
+
+use bevy::{
+    prelude::{
+        Commands,
+        Component,
+        Entity,
+        Mut,
+    },
+    utils::HashSet,
+};
+
+/// Whether an [`Animation`] plays forwards, backwards or alternates between both, like `alternate` on `animation-direction`.
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(PartialEq, Eq)]
+pub enum AnimationDirection
+{
+    #[default]
+    Normal,
+    Reverse,
+    Alternate,
+    AlternateReverse,
+}
+
+/// Whether an [`Animation`] applies its values before it starts and after it ends, like `forwards` on `animation-fill-mode`.
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(PartialEq, Eq)]
+pub enum AnimationFillMode
+{
+    #[default]
+    None,
+    Forwards,
+    Backwards,
+    Both,
+}
+
+/// A keyframe animation played on the entity, which is set by the `animation-*` properties.
+///
+/// The values of each animated property are interpolated from the `@keyframes` rule with the same name,
+/// which is searched on the [`StyleSheet`](crate::prelude::StyleSheet) of the entity or of its nearest ancestors.
+#[derive(Clone, Debug)]
+#[derive(Component)]
+pub struct Animation
+{
+    /// Name of the played `@keyframes` rule, like `fade` on `animation-name: fade`, or [`None`] for `none`.
+    pub name: Option<String>,
+    /// Duration of a single iteration, in seconds.
+    pub duration: f32,
+    /// Timing function used between keyframes which doesn't have their own.
+    pub timing_function: TimingFunction,
+    /// Time to wait before starting, in seconds, where negative values starts partway through the animation.
+    pub delay: f32,
+    /// How many times the animation is played, which may be fractional or [`f32::INFINITY`] for `infinite`.
+    pub iteration_count: f32,
+    /// Whether each iteration is played forwards or backwards.
+    pub direction: AnimationDirection,
+    /// Whether values are applied before the animation starts and after it ends.
+    pub fill_mode: AnimationFillMode,
+
+    /// Time since the animation started, in seconds, including the delay.
+    pub(crate) elapsed: f32,
+    /// Whether animated values were applied on the last update, so the underlying ones are applied again when it stops.
+    pub(crate) applying: bool,
+    /// Names of properties declared with `!important` on the entity, which aren't overridden by the animation.
+    pub(crate) important: HashSet<String>,
+}
+
+impl Default
+for Animation
+{
+    fn default(
+        // no args
+    ) -> Self {
+        Self{
+            name: None,
+            duration: 0.0,
+            timing_function: TimingFunction::default(),
+            delay: 0.0,
+            iteration_count: 1.0,
+            direction: AnimationDirection::default(),
+            fill_mode: AnimationFillMode::default(),
+            elapsed: 0.0,
+            applying: false,
+            important: HashSet::default(),
+        }
+    }
+}
+
+impl Animation
+{
+    /// Changes the played `@keyframes` rule, restarting the animation only if the name is different,
+    /// so applying the style sheet again doesn't restart it.
+    pub fn set_name(
+        &mut self,
+        name: Option<String>
+    ) {
+        if self.name != name
+        {
+            self.name = name;
+            self.elapsed = 0.0;
+        }
+    }
+
+    /// Computes the progress on the keyframes, from `0.0` to `1.0`, after applying the delay, iterations and direction,
+    /// or [`None`] if no value should be applied, like before the animation starts without a backwards fill mode.
+    pub fn progress(
+        &self
+    ) -> Option<f32> {
+        self.name.as_ref()?;
+
+        let active_time = self.elapsed - self.delay;
+        let duration = self.duration.max(0.0);
+        let iteration_count = self.iteration_count.max(0.0);
+        let total = match duration > 0.0
+        {
+            true => duration * iteration_count,
+            false => 0.0,
+        };
+
+        let (iteration, progress) = if active_time < 0.0
+        {
+            if !matches!(self.fill_mode, AnimationFillMode::Backwards | AnimationFillMode::Both)
+            {
+                return None;
+            }
+
+            (0.0, 0.0)
+        }
+        else if active_time >= total
+        {
+            if !matches!(self.fill_mode, AnimationFillMode::Forwards | AnimationFillMode::Both)
+            {
+                return None;
+            }
+
+            // Ends at the last iteration, which may be only partially played, like on `animation-iteration-count: 1.5`
+            match (iteration_count == 0.0, iteration_count.fract() == 0.0)
+            {
+                (true, _) => (0.0, 0.0),
+                (false, true) => (iteration_count - 1.0, 1.0),
+                (false, false) => (iteration_count.floor(), iteration_count.fract()),
+            }
+        }
+        else
+        {
+            let iterations = active_time / duration;
+            (iterations.floor(), iterations.fract())
+        };
+
+        let odd = iteration % 2.0 == 1.0;
+        let reversed = match self.direction
+        {
+            AnimationDirection::Normal => false,
+            AnimationDirection::Reverse => true,
+            AnimationDirection::Alternate => odd,
+            AnimationDirection::AlternateReverse => !odd,
+        };
+
+        match reversed
+        {
+            true => Some(1.0 - progress),
+            false => Some(progress),
+        }
+    }
+
+    /// Applies a single `animation-*` property on the [`Animation`] of the given entity,
+    /// inserting it when missing.
+    pub(crate) fn apply(
+        entity: Entity,
+        animation: Option<Mut<Animation>>,
+        commands: &mut Commands,
+        apply: impl FnOnce(&mut Animation) + Send + 'static,
+    ) {
+        match animation
+        {
+            Some(mut animation) => apply(&mut animation),
+            None => insert_or_merge(commands, entity, apply),
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use bevy::prelude::{
+    Component,
+    Reflect, ReflectComponent,
+};
+
+/// Marks an entity as checked, like a toggled checkbox, matched by `:checked` selector.
+///
+/// # Examples
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use tomt_bevycss::prelude::*;
+/// fn system(mut commands: Commands) {
+///     // This entity can be selected by ".checkbox:checked"
+///     commands.spawn((ButtonBundle::default(), Class::new("checkbox"), Checked));
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy, Reflect, Component)]
+#[reflect(Component)]
+pub struct Checked;
@@ -0,0 +1,26 @@
+use bevy::{
+    prelude::{
+        Component,
+        Deref,
+        Reflect, ReflectComponent
+    },
+    utils::HashMap,
+};
+
+/// Custom properties, like `--accent: #fff`, declared for an entity by style sheet rules.
+///
+/// This component is managed internally while applying style sheets. Descendants inherit these values,
+/// so any of them can reference a custom property with `var(--accent)`.
+#[derive(Debug, Reflect, Component, Default, Clone, PartialEq, Deref)]
+#[reflect(Component)]
+pub struct CustomProperties(HashMap<String, String>);
+
+impl CustomProperties
+{
+    /// Creates a new [`CustomProperties`] with the given raw css values.
+    pub(crate) fn new(
+        properties: HashMap<String, String>
+    ) -> Self {
+        Self(properties)
+    }
+}
@@ -0,0 +1,22 @@
+use bevy::prelude::{
+    Component,
+    Reflect, ReflectComponent,
+};
+
+/// Marks an entity as disabled, matched by `:disabled` selector instead of `:enabled`.
+///
+/// Disabled entities don't match `:hover`, `:active` or `:click`, and can't be focused.
+///
+/// # Examples
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use tomt_bevycss::prelude::*;
+/// fn system(mut commands: Commands) {
+///     // This entity can be selected by "button:disabled"
+///     commands.spawn((ButtonBundle::default(), Disabled));
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy, Reflect, Component)]
+#[reflect(Component)]
+pub struct Disabled;
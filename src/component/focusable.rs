@@ -0,0 +1,52 @@
+use bevy::prelude::{
+    Component,
+    Reflect, ReflectComponent,
+};
+
+/// Marks an entity which can receive focus, matched by `:focus`, `:focus-visible` and `:focus-within` selectors.
+///
+/// The focus itself is changed through [`FocusState`](crate::prelude::FocusState) resource,
+/// which is mirrored into this component so styles are matched again whenever the focus changes.
+///
+/// # Examples
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use tomt_bevycss::prelude::*;
+/// fn system(mut commands: Commands) {
+///     // This entity can be selected by "button:focus" once focused
+///     commands.spawn((ButtonBundle::default(), Focusable::default()));
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy, Reflect, Component)]
+#[derive(PartialEq, Eq)]
+#[reflect(Component)]
+pub struct Focusable
+{
+    focused: bool,
+    visible: bool,
+}
+
+impl Focusable
+{
+    /// Checks if this entity is focused, matched by `:focus`
+    pub fn is_focused(
+        &self
+    ) -> bool {
+        self.focused
+    }
+
+    /// Checks if this entity is focused by keyboard or gamepad navigation, matched by `:focus-visible`
+    pub fn is_focus_visible(
+        &self
+    ) -> bool {
+        self.focused && self.visible
+    }
+
+    pub(crate) fn new(
+        focused: bool,
+        visible: bool
+    ) -> Self {
+        Self { focused, visible: focused && visible }
+    }
+}
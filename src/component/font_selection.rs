@@ -0,0 +1,53 @@
+use super::insert_or_merge;
+
+use bevy::prelude::{
+    Commands,
+    Component,
+    Entity,
+    Mut,
+};
+
+/// The font selected for a text entity, which is set by the `font-family` and `font-weight` properties.
+///
+/// It's resolved into the best matching `@font-face` rule declared on the [`StyleSheet`](crate::prelude::StyleSheet)
+/// of the entity or of its nearest ancestors, trying each family in order until one is found.
+#[derive(Clone, Debug)]
+#[derive(PartialEq)]
+#[derive(Component)]
+pub struct FontSelection
+{
+    /// Font families in the order of preference, like `Fira` and `serif` on `font-family: Fira, serif`.
+    pub families: Vec<String>,
+    /// Preferred weight, from `1` to `1000`, like `700` on `font-weight: bold`.
+    pub weight: f32,
+}
+
+impl Default
+for FontSelection
+{
+    fn default(
+        // no args
+    ) -> Self {
+        Self {
+            families: Vec::new(),
+            weight: 400.0,
+        }
+    }
+}
+
+impl FontSelection
+{
+    /// Applies a single `font-*` property on the [`FontSelection`] of the given entity, inserting it when missing.
+    pub(crate) fn apply(
+        entity: Entity,
+        selection: Option<Mut<FontSelection>>,
+        commands: &mut Commands,
+        apply: impl FnOnce(&mut FontSelection) + Send + 'static,
+    ) {
+        match selection
+        {
+            Some(mut selection) => apply(&mut selection),
+            None => insert_or_merge(commands, entity, apply),
+        }
+    }
+}
@@ -1,10 +1,78 @@
+mod animation;
+pub use animation::*;
+
 mod class;
 pub use class::*;
 
+mod custom_properties;
+pub use custom_properties::*;
+
+mod font_selection;
+pub use font_selection::*;
+
+mod relative_lengths;
+pub(crate) use relative_lengths::*;
+
 mod style_sheet;
 pub use style_sheet::*;
 
-use bevy::prelude::Name;
+mod transitions;
+pub use transitions::*;
+
+#[cfg(feature = "pseudo_class")]
+mod checked;
+#[cfg(feature = "pseudo_class")]
+pub use checked::*;
+
+#[cfg(feature = "pseudo_class")]
+mod disabled;
+#[cfg(feature = "pseudo_class")]
+pub use disabled::*;
+
+#[cfg(feature = "pseudo_class")]
+mod focusable;
+#[cfg(feature = "pseudo_class")]
+pub use focusable::*;
+
+#[cfg(feature = "pseudo_prop")]
+mod pseudo_element;
+#[cfg(feature = "pseudo_prop")]
+pub use pseudo_element::*;
+
+use bevy::prelude::{
+    Commands,
+    Component,
+    Entity,
+    Name,
+    World,
+};
+
+/// Applies the given change on the component of the entity once commands are applied, inserting a default one when missing.
+///
+/// Many properties may be applied before the component is inserted, so each change is merged into the same component.
+pub(crate) fn insert_or_merge<T>(
+    commands: &mut Commands,
+    entity: Entity,
+    merge: impl FnOnce(&mut T) + Send + 'static,
+) where
+    T: Component + Default,
+{
+    commands.add(move |world: &mut World|
+    {
+        if let Some(mut entity) = world.get_entity_mut(entity)
+        {
+            match entity.get_mut::<T>()
+            {
+                Some(mut component) => merge(&mut component),
+                None => {
+                    let mut component = T::default();
+                    merge(&mut component);
+                    entity.insert(component);
+                }
+            }
+        }
+    });
+}
 
 /// Convenience trait which matches matches a component against a named element selector.
 pub(crate) trait MatchSelectorElement {
@@ -0,0 +1,63 @@
+use bevy::prelude::{
+    Component,
+    Reflect, ReflectComponent,
+};
+
+/// Marks a child entity managed by `tomt_bevycss`, which represents a pseudo-element like `::before`.
+///
+/// Pseudo-elements are spawned as children of the entities matched by rules like `.item::before { content: "•"; }`,
+/// and despawned once no rule matches them anymore. [`Marker`](PseudoElement::Marker) and [`Before`](PseudoElement::Before)
+/// are inserted as the first children, while [`After`](PseudoElement::After) is added as the last child.
+#[derive(Debug, Clone, Copy, Reflect, Component)]
+#[derive(PartialEq, Eq)]
+#[derive(PartialOrd, Ord)]
+#[derive(Hash)]
+#[reflect(Component)]
+pub enum PseudoElement
+{
+    /// The `::marker` pseudo-element, placed before any other child, like a list bullet
+    Marker,
+
+    /// The `::before` pseudo-element, placed before the children of the entity
+    Before,
+
+    /// The `::after` pseudo-element, placed after the children of the entity
+    After,
+}
+
+impl PseudoElement
+{
+    /// All pseudo-elements, in the order they are placed among the entity children
+    pub const ALL: [PseudoElement; 3] = [PseudoElement::Marker, PseudoElement::Before, PseudoElement::After];
+
+    /// Finds a pseudo-element by its name on `css`, like `before` for `::before`
+    pub fn from_name(
+        name: &str
+    ) -> Option<Self> {
+        Self::ALL.into_iter()
+            .find(|element| element.name() == name)
+    }
+
+    /// Name of the pseudo-element on `css`, like `before` for `::before`
+    pub fn name(
+        &self
+    ) -> &'static str {
+        match self
+        {
+            PseudoElement::Marker => "marker",
+            PseudoElement::Before => "before",
+            PseudoElement::After => "after",
+        }
+    }
+}
+
+impl std::fmt::Display
+for PseudoElement
+{
+    fn fmt(
+        &self,
+        formatter: &mut std::fmt::Formatter<'_>
+    ) -> std::fmt::Result {
+        write!(formatter, "::{}", self.name())
+    }
+}
@@ -0,0 +1,158 @@
+use super::insert_or_merge;
+use crate::property::{
+    LengthValue,
+    MathExpression,
+};
+
+use bevy::{
+    prelude::{
+        Commands,
+        Component,
+        Deref, DerefMut,
+        Entity,
+        Mut,
+        Style,
+        Text,
+    },
+    ui::Val,
+    utils::HashMap,
+};
+
+/// Which size of the parent node is used to resolve percents of a [`RelativeLength`].
+#[derive(Clone, Copy, Debug)]
+#[derive(PartialEq, Eq)]
+pub(crate) enum PercentBase
+{
+    Width,
+    Height,
+}
+
+/// What a [`RelativeLength`] is applied on, once resolved.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum LengthTarget
+{
+    /// A field of the [`Style`], where percents are resolved against the given size of the parent node.
+    Style(PercentBase, fn(&mut Style, Val)),
+
+    /// The font size of all sections of the [`Text`], where `em` and percents are resolved against the font size of the parent.
+    FontSize,
+}
+
+/// A length property which can only be resolved at layout, like `width: calc(100% - 20px)` or `margin: 2em`.
+#[derive(Clone, Debug)]
+pub(crate) struct RelativeLength
+{
+    pub expression: MathExpression,
+    pub target: LengthTarget,
+}
+
+/// Length properties of an entity which are resolved again whenever the size of its parent node or its font sizes changes.
+///
+/// Each entry is keyed by the name of the property it's applied by, like `width`, `margin-top` or `font-size`.
+#[derive(Debug, Default, Component)]
+#[derive(Deref, DerefMut)]
+pub(crate) struct RelativeLengths(
+    HashMap<&'static str, RelativeLength>
+);
+
+impl RelativeLengths
+{
+    /// Key of the relative `font-size`, which is applied on the [`Text`] instead of the [`Style`].
+    pub(crate) const FONT_SIZE: &'static str = "font-size";
+
+    /// Applies the [`LengthValue`] on the given [`Style`].
+    ///
+    /// [`Relative`](LengthValue::Relative) values are kept on this component to be resolved later,
+    /// while any other value replaces the relative one on the same field.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn apply(
+        key: &'static str,
+        length: &LengthValue,
+        base: PercentBase,
+        apply: fn(&mut Style, Val),
+        entity: Entity,
+        style: &mut Style,
+        lengths: Option<&mut Mut<RelativeLengths>>,
+        commands: &mut Commands,
+    ) {
+        match length
+        {
+            LengthValue::Val(val) => {
+                apply(style, *val);
+                Self::keep(key, None, entity, lengths, commands);
+            }
+
+            LengthValue::Relative(expression) => {
+                let relative = RelativeLength {
+                    expression: expression.clone(),
+                    target: LengthTarget::Style(base, apply),
+                };
+
+                Self::keep(key, Some(relative), entity, lengths, commands);
+            }
+        }
+    }
+
+    /// Applies the [`LengthValue`] of `font-size` on all sections of the given [`Text`].
+    ///
+    /// Lengths in pixels are applied right away, while any other value is kept on this component to be resolved later.
+    pub(crate) fn apply_font_size(
+        length: &LengthValue,
+        entity: Entity,
+        text: &mut Text,
+        lengths: Option<&mut Mut<RelativeLengths>>,
+        commands: &mut Commands,
+    ) {
+        match length
+        {
+            LengthValue::Val(Val::Px(size)) => {
+                for section in text.sections.iter_mut()
+                {
+                    section.style.font_size = *size;
+                }
+                Self::keep(Self::FONT_SIZE, None, entity, lengths, commands);
+            }
+
+            length => {
+                let relative = length.expression()
+                    .map(|expression| RelativeLength {
+                        expression,
+                        target: LengthTarget::FontSize,
+                    });
+
+                Self::keep(Self::FONT_SIZE, relative, entity, lengths, commands);
+            }
+        }
+    }
+
+    /// Keeps the relative length to be resolved later, or removes the one kept with the same key when [`None`].
+    fn keep(
+        key: &'static str,
+        relative: Option<RelativeLength>,
+        entity: Entity,
+        lengths: Option<&mut Mut<RelativeLengths>>,
+        commands: &mut Commands,
+    ) {
+        match (relative, lengths)
+        {
+            (Some(relative), Some(lengths)) => {
+                lengths.insert(key, relative);
+            }
+
+            (Some(relative), None) => insert_or_merge(commands, entity, move |lengths: &mut RelativeLengths|
+            {
+                lengths.insert(key, relative);
+            }),
+
+            // Avoid triggering change detection when there's nothing to remove
+            (None, Some(lengths)) => {
+                if lengths.contains_key(key)
+                {
+                    lengths.remove(key);
+                }
+            }
+
+            (None, None) => {}
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use crate::property::TimingFunction;
+
+use bevy::prelude::{
+    Component,
+    Deref, DerefMut,
+};
+
+/// How a property is interpolated when its value changes, like `width 300ms ease-in` on `transition`.
+#[derive(Clone, Debug, Default)]
+#[derive(PartialEq)]
+pub struct Transition
+{
+    /// Name of the transitioned property, like `width`, or `all` for any property.
+    pub property: String,
+    /// Duration of the transition, in seconds.
+    pub duration: f32,
+    /// Easing of the transition.
+    pub timing_function: TimingFunction,
+    /// Time to wait before starting, in seconds, where negative values starts partway through the transition.
+    pub delay: f32,
+}
+
+/// Properties which are interpolated when their values changes on the entity, which is set by the `transition` property,
+/// like `transition: background-color 150ms ease-out, width 300ms`.
+///
+/// Only properties registered with [`register_interpolated_property`](crate::RegisterProperty::register_interpolated_property)
+/// can be transitioned.
+#[derive(Clone, Debug, Default)]
+#[derive(Component, Deref, DerefMut)]
+pub struct Transitions(
+    pub Vec<Transition>
+);
+
+impl Transitions
+{
+    /// Gets the [`Transition`] of the property with the given name, where later transitions wins, as on CSS.
+    pub fn get(
+        &self,
+        property: &str
+    ) -> Option<&Transition> {
+        self.0.iter()
+            .rev()
+            .find(|transition| transition.property == property || transition.property == "all")
+    }
+}
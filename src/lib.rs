@@ -5,36 +5,72 @@ pub mod error;
 mod parser;
 pub mod plugins;
 pub mod property;
-mod selector;
+pub mod selector;
 mod stylesheet;
 pub mod system;
 
 use crate::{
-    property::Property,
+    property::{
+        Interpolate,
+        Property,
+    },
     system::{
         sets::BevyCssSet,
-        ComponentFilterRegistry,
+        AttributeFilterRegistry, AttributeGetter,
+        ComponentFilterRegistry, ComponentSelector,
     },
 };
 
-use bevy::{
-    ecs::system::SystemState,
-    prelude::*,
+#[cfg(feature = "pseudo_class")]
+use crate::system::{
+    PseudoClassFilterRegistry,
+    PseudoClassPredicate,
 };
 
+use bevy::prelude::*;
+
 pub type DynArray<T> = smallvec::SmallVec<[T; 8]>;
 
 /// use `tomt_bevycss::prelude::*;` to import common components, and plugins and utility functions.
 pub mod prelude {
     pub use super::{
-        component::{Class, StyleSheet},
+        component::{
+            Animation, AnimationDirection, AnimationFillMode,
+            Class,
+            CustomProperties,
+            FontSelection,
+            StyleSheet,
+            Transition, Transitions,
+        },
         error::BevyCssError,
         plugins::BevyCssPlugin,
         property::{Property, PropertyValues},
         stylesheet::StyleSheetAsset,
+        system::{
+            CustomMediaFeatures,
+            RootFontSize,
+        },
+        RegisterAttributeSelector,
         RegisterComponentSelector,
         RegisterProperty,
     };
+
+    #[cfg(feature = "pseudo_class")]
+    pub use super::{
+        component::{
+            Checked,
+            Disabled,
+            Focusable,
+        },
+        system::focus::{
+            FocusNavigation,
+            FocusState,
+        },
+        RegisterPseudoClass,
+    };
+
+    #[cfg(feature = "pseudo_prop")]
+    pub use super::component::PseudoElement;
 }
 
 /// Utility trait which adds the [`register_component_selector`](RegisterComponentSelector::register_component_selector)
@@ -83,13 +119,10 @@ for bevy::prelude::App
     where
         T: Component,
     {
-        let system_state = SystemState::<Query<Entity, With<T>>>::new(&mut self.world);
-        let boxed_state = Box::new(system_state);
+        let boxed_state = Box::new(ComponentSelector::<T>::new(&mut self.world));
 
         self.world
-            .get_resource_or_insert_with::<ComponentFilterRegistry>(|| {
-                ComponentFilterRegistry(Default::default())
-            })
+            .get_resource_or_insert_with(ComponentFilterRegistry::default)
             .0
             .insert(name, boxed_state);
 
@@ -97,6 +130,142 @@ for bevy::prelude::App
     }
 }
 
+/// Utility trait which adds the [`register_attribute_selector`](RegisterAttributeSelector::register_attribute_selector)
+/// function on [`App`] to add a new attribute selector.
+///
+/// The given getter reads the attribute value from a component, where [`None`] means the attribute isn't present.
+/// Many components may provide the same attribute name, in which case any of them may match the selector.
+/// Styles are matched again whenever the value returned by the getter changes.
+///
+/// Attributes which aren't registered are looked up as fields on reflected components registered with
+/// [`register_component_selector`](RegisterComponentSelector::register_component_selector).
+///
+/// # Examples
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use tomt_bevycss::prelude::*;
+/// #
+/// # fn some_main() {
+/// #    let mut app = App::new();
+/// #    app.add_plugins(DefaultPlugins).add_plugins(BevyCssPlugin::default());
+/// #
+///      #[derive(Component)]
+///      struct Slot { rarity: u8 }
+///
+///      app.register_attribute_selector::<Slot>("rarity", |slot| match slot.rarity
+///      {
+///          0 => None,
+///          1 => Some("common".to_string()),
+///          _ => Some("epic".to_string()),
+///      });
+///      // You may use it as selector now, like
+///      // [rarity="epic"] {
+///      //      background-color: purple;
+///      // }
+/// # }
+/// ```
+pub trait RegisterAttributeSelector
+{
+    fn register_attribute_selector<T>(
+        &mut self,
+        name: &'static str,
+        getter: impl Fn(&T) -> Option<String> + Send + Sync + 'static
+    ) -> &mut Self
+    where
+        T: Component;
+}
+
+impl RegisterAttributeSelector
+for bevy::prelude::App
+{
+    fn register_attribute_selector<T>(
+        &mut self,
+        name: &'static str,
+        getter: impl Fn(&T) -> Option<String> + Send + Sync + 'static
+    ) -> &mut Self
+    where
+        T: Component,
+    {
+        let boxed_getter = Box::new(AttributeGetter::new(&mut self.world, getter));
+
+        self.world
+            .get_resource_or_insert_with::<AttributeFilterRegistry>(Default::default)
+            .0
+            .entry(name)
+            .or_default()
+            .push(boxed_getter);
+
+        self
+    }
+}
+
+/// Utility trait which adds the [`register_pseudo_class`](RegisterPseudoClass::register_pseudo_class)
+/// function on [`App`] to add a new pseudo-class selector.
+///
+/// The given predicate checks if an entity with the component matches the pseudo-class.
+/// Many components may provide the same pseudo-class, in which case any of them may match the selector.
+/// Styles are matched again whenever the component changes or is removed, so the predicate should only depend on the component.
+///
+/// Registered pseudo-classes takes precedence over builtin ones with the same name.
+///
+/// # Examples
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use tomt_bevycss::prelude::*;
+/// #
+/// # fn some_main() {
+/// #    let mut app = App::new();
+/// #    app.add_plugins(DefaultPlugins).add_plugins(BevyCssPlugin::default());
+/// #
+///      #[derive(Component)]
+///      struct Toggle { on: bool }
+///
+///      app.register_pseudo_class::<Toggle>("toggled", |toggle| toggle.on);
+///      // You may use it as selector now, like
+///      // .option:toggled {
+///      //      background-color: green;
+///      // }
+/// # }
+/// ```
+#[cfg(feature = "pseudo_class")]
+pub trait RegisterPseudoClass
+{
+    fn register_pseudo_class<T>(
+        &mut self,
+        name: &'static str,
+        predicate: impl Fn(&T) -> bool + Send + Sync + 'static
+    ) -> &mut Self
+    where
+        T: Component;
+}
+
+#[cfg(feature = "pseudo_class")]
+impl RegisterPseudoClass
+for bevy::prelude::App
+{
+    fn register_pseudo_class<T>(
+        &mut self,
+        name: &'static str,
+        predicate: impl Fn(&T) -> bool + Send + Sync + 'static
+    ) -> &mut Self
+    where
+        T: Component,
+    {
+        let boxed_predicate = Box::new(PseudoClassPredicate::new(&mut self.world, predicate));
+
+        self.world
+            .get_resource_or_insert_with::<PseudoClassFilterRegistry>(Default::default)
+            .0
+            .entry(name)
+            .or_default()
+            .push(boxed_predicate);
+
+        self
+    }
+}
+
 /// Utility trait which adds the [`register_property`](RegisterProperty::register_property) function
 /// on [`App`] to add a [`Property`] parser.
 ///
@@ -108,6 +277,15 @@ pub trait RegisterProperty
     ) -> &mut Self
     where
         T: Property + 'static;
+
+    /// Same as [`register_property`](RegisterProperty::register_property), but the property is also interpolated
+    /// when its value changes on entities with a matching `transition`, like `transition: width 300ms`.
+    fn register_interpolated_property<T>(
+        &mut self
+    ) -> &mut Self
+    where
+        T: Property + 'static,
+        T::Cache: Interpolate;
 }
 
 impl RegisterProperty
@@ -121,4 +299,15 @@ for bevy::prelude::App
     {
         self.add_systems(Update, T::apply_system.in_set(BevyCssSet::Apply))
     }
+
+    fn register_interpolated_property<T>(
+        &mut self
+    ) -> &mut Self
+    where
+        T: Property + 'static,
+        T::Cache: Interpolate,
+    {
+        self.register_property::<T>()
+            .add_systems(Update, T::transition_system.after(BevyCssSet::Apply))
+    }
 }
@@ -0,0 +1,192 @@
+use super::format_error;
+use crate::{
+    prelude::BevyCssError,
+    stylesheet::FontFace,
+};
+
+use bevy::log::error;
+use cssparser::{
+    match_ignore_ascii_case,
+    AtRuleParser,
+    BasicParseErrorKind,
+    CowRcStr,
+    DeclarationParser,
+    ParseError, Parser,
+    QualifiedRuleParser,
+    RuleBodyItemParser,
+    RuleBodyParser,
+    Token,
+};
+
+/// Parses the block of a `@font-face` rule, like `font-family: "Fira"; src: url("fonts/FiraSans-Bold.ttf");`.
+pub(crate) struct FontFaceParser;
+
+/// A single supported descriptor of a `@font-face` rule.
+pub(crate) enum FontFaceDescriptor
+{
+    /// The family name, like `"Fira"` on `font-family: "Fira"`.
+    Family(String),
+    /// The asset path of the first `url()`, like `fonts/FiraSans-Bold.ttf` on `src: url("fonts/FiraSans-Bold.ttf")`.
+    Src(String),
+    /// The range of weights, like `700` on `font-weight: bold` or `100 900` on `font-weight: 100 900`.
+    Weight(f32, f32),
+}
+
+impl FontFaceParser
+{
+    /// Parses all descriptors until the end of the input, where both `font-family` and `src` are required.
+    pub(crate) fn parse<'i>(
+        input: &mut Parser<'i, '_>
+    ) -> Result<FontFace, ParseError<'i, BevyCssError>> {
+        let mut family = None;
+        let mut path = None;
+        let mut weight = (400.0, 400.0);
+
+        for descriptor in RuleBodyParser::new(input, &mut FontFaceParser)
+        {
+            match descriptor
+            {
+                Ok(FontFaceDescriptor::Family(name)) => family = Some(name),
+                Ok(FontFaceDescriptor::Src(url)) => path = Some(url),
+                Ok(FontFaceDescriptor::Weight(min, max)) => weight = (min, max),
+                Err((err, descriptor)) => {
+                    error!("Failed to parse font-face descriptor: {}. Error: {}", descriptor, format_error(err));
+                }
+            }
+        }
+
+        match (family, path)
+        {
+            (Some(family), Some(path)) => Ok(FontFace { family, path, weight }),
+            _ => Err(input.new_error(BasicParseErrorKind::AtRuleBodyInvalid)),
+        }
+    }
+
+    /// Parses a family name, like `"Fira Sans"` or `Fira Sans`, where unquoted names are joined by a single space.
+    fn parse_family<'i>(
+        input: &mut Parser<'i, '_>,
+    ) -> Result<String, ParseError<'i, BevyCssError>> {
+        if let Ok(name) = input.try_parse(|input| input.expect_string().map(|name| name.to_string()))
+        {
+            return Ok(name);
+        }
+
+        let mut names = vec![input.expect_ident()?.to_string()];
+        while let Ok(name) = input.try_parse(|input| input.expect_ident().map(|name| name.to_string()))
+        {
+            names.push(name);
+        }
+
+        Ok(names.join(" "))
+    }
+
+    /// Parses a single source of the `src` list, like `url("fonts/FiraSans-Bold.ttf") format("truetype")`.
+    ///
+    /// Local fonts, like `local("Fira Sans")`, aren't supported and so are skipped.
+    fn parse_source<'i>(
+        input: &mut Parser<'i, '_>,
+    ) -> Result<Option<String>, ParseError<'i, BevyCssError>> {
+        let source = match input.try_parse(|input| input.expect_url().map(|url| url.to_string()))
+        {
+            Ok(url) => Some(url),
+            Err(_) => {
+                input.expect_function_matching("local")?;
+                None
+            }
+        };
+
+        // Hints, like `format("truetype")` or `tech(variations)`, are only useful to browsers choosing what to download
+        while !input.is_exhausted()
+        {
+            input.expect_function()?;
+        }
+
+        Ok(source)
+    }
+
+    /// Parses a single weight, like `normal`, `bold` or `700`.
+    fn parse_weight<'i>(
+        input: &mut Parser<'i, '_>,
+    ) -> Result<f32, ParseError<'i, BevyCssError>> {
+        let location = input.current_source_location();
+
+        let weight = match input.next()?
+        {
+            Token::Ident(name) => match_ignore_ascii_case! { name,
+                "normal" => Some(400.0),
+                "bold" => Some(700.0),
+                _ => None,
+            },
+            &Token::Number { value, .. } if (1.0..=1000.0).contains(&value) => Some(value),
+            _ => None,
+        };
+
+        weight.ok_or_else(|| location.new_custom_error(BevyCssError::InvalidPropertyValue("font-weight".to_string())))
+    }
+}
+
+impl<'i> DeclarationParser<'i>
+for FontFaceParser
+{
+    type Declaration = FontFaceDescriptor;
+    type Error = BevyCssError;
+
+    fn parse_value<'t>(
+        &mut self,
+        name: CowRcStr<'i>,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self::Declaration, ParseError<'i, BevyCssError>> {
+        let descriptor = match_ignore_ascii_case! { &name,
+            "font-family" => FontFaceDescriptor::Family(Self::parse_family(input)?),
+            "src" => {
+                let sources = input.parse_comma_separated(Self::parse_source)?;
+                match sources.into_iter().flatten().next()
+                {
+                    Some(url) => FontFaceDescriptor::Src(url),
+                    None => return Err(input.new_custom_error(BevyCssError::InvalidPropertyValue(name.to_string()))),
+                }
+            },
+            "font-weight" => {
+                let min = Self::parse_weight(input)?;
+                let max = input.try_parse(Self::parse_weight).unwrap_or(min);
+
+                // As on CSS, reversed ranges are swapped, like `900 100`
+                FontFaceDescriptor::Weight(min.min(max), min.max(max))
+            },
+            _ => return Err(input.new_custom_error(BevyCssError::UnsupportedProperty(name.to_string()))),
+        };
+
+        input.expect_exhausted()?;
+        Ok(descriptor)
+    }
+}
+
+impl<'i> RuleBodyItemParser<'i, FontFaceDescriptor, BevyCssError>
+for FontFaceParser
+{
+    fn parse_declarations(&self) -> bool
+    {
+        true
+    }
+
+    fn parse_qualified(&self) -> bool
+    {
+        false
+    }
+}
+
+impl<'i> AtRuleParser<'i>
+for FontFaceParser
+{
+    type Prelude = ();
+    type AtRule = FontFaceDescriptor;
+    type Error = BevyCssError;
+}
+
+impl<'i> QualifiedRuleParser<'i>
+for FontFaceParser
+{
+    type Prelude = ();
+    type QualifiedRule = FontFaceDescriptor;
+    type Error = BevyCssError;
+}
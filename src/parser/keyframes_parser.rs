@@ -0,0 +1,140 @@
+use super::{
+    format_error,
+    Declaration,
+    PropertyParser,
+};
+use crate::{
+    prelude::BevyCssError,
+    stylesheet::{
+        Keyframe,
+        Keyframes,
+    },
+};
+
+use bevy::log::error;
+use cssparser::{
+    match_ignore_ascii_case,
+    AtRuleParser,
+    ParseError, Parser, ParserState,
+    QualifiedRuleParser,
+    RuleBodyParser,
+    Token,
+};
+
+/// Parses the block of a `@keyframes` rule, like `from { opacity: 0; } to { opacity: 1; }`.
+pub(crate) struct KeyframesParser;
+
+impl KeyframesParser
+{
+    /// Parses all keyframes until the end of the input, skipping the invalid ones.
+    pub(crate) fn parse(
+        input: &mut Parser<'_, '_>
+    ) -> Keyframes {
+        let keyframes = cssparser::StyleSheetParser::new(input, &mut KeyframesParser)
+            .filter_map(|result| match result
+            {
+                Ok(keyframes) => Some(keyframes),
+                Err((err, rule)) => {
+                    error!(
+                        "Failed to parse keyframe: {}. Error: {}",
+                        rule,
+                        format_error(err)
+                    );
+                    None
+                }
+            })
+            .flatten()
+            .collect::<Vec<_>>();
+
+        Keyframes::new(keyframes)
+    }
+
+    /// Parses a single keyframe selector, like `from`, `to` or `50%`, into its offset.
+    fn parse_offset<'i>(
+        input: &mut Parser<'i, '_>,
+    ) -> Result<f32, ParseError<'i, BevyCssError>> {
+        let location = input.current_source_location();
+
+        let offset = match input.next()?
+        {
+            Token::Ident(name) => match_ignore_ascii_case! { name,
+                "from" => Some(0.0),
+                "to" => Some(1.0),
+                _ => None,
+            },
+            &Token::Percentage { unit_value, .. } if (0.0..=1.0).contains(&unit_value) => Some(unit_value),
+            _ => None,
+        };
+
+        offset.ok_or_else(|| location.new_custom_error(BevyCssError::InvalidSelector))
+    }
+}
+
+impl<'i> QualifiedRuleParser<'i>
+for KeyframesParser
+{
+    type Prelude = Vec<f32>;
+    type QualifiedRule = Vec<Keyframe>;
+    type Error = BevyCssError;
+
+    fn parse_prelude<'t>(
+        &mut self,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self::Prelude, ParseError<'i, Self::Error>> {
+        input.parse_comma_separated(Self::parse_offset)
+    }
+
+    fn parse_block<'t>(
+        &mut self,
+        prelude: Self::Prelude,
+        _start: &ParserState,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self::QualifiedRule, ParseError<'i, Self::Error>> {
+        let mut keyframe = Keyframe::default();
+
+        for property in RuleBodyParser::new(input, &mut PropertyParser)
+        {
+            let (name, declaration, important) = match property
+            {
+                Ok(property) => property,
+                Err((err, declaration)) => {
+                    error!("Failed to parse keyframe declaration: {}. Error: {}", declaration, format_error(err));
+                    continue;
+                }
+            };
+
+            // As on CSS, important declarations are ignored inside keyframes
+            if important
+            {
+                error!("Ignoring {name}, since keyframes can't have !important declarations");
+                continue;
+            }
+
+            match declaration
+            {
+                Declaration::Values(values) if name == "animation-timing-function" => {
+                    keyframe.timing_function = values.timing_function();
+                }
+                Declaration::Values(values) => {
+                    keyframe.properties.insert(name, *values);
+                }
+                Declaration::Custom(_) | Declaration::Var(_) => {
+                    error!("Ignoring {name}, since keyframes can't use custom properties");
+                }
+            }
+        }
+
+        // Each offset on the list, like `0%` and `100%` on `0%, 100% { ... }`, is a keyframe with the same properties
+        Ok(prelude.into_iter()
+            .map(|offset| Keyframe{ offset, ..keyframe.clone() })
+            .collect())
+    }
+}
+
+impl<'i> AtRuleParser<'i>
+for KeyframesParser
+{
+    type Prelude = ();
+    type AtRule = Vec<Keyframe>;
+    type Error = BevyCssError;
+}
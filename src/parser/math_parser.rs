@@ -0,0 +1,111 @@
+use super::parse_unit;
+use crate::{
+    prelude::BevyCssError,
+    property::MathExpression,
+};
+
+use cssparser::{
+    match_ignore_ascii_case,
+    ParseError, Parser,
+    Token,
+};
+
+/// Checks if the function name is a math function, like `calc` or `clamp`.
+pub(crate) fn is_math_function(
+    name: &str
+) -> bool {
+    match_ignore_ascii_case! { name,
+        "calc" | "min" | "max" | "clamp" => true,
+        _ => false,
+    }
+}
+
+/// Parses the arguments of a math function, like `100% - 20px` inside `calc(...)`.
+pub(crate) fn parse_math_function<'i>(
+    name: &str,
+    input: &mut Parser<'i, '_>,
+) -> Result<MathExpression, ParseError<'i, BevyCssError>> {
+    match_ignore_ascii_case! { name,
+        "calc" => parse_sum(input),
+        "min" => Ok(MathExpression::Min(input.parse_comma_separated(parse_sum)?)),
+        "max" => Ok(MathExpression::Max(input.parse_comma_separated(parse_sum)?)),
+        "clamp" => {
+            let min = parse_sum(input)?;
+            input.expect_comma()?;
+            let value = parse_sum(input)?;
+            input.expect_comma()?;
+            let max = parse_sum(input)?;
+
+            Ok(MathExpression::Clamp(Box::new(min), Box::new(value), Box::new(max)))
+        },
+        _ => Err(input.new_custom_error(BevyCssError::InvalidPropertyValue(name.to_string()))),
+    }
+}
+
+/// Parses a sequence of products joined by `+` or `-`, like `100% - 20px * 2`.
+fn parse_sum<'i>(
+    input: &mut Parser<'i, '_>,
+) -> Result<MathExpression, ParseError<'i, BevyCssError>> {
+    let mut expression = parse_product(input)?;
+
+    loop
+    {
+        let operator = input.try_parse(|input| match input.next()
+        {
+            Ok(Token::Delim(c @ ('+' | '-'))) => Ok(*c),
+            _ => Err(()),
+        });
+
+        expression = match operator
+        {
+            Ok('+') => MathExpression::Sum(Box::new(expression), Box::new(parse_product(input)?)),
+            Ok(_) => MathExpression::Difference(Box::new(expression), Box::new(parse_product(input)?)),
+            Err(_) => break Ok(expression),
+        };
+    }
+}
+
+/// Parses a sequence of values joined by `*` or `/`, like `20px * 2`.
+fn parse_product<'i>(
+    input: &mut Parser<'i, '_>,
+) -> Result<MathExpression, ParseError<'i, BevyCssError>> {
+    let mut expression = parse_value(input)?;
+
+    loop
+    {
+        let operator = input.try_parse(|input| match input.next()
+        {
+            Ok(Token::Delim(c @ ('*' | '/'))) => Ok(*c),
+            _ => Err(()),
+        });
+
+        expression = match operator
+        {
+            Ok('*') => MathExpression::Product(Box::new(expression), Box::new(parse_value(input)?)),
+            Ok(_) => MathExpression::Quotient(Box::new(expression), Box::new(parse_value(input)?)),
+            Err(_) => break Ok(expression),
+        };
+    }
+}
+
+/// Parses a single value, a nested math function or an expression between parenthesis, like `(100% - 20px)`.
+fn parse_value<'i>(
+    input: &mut Parser<'i, '_>,
+) -> Result<MathExpression, ParseError<'i, BevyCssError>> {
+    let token = input.next()?.clone();
+
+    match token
+    {
+        Token::Number { value, .. } => Ok(MathExpression::Number(value)),
+        Token::Percentage { unit_value, .. } => Ok(MathExpression::Percentage(unit_value * 100.0)),
+        Token::Dimension { value, unit, .. } => Ok(MathExpression::Dimension(value, parse_unit(input, value, &unit)?)),
+
+        Token::ParenthesisBlock => input.parse_nested_block(parse_sum),
+
+        Token::Function(name) if is_math_function(&name) => {
+            input.parse_nested_block(|input| parse_math_function(&name, input))
+        }
+
+        token => Err(input.new_unexpected_token_error(token)),
+    }
+}
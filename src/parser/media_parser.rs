@@ -0,0 +1,244 @@
+use super::{
+    format_error,
+    parse_unit,
+};
+use crate::{
+    prelude::BevyCssError,
+    stylesheet::{
+        MediaComparison,
+        MediaFeature,
+        MediaQuery,
+        MediaQueryList,
+        MediaType,
+        MediaValue,
+    },
+};
+
+use bevy::log::error;
+use cssparser::{
+    match_ignore_ascii_case,
+    Delimiter,
+    ParseError, Parser,
+    Token,
+};
+
+/// Parses the media query list of a `@media` rule, like `screen and (min-width: 1280px), (orientation: portrait)`.
+///
+/// As on CSS, an invalid query never matches, without invalidating the other queries of the list.
+pub(crate) fn parse_media_query_list(
+    input: &mut Parser<'_, '_>,
+) -> MediaQueryList {
+    let start = input.position();
+    let mut queries = Vec::new();
+
+    while !input.is_exhausted()
+    {
+        match input.parse_until_before(Delimiter::Comma, parse_media_query)
+        {
+            Ok(query) => queries.push(query),
+            Err(err) => {
+                error!("Invalid media query, which never matches. Error: {}", format_error(err));
+                queries.push(MediaQuery::never());
+            }
+        }
+
+        // Skip the comma, if any
+        let _ = input.next();
+    }
+
+    MediaQueryList {
+        queries,
+        text: input.slice_from(start).trim().to_string(),
+    }
+}
+
+/// Parses a single media query, like `not print and (min-width: 1280px)` or `(orientation: landscape)`.
+fn parse_media_query<'i>(
+    input: &mut Parser<'i, '_>,
+) -> Result<MediaQuery, ParseError<'i, BevyCssError>> {
+    let mut negated = false;
+    let mut media_type = input.try_parse(|input| input.expect_ident_cloned()).ok();
+
+    // Modifiers are followed by the media type, or by the features when negating, like `not (orientation: portrait)`
+    if let Some(modifier) = media_type.clone()
+    {
+        match_ignore_ascii_case! { &modifier,
+            "not" => {
+                negated = true;
+                media_type = input.try_parse(|input| input.expect_ident_cloned()).ok();
+            },
+            "only" => media_type = Some(input.expect_ident_cloned()?),
+            _ => (),
+        }
+    }
+
+    let mut features = Vec::new();
+    let media_type = match media_type
+    {
+        Some(name) => match_ignore_ascii_case! { &name,
+            "all" => MediaType::All,
+            "screen" => MediaType::Screen,
+            "not" | "only" | "and" | "or" => return Err(input.new_custom_error(BevyCssError::UnexpectedToken(name.to_string()))),
+            _ => MediaType::Other,
+        },
+
+        None => {
+            features.extend(parse_media_feature(input)?);
+            MediaType::All
+        }
+    };
+
+    while !input.is_exhausted()
+    {
+        input.expect_ident_matching("and")?;
+        features.extend(parse_media_feature(input)?);
+    }
+
+    Ok(MediaQuery {
+        negated,
+        media_type,
+        features,
+    })
+}
+
+/// Parses a media feature inside parenthesis, like `(min-width: 1280px)`, `(width >= 1280px)` or `(400px <= width < 700px)`,
+/// where ranges are split into one feature for each comparison.
+fn parse_media_feature<'i>(
+    input: &mut Parser<'i, '_>,
+) -> Result<Vec<MediaFeature>, ParseError<'i, BevyCssError>> {
+    input.expect_parenthesis_block()?;
+    input.parse_nested_block(|input|
+    {
+        let mut features = Vec::new();
+
+        // Ranges starting by the value, like `400px <= width`, are the same as the flipped comparison, like `width >= 400px`
+        if let Ok(value) = input.try_parse(parse_range_value)
+        {
+            let comparison = flip(parse_comparison(input)?);
+            let name = input.expect_ident()?.to_string();
+            features.push(MediaFeature::Compare(name.clone(), comparison, value));
+
+            if !input.is_exhausted()
+            {
+                let comparison = parse_comparison(input)?;
+                features.push(MediaFeature::Compare(name, comparison, parse_media_value(input)?));
+            }
+
+            return Ok(features);
+        }
+
+        let name = input.expect_ident()?.to_string();
+        if input.is_exhausted()
+        {
+            features.push(MediaFeature::Boolean(name));
+            return Ok(features);
+        }
+
+        let feature = match input.try_parse(|input| input.expect_colon())
+        {
+            Ok(_) => {
+                let value = parse_media_value(input)?;
+                let lowercase = name.to_ascii_lowercase();
+
+                match (lowercase.strip_prefix("min-"), lowercase.strip_prefix("max-"))
+                {
+                    (Some(name), _) => MediaFeature::Compare(name.to_string(), MediaComparison::GreaterOrEqual, value),
+                    (_, Some(name)) => MediaFeature::Compare(name.to_string(), MediaComparison::LessOrEqual, value),
+                    _ => MediaFeature::Compare(name, MediaComparison::Equal, value),
+                }
+            }
+
+            Err(_) => {
+                let comparison = parse_comparison(input)?;
+                MediaFeature::Compare(name, comparison, parse_media_value(input)?)
+            }
+        };
+
+        input.expect_exhausted()?;
+        features.push(feature);
+        Ok(features)
+    })
+}
+
+/// Parses a comparison of a range, like `<=`.
+fn parse_comparison<'i>(
+    input: &mut Parser<'i, '_>,
+) -> Result<MediaComparison, ParseError<'i, BevyCssError>> {
+    let location = input.current_source_location();
+    let operator = match input.next()?
+    {
+        Token::Delim(c @ ('<' | '>' | '=')) => *c,
+        token => return Err(location.new_unexpected_token_error(token.clone())),
+    };
+
+    let or_equal = operator != '=' && input.try_parse(|input| input.expect_delim('=')).is_ok();
+
+    Ok(match (operator, or_equal)
+    {
+        ('<', false) => MediaComparison::Less,
+        ('<', true) => MediaComparison::LessOrEqual,
+        ('>', false) => MediaComparison::Greater,
+        ('>', true) => MediaComparison::GreaterOrEqual,
+        _ => MediaComparison::Equal,
+    })
+}
+
+/// Flips a comparison, so the value and the feature can be swapped, like `<` on `400px < width` into `>` on `width > 400px`.
+fn flip(
+    comparison: MediaComparison
+) -> MediaComparison {
+    match comparison
+    {
+        MediaComparison::Less => MediaComparison::Greater,
+        MediaComparison::LessOrEqual => MediaComparison::GreaterOrEqual,
+        MediaComparison::Greater => MediaComparison::Less,
+        MediaComparison::GreaterOrEqual => MediaComparison::LessOrEqual,
+        MediaComparison::Equal => MediaComparison::Equal,
+    }
+}
+
+/// Parses a value which can start a range, like `400px`, but not an identifier, since it would be the feature name.
+fn parse_range_value<'i>(
+    input: &mut Parser<'i, '_>,
+) -> Result<MediaValue, ParseError<'i, BevyCssError>> {
+    match parse_media_value(input)?
+    {
+        MediaValue::Ident(ident) => Err(input.new_custom_error(BevyCssError::UnexpectedToken(ident))),
+        value => Ok(value),
+    }
+}
+
+/// Parses the value of a media feature, like `1280px`, `16/9`, `2dppx` or `landscape`.
+fn parse_media_value<'i>(
+    input: &mut Parser<'i, '_>,
+) -> Result<MediaValue, ParseError<'i, BevyCssError>> {
+    let location = input.current_source_location();
+
+    match input.next()?.clone()
+    {
+        Token::Number { value, .. } => {
+            let denominator = input.try_parse(|input|
+            {
+                input.expect_delim('/')?;
+                input.expect_number()
+            });
+
+            Ok(match denominator
+            {
+                Ok(denominator) => MediaValue::Ratio(value / denominator),
+                Err(_) => MediaValue::Number(value),
+            })
+        }
+
+        Token::Dimension { value, ref unit, .. } => match_ignore_ascii_case! { unit,
+            "dppx" | "x" => Ok(MediaValue::Resolution(value)),
+            "dpi" => Ok(MediaValue::Resolution(value / 96.0)),
+            "dpcm" => Ok(MediaValue::Resolution(value * 2.54 / 96.0)),
+            _ => Ok(MediaValue::Length(value, parse_unit(input, value, unit)?)),
+        },
+
+        Token::Ident(ident) => Ok(MediaValue::Ident(ident.to_string())),
+
+        token => Err(location.new_unexpected_token_error(token)),
+    }
+}
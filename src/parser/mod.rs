@@ -1,16 +1,48 @@
 pub(crate) use style_sheet_parser::*;
-use crate::{DynArray, prelude::BevyCssError};
+pub(crate) use var_resolver::*;
+use crate::{
+    DynArray,
+    prelude::BevyCssError,
+    property::{
+        is_color_function,
+        is_timing_function,
+        parse_color_function,
+        parse_timing_function,
+        PropertyToken, PropertyValues,
+        Unit,
+    },
+};
 
 use cssparser::{
-    Parser, ParseError,
+    Parser, ParseError, ParserInput,
     ToCss, Token,
 };
 
+mod font_face_parser;
+use font_face_parser::FontFaceParser;
+
+mod keyframes_parser;
+use keyframes_parser::KeyframesParser;
+
+mod math_parser;
+pub(crate) use math_parser::{
+    is_math_function,
+    parse_math_function,
+};
+
+mod media_parser;
+use media_parser::parse_media_query_list;
+
 mod property_parser;
-use property_parser::PropertyParser;
+use property_parser::{
+    Declaration,
+    PropertyParser,
+};
 
 mod style_sheet_parser;
 
+mod var_resolver;
+
 
 fn format_error(
     error: ParseError<BevyCssError>
@@ -36,16 +68,76 @@ fn format_error(
     )
 }
 
+/// Gets the [`Unit`] of a dimension, like `px` on `10px`, failing when the unit isn't supported.
+fn parse_unit<'i>(
+    parser: &Parser<'i, '_>,
+    value: f32,
+    unit: &str,
+) -> Result<Unit, ParseError<'i, BevyCssError>> {
+    Unit::from_name(unit)
+        .ok_or_else(|| parser.new_custom_error(BevyCssError::InvalidPropertyValue(format!("unknown unit `{}` on {}{}", unit, value, unit))))
+}
+
+/// Parses all tokens of a property value into [`PropertyValues`], skipping any token which isn't a valid value.
 fn parse_values<'i>(
     parser: &mut Parser<'i, '_>,
-) -> Result<DynArray<Token<'i>>, ParseError<'i, BevyCssError>> {
+) -> Result<PropertyValues, ParseError<'i, BevyCssError>> {
     let mut values = DynArray::new();
     while let Ok(token) = parser.next_including_whitespace()
     {
-        values.push(token.clone())
+        match token
+        {
+            Token::Function(name) if is_math_function(name) => {
+                let name = name.clone();
+                let expression = parser.parse_nested_block(|input| parse_math_function(&name, input))?;
+                values.push(PropertyToken::Math(expression));
+            }
+
+            Token::Function(name) if is_color_function(name) => {
+                let name = name.clone();
+                let color = parser.parse_nested_block(|input| parse_color_function(&name, input))?;
+                values.push(PropertyToken::Color(color));
+            }
+
+            Token::Function(name) if is_timing_function(name) => {
+                let name = name.clone();
+                let timing_function = parser.parse_nested_block(|input| parse_timing_function(&name, input))?;
+                values.push(PropertyToken::TimingFunction(timing_function));
+            }
+
+            &Token::Dimension { value, ref unit, .. } if unit.eq_ignore_ascii_case("s") || unit.eq_ignore_ascii_case("ms") => {
+                let seconds = match unit.eq_ignore_ascii_case("s")
+                {
+                    true => value,
+                    false => value / 1000.0,
+                };
+                values.push(PropertyToken::Time(seconds));
+            }
+
+            &Token::Dimension { value, ref unit, .. } => {
+                let unit = unit.clone();
+                values.push(PropertyToken::Dimension(value, parse_unit(parser, value, &unit)?));
+            }
+
+            token => if let Ok(value) = token.clone().try_into()
+            {
+                values.push(value);
+            }
+        }
     }
 
-    Ok(values)
+    Ok(PropertyValues(values))
+}
+
+/// Parses a raw property value, like `10px auto`, into [`PropertyValues`].
+pub(crate) fn parse_property_values(
+    content: &str
+) -> PropertyValues {
+    let mut input = ParserInput::new(content);
+    let mut parser = Parser::new(&mut input);
+
+    parse_values(&mut parser)
+        .unwrap_or_default()
 }
 
 #[cfg(test)]
@@ -2,26 +2,96 @@ use super::{
     parse_values,
 };
 use crate::{
-    DynArray,
     prelude::BevyCssError,
     property::PropertyValues,
 };
 
 use cssparser::{
+    parse_important,
     AtRuleParser,
     DeclarationParser,
+    Delimiter,
     Parser,
     ParseError,
     RuleBodyItemParser,
     QualifiedRuleParser,
+    Token,
 };
 
 pub struct PropertyParser;
 
+/// A single declaration parsed from a rule block.
+#[derive(Debug, Clone)]
+pub enum Declaration
+{
+    /// A property which values can be parsed ahead, like `width: 10px`.
+    Values(Box<PropertyValues>),
+
+    /// A custom property, like `--accent: #fff`, with its raw css value.
+    Custom(String),
+
+    /// A property which references custom properties, like `color: var(--accent)`, with its raw css value.
+    Var(String),
+}
+
+impl PropertyParser
+{
+    /// Parses a declaration value, without its `!important` flag.
+    fn parse_declaration<'i>(
+        name: &str,
+        parser: &mut Parser<'i, '_>,
+    ) -> Result<Declaration, ParseError<'i, BevyCssError>> {
+        let start = parser.state();
+
+        // Values depending on custom properties are only known when applied to an entity, so keep them as they are
+        let has_var = Self::has_var_function(parser);
+        let raw = parser.slice_from(start.position()).trim().to_string();
+
+        let declaration = match (name.starts_with("--"), has_var)
+        {
+            (true, _) => Declaration::Custom(raw),
+            (false, true) => Declaration::Var(raw),
+            (false, false) => {
+                parser.reset(&start);
+                Declaration::Values(Box::new(parse_values(parser)?))
+            }
+        };
+
+        Ok(declaration)
+    }
+
+    /// Checks if there is any `var()` function in the remaining tokens, consuming all of them.
+    fn has_var_function<'i>(
+        input: &mut Parser<'i, '_>,
+    ) -> bool {
+        let mut found = false;
+
+        while let Ok(token) = input.next()
+        {
+            match token
+            {
+                Token::Function(name) if name.eq_ignore_ascii_case("var") => found = true,
+
+                Token::Function(_)
+                | Token::ParenthesisBlock
+                | Token::SquareBracketBlock
+                | Token::CurlyBracketBlock => {
+                    found |= input.parse_nested_block(|input| Ok::<_, ParseError<()>>(Self::has_var_function(input)))
+                        .unwrap_or(false);
+                }
+
+                _ => (),
+            }
+        }
+
+        found
+    }
+}
+
 impl<'i> DeclarationParser<'i>
 for PropertyParser
 {
-    type Declaration = (String, PropertyValues);
+    type Declaration = (String, Declaration, bool);
     type Error = BevyCssError;
 
     fn parse_value<'t>(
@@ -29,21 +99,15 @@ for PropertyParser
         name: cssparser::CowRcStr<'i>,
         parser: &mut Parser<'i, 't>,
     ) -> Result<Self::Declaration, ParseError<'i, BevyCssError>> {
-        let mut tokens = DynArray::new();
-        for token in parse_values(parser)?
-        {
-            match token.try_into()
-            {
-                Ok(t) => tokens.push(t),
-                Err(_) => continue,
-            }
-        }
+        let declaration = parser.parse_until_before(Delimiter::Bang, |parser| Self::parse_declaration(&name, parser))?;
+        let important = parser.try_parse(parse_important).is_ok();
+        parser.expect_exhausted()?;
 
-        Ok((name.to_string(), PropertyValues(tokens)))
+        Ok((name.to_string(), declaration, important))
     }
 }
 
-impl<'i> RuleBodyItemParser<'i, (String, PropertyValues), BevyCssError> for PropertyParser {
+impl<'i> RuleBodyItemParser<'i, (String, Declaration, bool), BevyCssError> for PropertyParser {
     fn parse_declarations(&self) -> bool
     {
         true
@@ -59,12 +123,12 @@ impl<'i> AtRuleParser<'i>
 for PropertyParser
 {
     type Prelude = ();
-    type AtRule = (String, PropertyValues);
+    type AtRule = (String, Declaration, bool);
     type Error = BevyCssError;
 }
 
 impl<'i> QualifiedRuleParser<'i> for PropertyParser {
     type Prelude = ();
-    type QualifiedRule = (String, PropertyValues);
+    type QualifiedRule = (String, Declaration, bool);
     type Error = BevyCssError;
 }
\ No newline at end of file
@@ -1,18 +1,33 @@
 use super::{
     format_error,
+    parse_media_query_list,
+    Declaration,
+    FontFaceParser,
+    KeyframesParser,
     PropertyParser,
 };
 use crate::{
     DynArray,
     prelude::BevyCssError,
-    selector::{Selector, SelectorElement},
-    stylesheet::StyleRule,
+    selector::{AttributeMatch, Selector, SelectorElement},
+    stylesheet::{
+        FontFace,
+        Keyframes,
+        MediaQueryList,
+        StyleRule,
+    },
 };
 
-use bevy::log::error;
+use bevy::{
+    log::error,
+    utils::HashMap,
+};
 use cssparser::{
+    match_ignore_ascii_case,
     AtRuleParser,
-    ParseError, Parser, ParserInput,
+    BasicParseErrorKind,
+    CowRcStr,
+    ParseError, Parser, ParserInput, ParserState,
     QualifiedRuleParser,
     RuleBodyParser,
     ToCss,
@@ -21,15 +36,91 @@ use cssparser::{
 /// Parses a `css` string using [`StyleSheetParser`].
 pub(crate) struct StyleSheetParser;
 
+/// A top level rule of a style sheet.
+pub(crate) enum CssRule
+{
+    /// A style rule, like `a { color: red; }`.
+    Style(Box<StyleRule>),
+    /// An imported style sheet, like `@import "common.css";`, with its path relative to the importing sheet.
+    Import(String),
+    /// The style rules of a `@media` rule, like `@media (min-width: 1280px) { ... }`, already restricted to its queries.
+    Media(Vec<StyleRule>),
+    /// A named animation, like `@keyframes fade { from { opacity: 0; } to { opacity: 1; } }`.
+    Keyframes(String, Keyframes),
+    /// A font, like `@font-face { font-family: "Fira"; src: url("fonts/FiraSans-Bold.ttf"); }`.
+    FontFace(FontFace),
+}
+
+/// The prelude of a supported at-rule, like `"common.css"` on `@import "common.css";`.
+pub(crate) enum AtRulePrelude
+{
+    Import(String),
+    Media(MediaQueryList),
+    Keyframes(String),
+    FontFace,
+}
+
+/// Everything parsed from a `css` string, besides the rules which are only used while parsing, like `@media`.
+#[derive(Default)]
+pub(crate) struct ParsedStyleSheet
+{
+    /// Style rules, in the declared order.
+    pub(crate) rules: DynArray<StyleRule>,
+    /// Paths of the imported sheets, like `common.css` on `@import "common.css";`, in the declared order.
+    pub(crate) imports: Vec<String>,
+    /// Keyframes by the name of their animation, where later `@keyframes` with the same name replaces earlier ones.
+    pub(crate) keyframes: HashMap<String, Keyframes>,
+    /// Fonts declared by `@font-face` rules, in the declared order.
+    pub(crate) font_faces: Vec<FontFace>,
+}
+
 impl StyleSheetParser
 {
+    /// Parses a `css` string into its style rules, ignoring imports, keyframes and fonts.
+    #[cfg(test)]
     pub(crate) fn parse(
         content: &str
     ) -> DynArray<StyleRule> {
+        Self::parse_sheet(content).rules
+    }
+
+    /// Parses a `css` string into its style rules, the paths of the sheets imported by it, its keyframes and its fonts.
+    ///
+    /// As on CSS, imports are only valid before any other rule, so later ones are ignored.
+    pub(crate) fn parse_sheet(
+        content: &str
+    ) -> ParsedStyleSheet {
         let mut input = ParserInput::new(content);
         let mut parser = Parser::new(&mut input);
 
-        cssparser::StyleSheetParser::new(&mut parser, &mut StyleSheetParser)
+        let mut sheet = ParsedStyleSheet::default();
+
+        for rule in Self::parse_rules(&mut parser)
+        {
+            match rule
+            {
+                CssRule::Style(rule) => sheet.rules.push(*rule),
+                CssRule::Media(media_rules) => sheet.rules.extend(media_rules),
+                CssRule::Keyframes(name, keyframes) => {
+                    sheet.keyframes.insert(name, keyframes);
+                }
+                CssRule::FontFace(font_face) => sheet.font_faces.push(font_face),
+                CssRule::Import(path) if sheet.rules.is_empty()
+                    && sheet.keyframes.is_empty()
+                    && sheet.font_faces.is_empty() => sheet.imports.push(path),
+                CssRule::Import(path) => error!("Ignoring import of {path}, since imports must come before any rule"),
+            }
+        }
+
+        sheet
+    }
+
+    /// Parses all rules until the end of the input, like the whole sheet or the block of a `@media` rule,
+    /// skipping the invalid ones.
+    fn parse_rules(
+        parser: &mut Parser<'_, '_>
+    ) -> Vec<CssRule> {
+        cssparser::StyleSheetParser::new(parser, &mut StyleSheetParser)
             .filter_map(|result| match result
             {
                 Ok(rule) => Some(rule),
@@ -46,18 +137,20 @@ impl StyleSheetParser
     }
 }
 
-impl<'i> QualifiedRuleParser<'i>
-for StyleSheetParser
+impl StyleSheetParser
 {
-    type Prelude = Selector;
-    type QualifiedRule = StyleRule;
-    type Error = BevyCssError;
+    /// Parses a single complex selector, like `a.b > #c`, from a comma separated selector list.
+    fn parse_selector<'i>(
+        input: &mut Parser<'i, '_>,
+    ) -> Result<Selector, ParseError<'i, BevyCssError>> {
+        Self::parse_selector_elements(input, DynArray::new())
+    }
 
-    fn parse_prelude<'t>(
-        &mut self,
-        input: &mut Parser<'i, 't>,
-    ) -> Result<Self::Prelude, ParseError<'i, Self::Error>> {
-        let mut elements = DynArray::new();
+    /// Parses the elements of a complex selector, appending them to the already known `elements`.
+    fn parse_selector_elements<'i>(
+        input: &mut Parser<'i, '_>,
+        mut elements: DynArray<SelectorElement>,
+    ) -> Result<Selector, ParseError<'i, BevyCssError>> {
 
         #[derive(Debug, Default, Clone)]
         enum DelimType
@@ -97,20 +190,55 @@ for StyleSheetParser
 
                     #[cfg(feature = "pseudo_prop")]
                     DelimType::PseudoProp => {
-                        let err_str = format!(":{v}");
-                        return Err(
-                            input.new_custom_error(BevyCssError::UnexpectedToken(err_str))
-                        );
+                        prev_delim = DelimType::None;
+                        match crate::component::PseudoElement::from_name(v)
+                        {
+                            Some(element) => SelectorElement::PseudoProp(element),
+                            None => return Err(input.new_custom_error(BevyCssError::UnsupportedSelector)),
+                        }
                     }
                 }),
 
+                SquareBracketBlock if matches!(prev_delim, DelimType::None) => {
+                    let attribute = input.parse_nested_block(Self::parse_attribute)?;
+                    elements.push(attribute);
+                }
+
                 IDHash(v) => match v.is_empty()
                 {
                     true => return Err(input.new_custom_error(BevyCssError::InvalidSelector)),
                     false => elements.push(SelectorElement::Name(v.to_string())),
                 }
                 
-                WhiteSpace(_) => elements.push(SelectorElement::Child),
+                // Whitespace around other combinators is just noise
+                WhiteSpace(_) => match elements.last()
+                {
+                    Some(last) if !last.is_combinator() => elements.push(SelectorElement::Child),
+                    _ => (),
+                },
+
+                Delim(c @ ('>' | '+' | '~')) if matches!(prev_delim, DelimType::None) => {
+                    let combinator = match c
+                    {
+                        '>' => SelectorElement::DirectChild,
+                        '+' => SelectorElement::AdjacentSibling,
+                        _ => SelectorElement::GeneralSibling,
+                    };
+
+                    match elements.last()
+                    {
+                        Some(SelectorElement::Child) => *elements.last_mut().unwrap() = combinator,
+                        Some(last) if !last.is_combinator() => elements.push(combinator),
+                        _ => {
+                            let err_str = token.to_css_string();
+                            return Err(
+                                input.new_custom_error(BevyCssError::UnexpectedToken(err_str))
+                            );
+                        }
+                    }
+                }
+
+                Delim('*') if matches!(prev_delim, DelimType::None) => elements.push(SelectorElement::Universal),
 
                 Delim(c) => prev_delim = match (*c, prev_delim)
                 {
@@ -139,6 +267,15 @@ for StyleSheetParser
                     }
                 },
 
+                #[cfg(feature = "pseudo_class")]
+                Function(name) if matches!(prev_delim, DelimType::PseudoClass) => {
+                    prev_delim = DelimType::None;
+
+                    let name = name.to_string();
+                    let element = input.parse_nested_block(|input| Self::parse_pseudo_function(&name, input))?;
+                    elements.push(element);
+                }
+
                 _ => {
                     let token = token.to_css_string();
                     return Err(input.new_custom_error(BevyCssError::UnexpectedToken(token)));
@@ -157,36 +294,280 @@ for StyleSheetParser
             elements.remove(elements.len() - 1);
         }
 
+        // Any other combinator must be followed by a selector, like `a > b`
+        if elements.last().is_none_or(SelectorElement::is_combinator)
+        {
+            return Err(input.new_custom_error(BevyCssError::InvalidSelector));
+        }
+
+        // Pseudo-elements can only be the last element, like `a::before`
+        #[cfg(feature = "pseudo_prop")]
+        if elements.iter()
+            .rev()
+            .skip(1)
+            .any(|element| matches!(element, SelectorElement::PseudoProp(_)))
+        {
+            return Err(input.new_custom_error(BevyCssError::InvalidSelector));
+        }
+
         Ok(Selector::new(elements))
     }
+}
+
+impl StyleSheetParser
+{
+    /// Parses the content of an attribute selector, like `disabled` or `rarity="epic"` inside `[...]`.
+    fn parse_attribute<'i>(
+        input: &mut Parser<'i, '_>,
+    ) -> Result<SelectorElement, ParseError<'i, BevyCssError>> {
+        let name = input.expect_ident()?.to_string();
+
+        if input.is_exhausted()
+        {
+            return Ok(SelectorElement::Attribute(name, AttributeMatch::Exists));
+        }
+
+        let operator = input.next()?.clone();
+        let value = input.expect_ident_or_string()?.to_string();
+
+        use cssparser::Token::*;
+        let matching = match operator
+        {
+            Delim('=') => AttributeMatch::Equals(value),
+            IncludeMatch => AttributeMatch::Includes(value),
+            DashMatch => AttributeMatch::DashMatch(value),
+            PrefixMatch => AttributeMatch::Prefix(value),
+            SuffixMatch => AttributeMatch::Suffix(value),
+            SubstringMatch => AttributeMatch::Substring(value),
+            _ => {
+                let err_str = operator.to_css_string();
+                return Err(input.new_custom_error(BevyCssError::UnexpectedToken(err_str)));
+            }
+        };
+
+        input.expect_exhausted()?;
+        Ok(SelectorElement::Attribute(name, matching))
+    }
+}
+
+#[cfg(feature = "pseudo_class")]
+impl StyleSheetParser
+{
+    /// Parses the arguments of a functional pseudo-class, like `2n+1` inside `:nth-child(...)` or `.hidden` inside `:not(...)`.
+    fn parse_pseudo_function<'i>(
+        name: &str,
+        input: &mut Parser<'i, '_>,
+    ) -> Result<SelectorElement, ParseError<'i, BevyCssError>> {
+        use crate::selector::Nth;
+
+        let element = match name
+        {
+            "nth-child" => {
+                let (a, b) = cssparser::parse_nth(input)?;
+                SelectorElement::NthChild(Nth { a, b })
+            }
+
+            "nth-last-child" => {
+                let (a, b) = cssparser::parse_nth(input)?;
+                SelectorElement::NthLastChild(Nth { a, b })
+            }
+
+            "not" => SelectorElement::Not(Self::parse_selector_list(input)?),
+            "is" => SelectorElement::Is(Self::parse_selector_list(input)?),
+            "where" => SelectorElement::Where(Self::parse_selector_list(input)?),
+            "has" => SelectorElement::Has(input.parse_comma_separated(Self::parse_relative_selector)?),
+
+            _ => return Err(input.new_custom_error(BevyCssError::UnsupportedSelector)),
+        };
+
+        input.expect_exhausted()?;
+        Ok(element)
+    }
+
+    /// Parses a comma separated list of selectors, like the arguments of `:is(button, .slot)`
+    fn parse_selector_list<'i>(
+        input: &mut Parser<'i, '_>,
+    ) -> Result<Vec<Selector>, ParseError<'i, BevyCssError>> {
+        input.parse_comma_separated(Self::parse_selector)
+    }
+
+    /// Parses a relative selector, like `> .item` inside `:has(...)`.
+    ///
+    /// The leading combinator is kept as the first element, defaulting to [`Child`](SelectorElement::Child) when omitted.
+    fn parse_relative_selector<'i>(
+        input: &mut Parser<'i, '_>,
+    ) -> Result<Selector, ParseError<'i, BevyCssError>> {
+        input.skip_whitespace();
+
+        let combinator = input.try_parse(|input| match input.next()
+            {
+                Ok(cssparser::Token::Delim('>')) => Ok(SelectorElement::DirectChild),
+                Ok(cssparser::Token::Delim('+')) => Ok(SelectorElement::AdjacentSibling),
+                Ok(cssparser::Token::Delim('~')) => Ok(SelectorElement::GeneralSibling),
+                _ => Err(()),
+            })
+            .unwrap_or(SelectorElement::Child);
+
+        Self::parse_selector_elements(input, smallvec::smallvec![combinator])
+    }
+}
+
+impl<'i> QualifiedRuleParser<'i>
+for StyleSheetParser
+{
+    type Prelude = DynArray<Selector>;
+    type QualifiedRule = CssRule;
+    type Error = BevyCssError;
+
+    fn parse_prelude<'t>(
+        &mut self,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self::Prelude, ParseError<'i, Self::Error>> {
+        input.parse_comma_separated(Self::parse_selector)
+            .map(DynArray::from_vec)
+    }
 
     fn parse_block<'t>(
         &mut self,
         prelude: Self::Prelude,
-        _start: &cssparser::ParserState,
+        _start: &ParserState,
         input: &mut Parser<'i, 't>,
     ) -> Result<Self::QualifiedRule, ParseError<'i, Self::Error>> {
         let mut rule = StyleRule::new(prelude);
 
         for property in RuleBodyParser::new(input, &mut PropertyParser)
         {
-            match property
+            let (name, declaration, important) = match property
             {
-                Ok((name, property)) => {
-                    rule.properties.insert(name, property);
+                Ok(property) => property,
+                Err((err, a)) => {
+                    println!("Failed: {:?} ({})", err, a);
+                    continue;
+                }
+            };
+
+            // Important declarations wins over normal ones of the same property, regardless of their order
+            if !important && rule.important.contains(&name)
+            {
+                continue;
+            }
+
+            if important
+            {
+                rule.important.insert(name.clone());
+            }
+
+            // Otherwise the last declaration wins, even if it's of another kind, like `var()` replacing a plain value
+            rule.properties.remove(&name);
+            rule.custom_properties.remove(&name);
+            rule.var_properties.remove(&name);
+
+            match declaration
+            {
+                Declaration::Values(property) => {
+                    rule.properties.insert(name, *property);
+                }
+                Declaration::Custom(value) => {
+                    rule.custom_properties.insert(name, value);
+                }
+                Declaration::Var(value) => {
+                    rule.var_properties.insert(name, value);
                 }
-                Err((err, a)) => println!("Failed: {:?} ({})", err, a),
             }
         }
 
-        Ok(rule)
+        Ok(CssRule::Style(Box::new(rule)))
     }
 }
 
 impl<'i> AtRuleParser<'i>
 for StyleSheetParser
 {
-    type Prelude = ();
-    type AtRule = StyleRule;
+    type Prelude = AtRulePrelude;
+    type AtRule = CssRule;
     type Error = BevyCssError;
+
+    fn parse_prelude<'t>(
+        &mut self,
+        name: CowRcStr<'i>,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self::Prelude, ParseError<'i, Self::Error>> {
+        match_ignore_ascii_case! { &name,
+            "import" => {
+                let path = input.expect_url_or_string()?.to_string();
+                Ok(AtRulePrelude::Import(path))
+            },
+            "media" => Ok(AtRulePrelude::Media(parse_media_query_list(input))),
+            "keyframes" => {
+                let location = input.current_source_location();
+                let name = match input.next()?
+                {
+                    cssparser::Token::Ident(name) if !name.eq_ignore_ascii_case("none") => name.to_string(),
+                    cssparser::Token::QuotedString(name) => name.to_string(),
+                    token => {
+                        let token = token.to_css_string();
+                        return Err(location.new_custom_error(BevyCssError::UnexpectedToken(token)));
+                    }
+                };
+                input.expect_exhausted()?;
+
+                Ok(AtRulePrelude::Keyframes(name))
+            },
+            "font-face" => {
+                input.expect_exhausted()?;
+                Ok(AtRulePrelude::FontFace)
+            },
+            _ => Err(input.new_error(BasicParseErrorKind::AtRuleInvalid(name))),
+        }
+    }
+
+    fn rule_without_block(
+        &mut self,
+        prelude: Self::Prelude,
+        _start: &ParserState,
+    ) -> Result<Self::AtRule, ()> {
+        match prelude
+        {
+            AtRulePrelude::Import(path) => Ok(CssRule::Import(path)),
+            AtRulePrelude::Media(_) | AtRulePrelude::Keyframes(_) | AtRulePrelude::FontFace => Err(()),
+        }
+    }
+
+    fn parse_block<'t>(
+        &mut self,
+        prelude: Self::Prelude,
+        _start: &ParserState,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self::AtRule, ParseError<'i, Self::Error>> {
+        let media = match prelude
+        {
+            AtRulePrelude::Media(media) => media,
+            AtRulePrelude::Keyframes(name) => return Ok(CssRule::Keyframes(name, KeyframesParser::parse(input))),
+            AtRulePrelude::FontFace => return FontFaceParser::parse(input).map(CssRule::FontFace),
+            AtRulePrelude::Import(_) => return Err(input.new_error(BasicParseErrorKind::AtRuleBodyInvalid)),
+        };
+
+        let mut rules = Vec::new();
+        for rule in Self::parse_rules(input)
+        {
+            match rule
+            {
+                CssRule::Style(rule) => rules.push(*rule),
+                // Nested rules must match the queries of all `@media` rules they're in
+                CssRule::Media(media_rules) => rules.extend(media_rules),
+                CssRule::Import(path) => error!("Ignoring import of {path}, since imports can't be inside @media"),
+                CssRule::Keyframes(name, _) => error!("Ignoring keyframes {name}, since keyframes inside @media aren't supported"),
+                CssRule::FontFace(font_face) => {
+                    error!("Ignoring font-face {}, since font-faces inside @media aren't supported", font_face.family);
+                }
+            }
+        }
+
+        for rule in rules.iter_mut()
+        {
+            rule.restrict_to_media(&media);
+        }
+
+        Ok(CssRule::Media(rules))
+    }
 }
@@ -4,6 +4,8 @@ use crate::{
     selector::SelectorElement,
 };
 
+use smallvec::smallvec;
+
 #[test]
 fn parse_empty(
     // no args
@@ -42,7 +44,7 @@ fn parse_single_name_selector_no_property(
     assert_eq!(rules.len(), 1, "Should have a single rule");
 
     let rule = &rules[0];
-    let tree = rule.selector.get_parent_tree();
+    let tree = rule.selectors[0].get_parent_tree();
     assert_eq!(tree.len(), 1, "Should have a single selector node");
 
     let node = &tree[0];
@@ -65,7 +67,7 @@ fn parse_single_class_selector_no_property(
     assert_eq!(rules.len(), 1, "Should have a single rule");
 
     let rule = &rules[0];
-    let tree = rule.selector.get_parent_tree();
+    let tree = rule.selectors[0].get_parent_tree();
     assert_eq!(tree.len(), 1, "Should have a single selector node");
 
     let node = &tree[0];
@@ -88,7 +90,7 @@ fn parse_single_component_selector_no_property(
     assert_eq!(rules.len(), 1, "Should have a single rule");
 
     let rule = &rules[0];
-    let tree = rule.selector.get_parent_tree();
+    let tree = rule.selectors[0].get_parent_tree();
     assert_eq!(tree.len(), 1, "Should have a single selector node");
 
     let node = &tree[0];
@@ -111,7 +113,7 @@ fn parse_single_complex_class_selector_no_property(
     assert_eq!(rules.len(), 1, "Should have a single rule");
 
     let rule = &rules[0];
-    let tree = rule.selector.get_parent_tree();
+    let tree = rule.selectors[0].get_parent_tree();
     assert_eq!(tree.len(), 1, "Should have a single selector node");
 
     let node = &tree[0];
@@ -146,7 +148,7 @@ fn parse_single_composed_selector_no_property(
     assert_eq!(rules.len(), 1, "Should have a single rule");
 
     let rule = &rules[0];
-    let tree = rule.selector.get_parent_tree();
+    let tree = rule.selectors[0].get_parent_tree();
     assert_eq!(tree.len(), 1, "Should have a single selector node");
 
     let node = &tree[0];
@@ -178,7 +180,7 @@ fn parse_multiple_composed_selector_no_property(
     assert_eq!(rules.len(), 1, "Should have a single rule");
 
     let rule = &rules[0];
-    let tree = rule.selector.get_parent_tree();
+    let tree = rule.selectors[0].get_parent_tree();
     assert_eq!(tree.len(), 7, "Should have a single selector node");
 
     use SelectorElement::*;
@@ -212,6 +214,438 @@ fn parse_multiple_composed_selector_no_property(
     assert!(rule.properties.is_empty(), "Should have no properties");
 }
 
+#[test]
+fn parse_direct_child_selector_no_property(
+    // no args
+) {
+    let rules = StyleSheetParser::parse("a > .b c>#d {}");
+    assert_eq!(rules.len(), 1, "Should have a single rule");
+
+    let rule = &rules[0];
+    let tree = rule.selectors[0].get_combinator_tree();
+    assert_eq!(tree.len(), 4, "Should have 4 selector nodes");
+
+    use SelectorElement::*;
+    let expected: DynArray<(Option<SelectorElement>, SelectorElement)> = smallvec![
+        (None, Component("a".to_string())),
+        (Some(DirectChild), Class("b".to_string())),
+        (Some(Child), Component("c".to_string())),
+        (Some(DirectChild), Name("d".to_string())),
+    ];
+
+    expected.into_iter()
+        .zip(tree)
+        .for_each(|((combinator, expected), (node_combinator, node))|
+        {
+            assert_eq!(combinator.as_ref(), node_combinator);
+            assert_eq!(node.len(), 1, "Should have a single element per node");
+            assert_eq!(expected, *node[0]);
+        });
+}
+
+#[test]
+fn parse_sibling_selector_no_property(
+    // no args
+) {
+    let rules = StyleSheetParser::parse(".a + .b ~ c~#d {}");
+    assert_eq!(rules.len(), 1, "Should have a single rule");
+
+    let rule = &rules[0];
+    let tree = rule.selectors[0].get_combinator_tree();
+    assert_eq!(tree.len(), 4, "Should have 4 selector nodes");
+
+    use SelectorElement::*;
+    let expected: DynArray<(Option<SelectorElement>, SelectorElement)> = smallvec![
+        (None, Class("a".to_string())),
+        (Some(AdjacentSibling), Class("b".to_string())),
+        (Some(GeneralSibling), Component("c".to_string())),
+        (Some(GeneralSibling), Name("d".to_string())),
+    ];
+
+    expected.into_iter()
+        .zip(tree)
+        .for_each(|((combinator, expected), (node_combinator, node))|
+        {
+            assert_eq!(combinator.as_ref(), node_combinator);
+            assert_eq!(node.len(), 1, "Should have a single element per node");
+            assert_eq!(expected, *node[0]);
+        });
+}
+
+#[test]
+fn parse_invalid_combinator_selector(
+    // no args
+) {
+    assert!(
+        StyleSheetParser::parse("> a {}").is_empty(),
+        "Should not accept a leading combinator"
+    );
+    assert!(
+        StyleSheetParser::parse("a > {}").is_empty(),
+        "Should not accept a trailing combinator"
+    );
+    assert!(
+        StyleSheetParser::parse("a > > b {}").is_empty(),
+        "Should not accept consecutive combinators"
+    );
+    assert!(
+        StyleSheetParser::parse("a + ~ b {}").is_empty(),
+        "Should not accept consecutive sibling combinators"
+    );
+}
+
+#[test]
+fn parse_selector_list_single_property(
+    // no args
+) {
+    let rules = StyleSheetParser::parse("h1, .a > b,#c {d: e}");
+    assert_eq!(rules.len(), 1, "Should have a single rule");
+
+    let rule = &rules[0];
+    assert_eq!(rule.selectors.len(), 3, "Should have 3 selectors");
+
+    use SelectorElement::*;
+    let expected: DynArray<DynArray<SelectorElement>> = smallvec![
+        smallvec![Component("h1".to_string())],
+        smallvec![Class("a".to_string()), DirectChild, Component("b".to_string())],
+        smallvec![Name("c".to_string())],
+    ];
+
+    expected.into_iter()
+        .zip(rule.selectors.iter())
+        .for_each(|(expected, selector)|
+        {
+            let elements: DynArray<SelectorElement> = selector.get_combinator_tree()
+                .into_iter()
+                .flat_map(|(combinator, node)| combinator.into_iter().chain(node))
+                .cloned()
+                .collect();
+
+            assert_eq!(expected, elements);
+        });
+
+    assert_eq!(rule.properties.len(), 1, "Should have a single shared property");
+}
+
+#[test]
+fn parse_selector_repeated_across_rules(
+    // no args
+) {
+    use crate::stylesheet::StyleSheetAsset;
+
+    let sheet = StyleSheetAsset::parse("", "h1, h2 { a: b; } h1 { c: d; } h1, h2 { a: b; }");
+    let rules = sheet.iter().collect::<Vec<_>>();
+    assert_eq!(rules.len(), 3);
+
+    let (first, second, third) = (&rules[0].selectors[0], &rules[1].selectors[0], &rules[2].selectors[0]);
+    assert_ne!(first, second, "Should be distinct for each rule");
+    assert_ne!(first, third, "Should be distinct even for identical rules");
+    assert!(first.load_order() < second.load_order());
+    assert_eq!(sheet.get_property_names(first).unwrap(), ["a"]);
+    assert_eq!(sheet.get_property_names(second).unwrap(), ["c"], "Should not be lost by the first rule");
+}
+
+#[test]
+fn parse_invalid_selector_list(
+    // no args
+) {
+    assert!(
+        StyleSheetParser::parse("a, {b: c}").is_empty(),
+        "Should not accept a trailing comma"
+    );
+    assert!(
+        StyleSheetParser::parse("a, > b {b: c}").is_empty(),
+        "Should not accept a single invalid selector on list"
+    );
+}
+
+#[test]
+fn parse_universal_selector_no_property(
+    // no args
+) {
+    let rules = StyleSheetParser::parse("* #a *.b > * {}");
+    assert_eq!(rules.len(), 1, "Should have a single rule");
+
+    let tree = rules[0].selectors[0].get_parent_tree();
+    assert_eq!(tree.len(), 4, "Should have 4 selector nodes");
+
+    use SelectorElement::*;
+    let expected: DynArray<DynArray<SelectorElement>> = smallvec![
+        smallvec![Universal],
+        smallvec![Name("a".to_string())],
+        smallvec![Universal, Class("b".to_string())],
+        smallvec![Universal],
+    ];
+
+    expected.into_iter()
+        .zip(tree)
+        .for_each(|(node_expected, node)|
+        {
+            assert_eq!(node_expected.len(), node.len());
+            node_expected
+                .into_iter()
+                .zip(node)
+                .for_each(|(expected, element)|
+                {
+                    assert_eq!(expected, *element);
+                });
+        });
+}
+
+#[test]
+fn parse_attribute_selector_no_property(
+    // no args
+) {
+    let rules = StyleSheetParser::parse(
+        r#"button[disabled][a=b][c~="d"][e|=f][g^="h"][i$=j][k*="l m"] {}"#
+    );
+    assert_eq!(rules.len(), 1, "Should have a single rule");
+
+    let tree = rules[0].selectors[0].get_parent_tree();
+    assert_eq!(tree.len(), 1, "Should have a single selector node");
+
+    use crate::selector::AttributeMatch;
+    use SelectorElement::*;
+    let expected: DynArray<SelectorElement> = smallvec![
+        Component("button".to_string()),
+        Attribute("disabled".to_string(), AttributeMatch::Exists),
+        Attribute("a".to_string(), AttributeMatch::Equals("b".to_string())),
+        Attribute("c".to_string(), AttributeMatch::Includes("d".to_string())),
+        Attribute("e".to_string(), AttributeMatch::DashMatch("f".to_string())),
+        Attribute("g".to_string(), AttributeMatch::Prefix("h".to_string())),
+        Attribute("i".to_string(), AttributeMatch::Suffix("j".to_string())),
+        Attribute("k".to_string(), AttributeMatch::Substring("l m".to_string())),
+    ];
+
+    assert_eq!(tree[0].len(), expected.len());
+    expected.into_iter()
+        .zip(&tree[0])
+        .for_each(|(expected, element)|
+        {
+            assert_eq!(expected, **element);
+        });
+
+    assert!(StyleSheetParser::parse("a[] {}").is_empty(), "Should not accept an empty attribute");
+    assert!(StyleSheetParser::parse("a[b=] {}").is_empty(), "Should not accept a missing value");
+    assert!(StyleSheetParser::parse("a[b c] {}").is_empty(), "Should not accept a missing operator");
+}
+
+#[test]
+fn parse_selector_specificity(
+    // no args
+) {
+    use crate::selector::Specificity;
+
+    let rules = StyleSheetParser::parse("#id, .a .b .c, button.a[b] > text, * {}");
+    assert_eq!(rules.len(), 1, "Should have a single rule");
+
+    let expected = [
+        Specificity { ids: 1, classes: 0, types: 0 },
+        Specificity { ids: 0, classes: 3, types: 0 },
+        Specificity { ids: 0, classes: 2, types: 2 },
+        Specificity { ids: 0, classes: 0, types: 0 },
+    ];
+
+    let selectors = &rules[0].selectors;
+    assert_eq!(selectors.len(), expected.len());
+    expected.into_iter()
+        .zip(selectors.iter())
+        .for_each(|(expected, selector)|
+        {
+            assert_eq!(expected, selector.specificity(), "Wrong specificity for {selector}");
+        });
+
+    assert!(selectors[0] > selectors[1], "Id should win over many classes");
+    assert!(selectors[1] > selectors[2], "Classes should win over many types");
+    assert!(selectors[2] > selectors[3], "Universal selector should have no specificity");
+}
+
+#[cfg(feature = "pseudo_class")]
+#[test]
+fn parse_structural_pseudo_class_no_property(
+    // no args
+) {
+    use crate::selector::Nth;
+
+    let rules = StyleSheetParser::parse(
+        ".a:first-child:last-child:only-child:empty:nth-child(2n+1):nth-last-child(odd):nth-child( -n + 3 ) {}"
+    );
+    assert_eq!(rules.len(), 1, "Should have a single rule");
+
+    let tree = rules[0].selectors[0].get_parent_tree();
+    assert_eq!(tree.len(), 1, "Should have a single selector node");
+
+    use SelectorElement::*;
+    let expected: DynArray<SelectorElement> = smallvec![
+        Class("a".to_string()),
+        PseudoClass("first-child".to_string()),
+        PseudoClass("last-child".to_string()),
+        PseudoClass("only-child".to_string()),
+        PseudoClass("empty".to_string()),
+        NthChild(Nth { a: 2, b: 1 }),
+        NthLastChild(Nth { a: 2, b: 1 }),
+        NthChild(Nth { a: -1, b: 3 }),
+    ];
+
+    assert_eq!(tree[0].len(), expected.len());
+    expected.into_iter()
+        .zip(&tree[0])
+        .for_each(|(expected, element)|
+        {
+            assert_eq!(expected, **element);
+        });
+
+    assert!(StyleSheetParser::parse("a:nth-child() {}").is_empty(), "Should not accept empty arguments");
+    assert!(StyleSheetParser::parse("a:nth-child(2n+1 b) {}").is_empty(), "Should not accept trailing arguments");
+    assert!(StyleSheetParser::parse("a:unknown(2) {}").is_empty(), "Should not accept unknown functions");
+}
+
+#[cfg(feature = "pseudo_class")]
+#[test]
+fn parse_matching_list_pseudo_class_no_property(
+    // no args
+) {
+    use crate::selector::{Selector, Specificity};
+
+    let rules = StyleSheetParser::parse("button:not(.a, #b):is(.c > text, d), :where(#e .f) {}");
+    assert_eq!(rules.len(), 1, "Should have a single rule");
+
+    let selectors = &rules[0].selectors;
+    assert_eq!(selectors.len(), 2, "Should have two selectors");
+
+    let tree = selectors[0].get_parent_tree();
+    assert_eq!(tree.len(), 1, "Should have a single selector node");
+
+    use SelectorElement::*;
+    let expected: DynArray<SelectorElement> = smallvec![
+        Component("button".to_string()),
+        Not(vec![
+            Selector::new(smallvec![Class("a".to_string())]),
+            Selector::new(smallvec![Name("b".to_string())]),
+        ]),
+        Is(vec![
+            Selector::new(smallvec![Class("c".to_string()), DirectChild, Component("text".to_string())]),
+            Selector::new(smallvec![Component("d".to_string())]),
+        ]),
+    ];
+
+    assert_eq!(tree[0].len(), expected.len());
+    expected.into_iter()
+        .zip(&tree[0])
+        .for_each(|(expected, element)|
+        {
+            assert_eq!(expected, **element);
+        });
+
+    assert_eq!(selectors[0].to_string(), "button:not(.a, #b):is(.c > text, d)");
+    assert_eq!(selectors[0].specificity(), Specificity { ids: 1, classes: 1, types: 2 });
+    assert_eq!(selectors[1].specificity(), Specificity::default(), ":where() should have no specificity");
+
+    assert!(StyleSheetParser::parse("a:not() {}").is_empty(), "Should not accept empty selector lists");
+    assert!(StyleSheetParser::parse("a:is(b >) {}").is_empty(), "Should not accept invalid selectors");
+}
+
+#[cfg(feature = "pseudo_class")]
+#[test]
+fn parse_relational_pseudo_class_no_property(
+    // no args
+) {
+    use crate::selector::{Selector, Specificity};
+
+    let rules = StyleSheetParser::parse(".slot:has(.item.legendary, > #icon, + text ~ button) {}");
+    assert_eq!(rules.len(), 1, "Should have a single rule");
+
+    let selector = &rules[0].selectors[0];
+    let tree = selector.get_parent_tree();
+    assert_eq!(tree.len(), 1, "Should have a single selector node");
+
+    use SelectorElement::*;
+    let expected: DynArray<SelectorElement> = smallvec![
+        Class("slot".to_string()),
+        Has(vec![
+            Selector::new(smallvec![Child, Class("item".to_string()), Class("legendary".to_string())]),
+            Selector::new(smallvec![DirectChild, Name("icon".to_string())]),
+            Selector::new(smallvec![
+                AdjacentSibling,
+                Component("text".to_string()),
+                GeneralSibling,
+                Component("button".to_string()),
+            ]),
+        ]),
+    ];
+
+    assert_eq!(tree[0].len(), expected.len());
+    expected.into_iter()
+        .zip(&tree[0])
+        .for_each(|(expected, element)|
+        {
+            assert_eq!(expected, **element);
+        });
+
+    assert!(selector.is_relational(), "Should be matched again when related entities changes");
+    assert_eq!(selector.to_string(), ".slot:has(.item.legendary, > #icon, + text ~ button)");
+    assert_eq!(selector.specificity(), Specificity { ids: 1, classes: 1, types: 0 });
+
+    assert!(StyleSheetParser::parse("a:has() {}").is_empty(), "Should not accept empty selector lists");
+    assert!(StyleSheetParser::parse("a:has(>) {}").is_empty(), "Should not accept a lone combinator");
+    assert!(StyleSheetParser::parse("a:has(> > b) {}").is_empty(), "Should not accept repeated combinators");
+}
+
+#[cfg(feature = "pseudo_prop")]
+#[test]
+fn parse_pseudo_element_selector_no_property(
+    // no args
+) {
+    use crate::component::PseudoElement;
+
+    let rules = StyleSheetParser::parse(".item::before, .list > text::marker, *::after {}");
+    assert_eq!(rules.len(), 1, "Should have a single rule");
+
+    let selectors = &rules[0].selectors;
+    assert_eq!(selectors.len(), 3, "Should have three selectors");
+
+    let expected = [
+        (".item", PseudoElement::Before),
+        (".list > text", PseudoElement::Marker),
+        ("*", PseudoElement::After),
+    ];
+
+    expected.into_iter()
+        .zip(selectors.iter())
+        .for_each(|((originating, element), selector)|
+        {
+            let (selector_originating, selector_element) = selector.split_pseudo_element()
+                .expect("Should have a pseudo-element");
+
+            assert_eq!(selector_originating.to_string(), originating);
+            assert_eq!(selector_element, element);
+        });
+
+    assert_eq!(selectors[0].to_string(), ".item::before");
+
+    assert!(StyleSheetParser::parse("a::before.b {}").is_empty(), "Should only accept pseudo-elements at the end");
+    assert!(StyleSheetParser::parse("a::before b {}").is_empty(), "Should only accept pseudo-elements at the end");
+    assert!(StyleSheetParser::parse("a::first-line {}").is_empty(), "Should not accept unknown pseudo-elements");
+}
+
+#[cfg(feature = "pseudo_class")]
+#[test]
+fn nth_matches_index(
+    // no args
+) {
+    use crate::selector::Nth;
+
+    let matching = |nth: Nth| (1..=10)
+        .filter(|&index| nth.matches(index))
+        .collect::<Vec<_>>();
+
+    assert_eq!(matching(Nth { a: 2, b: 1 }), vec![1, 3, 5, 7, 9]);
+    assert_eq!(matching(Nth { a: 2, b: 0 }), vec![2, 4, 6, 8, 10]);
+    assert_eq!(matching(Nth { a: 0, b: 3 }), vec![3]);
+    assert_eq!(matching(Nth { a: -1, b: 3 }), vec![1, 2, 3]);
+    assert_eq!(matching(Nth { a: 3, b: -2 }), vec![1, 4, 7, 10]);
+}
+
 #[test]
 fn parse_single_token(
     // no args
@@ -262,7 +696,7 @@ fn parse_multiple_complex_properties(
     use PropertyToken::*;
     let expected = [
         ("b", vec![Identifier("c".to_string())]),
-        ("d", vec![Dimension(0.0)]),
+        ("d", vec![Dimension(0.0, Unit::Px)]),
         ("e", vec![Hash("f".to_string())]),
         (
             "g",
@@ -273,7 +707,7 @@ fn parse_multiple_complex_properties(
             ],
         ),
         ("k-k", vec![Percentage(100.0)]),
-        ("l", vec![Dimension(15.3), Percentage(3.0)]),
+        ("l", vec![Dimension(15.3, Unit::Px), Percentage(3.0)]),
         ("m", vec![Number(12.9)]),
         ("n", vec![String("str".to_string())]),
         (
@@ -286,7 +720,7 @@ fn parse_multiple_complex_properties(
                 String("t".to_string()),
                 Number(1.0),
                 Percentage(45.67),
-                Dimension(33.0),
+                Dimension(33.0, Unit::Px),
             ],
         ),
     ];
@@ -315,7 +749,7 @@ fn parse_multiple_rules(
 
     for rule in rules
     {
-        match rule.selector.get_parent_tree()[0][0]
+        match rule.selectors[0].get_parent_tree()[0][0]
         {
             SelectorElement::Component(a) => assert_eq!(a, "a"),
             _ => panic!("Should have only a single component \"a\""),
@@ -332,3 +766,676 @@ fn parse_multiple_rules(
         }
     }
 }
+
+#[test]
+fn parse_custom_properties(
+    // no args
+) {
+    let rules = StyleSheetParser::parse(
+        r#"a {
+        --accent: #fff;
+        --gap:   10px  5px ;
+        color: var(--accent);
+        margin: calc(var(--gap, 1px) * 2);
+        width: 10px;
+        }"#,
+    );
+
+    assert_eq!(rules.len(), 1, "Should have a single rule");
+    let rule = &rules[0];
+
+    assert_eq!(rule.custom_properties.len(), 2, "Should have 2 custom properties");
+    assert_eq!(rule.custom_properties.get("--accent").unwrap(), "#fff");
+    assert_eq!(rule.custom_properties.get("--gap").unwrap(), "10px  5px");
+
+    assert_eq!(rule.var_properties.len(), 2, "Should have 2 properties referencing custom properties");
+    assert_eq!(rule.var_properties.get("color").unwrap(), "var(--accent)");
+    assert_eq!(rule.var_properties.get("margin").unwrap(), "calc(var(--gap, 1px) * 2)");
+
+    assert_eq!(rule.properties.len(), 1, "Should have a single plain property");
+    assert!(rule.properties.contains_key("width"));
+}
+
+#[test]
+fn resolve_var_functions(
+    // no args
+) {
+    let mut lookup = |name: &str| match name
+    {
+        "--accent" => Some("#fff".to_string()),
+        "--gap" => Some("10px 5px".to_string()),
+        _ => None,
+    };
+
+    assert_eq!(resolve_vars("var(--accent)", &mut lookup).unwrap(), "#fff");
+    assert_eq!(resolve_vars("var(--gap) auto", &mut lookup).unwrap(), "10px 5px auto");
+    assert_eq!(resolve_vars("var(--missing, red)", &mut lookup).unwrap(), "red");
+    assert_eq!(resolve_vars("var(--missing, var(--accent))", &mut lookup).unwrap(), "#fff");
+    assert_eq!(resolve_vars("var(--accent, var(--missing))", &mut lookup).unwrap(), "#fff");
+    assert_eq!(resolve_vars("calc(var(--gap) * 2)", &mut lookup).unwrap(), "calc(10px 5px * 2)");
+
+    assert!(resolve_vars("var(--missing)", &mut lookup).is_none(), "Should fail without a fallback value");
+    assert!(resolve_vars("var(accent, red)", &mut lookup).is_none(), "Should fail without a custom property name");
+
+    use PropertyToken::*;
+    let values = parse_property_values(&resolve_vars("var(--gap) 3%", &mut lookup).unwrap());
+    assert_eq!(values.as_slice(), &[Dimension(10.0, Unit::Px), Dimension(5.0, Unit::Px), Percentage(3.0)]);
+}
+
+#[test]
+fn parse_math_functions(
+    // no args
+) {
+    use crate::property::{LengthContext, LengthValue, MathExpression};
+    use bevy::ui::Val;
+
+    let context = |percent_base| LengthContext { percent_base, ..Default::default() };
+
+    let rules = StyleSheetParser::parse(
+        r#"a {
+        a: calc(100% / 4);
+        b: calc((10px + 5px) * 2);
+        c: min(50%, 25%);
+        d: clamp(10px, 5px, 40px);
+        e: calc(100% - 20px);
+        f: max(10px, 5%);
+        g: calc(2 * 3);
+        h: calc(10px * 5px);
+        i: calc(10px 5px);
+        }"#,
+    );
+
+    assert_eq!(rules.len(), 1, "Should have a single rule");
+    let properties = &rules[0].properties;
+    let get = |name: &str| properties.get(name).unwrap();
+
+    assert_eq!(get("a").val(), Some(Val::Percent(25.0)));
+    assert_eq!(get("b").val(), Some(Val::Px(30.0)));
+    assert_eq!(get("c").val(), Some(Val::Percent(25.0)));
+    assert_eq!(get("d").val(), Some(Val::Px(10.0)));
+    assert_eq!(get("g").f32(), Some(6.0));
+
+    // Mixing percents and dimensions can only be resolved with the size of the parent node
+    assert_eq!(get("e").val(), None);
+    match get("e").length()
+    {
+        Some(LengthValue::Relative(expression)) => {
+            assert_eq!(expression.resolve(&context(200.0)), Some(180.0));
+        }
+        other => panic!("Should be a relative length, found {other:?}"),
+    }
+
+    match &get("f")[0]
+    {
+        PropertyToken::Math(expression @ MathExpression::Max(_)) => {
+            assert_eq!(expression.resolve(&context(100.0)), Some(10.0));
+            assert_eq!(expression.resolve(&context(400.0)), Some(20.0));
+        }
+        other => panic!("Should be a max expression, found {other:?}"),
+    }
+
+    assert!(get("h").length().is_none(), "Should not multiply two dimensions");
+    assert!(!properties.contains_key("i"), "Should drop an invalid expression");
+}
+
+#[test]
+fn parse_length_units(
+    // no args
+) {
+    use crate::property::{LengthContext, LengthValue};
+    use bevy::{prelude::Vec2, ui::Val};
+
+    let rules = StyleSheetParser::parse(
+        r#"a {
+        a: 10vw;
+        b: 20VH;
+        c: 5vmin 6vmax;
+        d: 2em;
+        e: calc(1rem + 10px);
+        f: 10furlongs;
+        g: calc(100% - 10qux);
+        h: calc(50vw - 50%);
+        }"#,
+    );
+
+    assert_eq!(rules.len(), 1, "Should have a single rule");
+    let properties = &rules[0].properties;
+    let get = |name: &str| properties.get(name).unwrap();
+
+    assert_eq!(get("a").val(), Some(Val::Vw(10.0)));
+    assert_eq!(get("b").val(), Some(Val::Vh(20.0)));
+    assert_eq!(get("c").rect().map(|rect| (rect.top, rect.right)), Some((Val::VMin(5.0), Val::VMax(6.0))));
+
+    let context = LengthContext {
+        percent_base: 200.0,
+        viewport: Vec2::new(800.0, 600.0),
+        font_size: 20.0,
+        root_font_size: 16.0,
+    };
+
+    // Font relative units can only be resolved at layout
+    assert_eq!(get("d").val(), None);
+    for (name, expected) in [("d", 40.0), ("e", 26.0), ("h", 300.0)]
+    {
+        match get(name).length()
+        {
+            Some(LengthValue::Relative(expression)) => assert_eq!(expression.resolve(&context), Some(expected)),
+            other => panic!("Should be a relative length, found {other:?}"),
+        }
+    }
+
+    assert!(!properties.contains_key("f"), "Should reject an unknown unit");
+    assert!(!properties.contains_key("g"), "Should reject an unknown unit inside a math function");
+}
+
+#[test]
+fn parse_color_functions(
+    // no args
+) {
+    let rules = StyleSheetParser::parse(
+        r#"a {
+        a: rgb(255 0 0);
+        b: rgba(255, 0, 0, 0.5);
+        c: rgb(100% 50% 0% / 25%);
+        d: hsl(120 100% 50%);
+        e: hsla(120deg, 100%, 25%, 0.5);
+        f: hwb(0.5turn 50% 50%);
+        g: lab(100 0 0);
+        h: lch(50 0 0 / 50%);
+        i: oklch(62.8% 0.2577 29.23);
+        j: oklab(0 none none);
+        k: lab(1, 2, 3);
+        l: hsl(10% 50% 50%);
+        m: rgb(1 2);
+        }"#,
+    );
+
+    assert_eq!(rules.len(), 1, "Should have a single rule");
+    let properties = &rules[0].properties;
+
+    let expected = [
+        ("a", [1.0, 0.0, 0.0, 1.0]),
+        ("b", [1.0, 0.0, 0.0, 0.5]),
+        ("c", [1.0, 0.5, 0.0, 0.25]),
+        ("d", [0.0, 1.0, 0.0, 1.0]),
+        ("e", [0.0, 0.5, 0.0, 0.5]),
+        ("f", [0.5, 0.5, 0.5, 1.0]),
+        ("g", [1.0, 1.0, 1.0, 1.0]),
+        ("h", [0.466, 0.466, 0.466, 0.5]),
+        ("i", [1.0, 0.0, 0.0, 1.0]),
+        ("j", [0.0, 0.0, 0.0, 1.0]),
+    ];
+
+    for (name, expected) in expected
+    {
+        let color = properties.get(name)
+            .and_then(|values| values.color())
+            .unwrap_or_else(|| panic!("Should parse {name} as a color"))
+            .as_rgba_f32();
+
+        assert!(
+            color.iter().zip(expected).all(|(channel, expected)| (channel - expected).abs() < 0.01),
+            "{name}: expected {expected:?}, found {color:?}"
+        );
+    }
+
+    for name in ["k", "l", "m"]
+    {
+        assert!(!properties.contains_key(name), "Should reject invalid color function {name}");
+    }
+}
+
+#[test]
+fn parse_derived_colors(
+    // no args
+) {
+    let rules = StyleSheetParser::parse(
+        r#"a {
+        a: color-mix(in srgb, red, blue);
+        b: color-mix(in srgb, red 70%, black);
+        c: color-mix(in srgb, 25% red, blue 25%);
+        d: color-mix(in oklab, white, black);
+        e: color-mix(in hsl, red, white);
+        f: color-mix(in hsl shorter hue, hsl(350 100% 50%), hsl(10 100% 50%));
+        g: rgb(from red r g b / 50%);
+        h: hsl(from rgb(255 0 0) h s calc(l - 25));
+        i: rgb(from #00000080 r g 255);
+        j: color-mix(in foo, red, blue);
+        k: color-mix(in srgb, red 0%, blue 0%);
+        l: rgb(from red x g b);
+        }"#,
+    );
+
+    assert_eq!(rules.len(), 1, "Should have a single rule");
+    let properties = &rules[0].properties;
+
+    let expected = [
+        ("a", [0.5, 0.0, 0.5, 1.0]),
+        ("b", [0.7, 0.0, 0.0, 1.0]),
+        ("c", [0.5, 0.0, 0.5, 0.5]),
+        ("d", [0.389, 0.389, 0.389, 1.0]),
+        ("e", [0.875, 0.625, 0.625, 1.0]),
+        ("f", [1.0, 0.0, 0.0, 1.0]),
+        ("g", [1.0, 0.0, 0.0, 0.5]),
+        ("h", [0.5, 0.0, 0.0, 1.0]),
+        ("i", [0.0, 0.0, 1.0, 0.5]),
+    ];
+
+    for (name, expected) in expected
+    {
+        let color = properties.get(name)
+            .and_then(|values| values.color())
+            .unwrap_or_else(|| panic!("Should parse {name} as a color"))
+            .as_rgba_f32();
+
+        assert!(
+            color.iter().zip(expected).all(|(channel, expected)| (channel - expected).abs() < 0.01),
+            "{name}: expected {expected:?}, found {color:?}"
+        );
+    }
+
+    for name in ["j", "k", "l"]
+    {
+        assert!(!properties.contains_key(name), "Should reject invalid color {name}");
+    }
+
+    // Operands are usually custom properties, which are replaced before the color is parsed
+    let mut lookup = |name: &str| (name == "--accent").then(|| "rgb(0 0 255)".to_string());
+    let values = parse_property_values(&resolve_vars("color-mix(in srgb, var(--accent) 50%, rgb(from var(--accent) b g r))", &mut lookup).unwrap());
+    let color = values.color().expect("Should parse a color from custom properties").as_rgba_f32();
+    assert!((color[0] - 0.5).abs() < 0.01 && (color[2] - 0.5).abs() < 0.01, "Found {color:?}");
+}
+
+#[test]
+fn parse_important(
+    // no args
+) {
+    let rules = StyleSheetParser::parse(
+        r#"a {
+        a: red !important;
+        b: 10px;
+        c: 5px ! IMPORTANT;
+        --d: blue !important;
+        e: 1px !important;
+        e: 2px;
+        f: 1px;
+        f: var(--d);
+        g: 1px !importantly;
+        }"#,
+    );
+
+    assert_eq!(rules.len(), 1, "Should have a single rule");
+    let rule = &rules[0];
+
+    let mut important = rule.important.iter().map(|name| name.as_str()).collect::<Vec<_>>();
+    important.sort();
+    assert_eq!(important, ["--d", "a", "c", "e"]);
+
+    use PropertyToken::*;
+    assert_eq!(rule.properties.get("a").unwrap().as_slice(), &[Identifier("red".to_string())]);
+    assert_eq!(rule.properties.get("c").unwrap().as_slice(), &[Dimension(5.0, Unit::Px)]);
+    assert_eq!(rule.custom_properties.get("--d").unwrap(), "blue");
+
+    // Important declarations wins over later normal ones, otherwise the last one wins
+    assert_eq!(rule.properties.get("e").unwrap().as_slice(), &[Dimension(1.0, Unit::Px)]);
+    assert!(!rule.properties.contains_key("f"), "Should be replaced by the var() declaration");
+    assert_eq!(rule.var_properties.get("f").unwrap(), "var(--d)");
+
+    assert!(!rule.properties.contains_key("g"), "Should reject an invalid importance");
+}
+
+#[test]
+fn parse_imports(
+    // no args
+) {
+    let sheet = StyleSheetParser::parse_sheet(
+        r#"
+        @import "common.css";
+        @import url(../shared/base.css);
+        @import url("theme.css") screen;
+        @import;
+        a { b: c; }
+        @import "late.css";
+        "#,
+    );
+
+    assert_eq!(sheet.rules.len(), 1, "Should still parse rules after imports");
+    assert_eq!(sheet.imports, ["common.css", "../shared/base.css"], "Should skip invalid imports and imports after rules");
+}
+
+#[test]
+fn merge_imports(
+    // no args
+) {
+    use crate::stylesheet::StyleSheetAsset;
+
+    let mut sheet = StyleSheetAsset::parse("", r#"@import "base.css"; .a { color: blue; } .b { width: 1px; }"#);
+    let base = StyleSheetAsset::parse("", ".a { color: red !important; width: 10px; height: 5px; } .a { height: 1px; } .c { width: 2px; }");
+    assert_eq!(sheet.imports(), ["base.css"]);
+
+    let hash = sheet.hash();
+    sheet.merge_imports(vec![base]);
+    assert_ne!(sheet.hash(), hash, "Should change the hash, so cached values are parsed again");
+
+    let rules = sheet.iter().collect::<Vec<_>>();
+    assert_eq!(rules.len(), 5, "Should have imported rules");
+    assert!(rules[2].selectors[0].load_order() < rules[3].selectors[0].load_order(), "Should sort imported rules before the importing ones");
+    assert!(rules[3].selectors[0].load_order() < rules[4].selectors[0].load_order());
+
+    // The same selector on many rules is distinct for each one, so they're sorted by the cascade
+    let (imported, importing) = (&rules[0].selectors[0], &rules[3].selectors[0]);
+    assert_ne!(imported, importing);
+    assert_ne!(imported, &rules[1].selectors[0]);
+    assert_eq!(sheet.get_property_names(importing).unwrap(), ["color"]);
+
+    let mut names = sheet.get_property_names(imported).unwrap();
+    names.sort();
+    assert_eq!(names, ["color", "height", "width"]);
+
+    use PropertyToken::*;
+    assert_eq!(sheet.get_property_value(importing, "color").unwrap().as_slice(), &[Identifier("blue".to_string())]);
+    assert!(!sheet.is_important(importing, "color"));
+    assert_eq!(sheet.get_property_value(imported, "color").unwrap().as_slice(), &[Identifier("red".to_string())]);
+    assert!(sheet.is_important(imported, "color"));
+    assert_eq!(sheet.get_property_value(imported, "height").unwrap().as_slice(), &[Dimension(5.0, Unit::Px)]);
+    assert_eq!(sheet.get_property_value(&rules[1].selectors[0], "height").unwrap().as_slice(), &[Dimension(1.0, Unit::Px)]);
+}
+
+#[test]
+fn parse_media_queries(
+    // no args
+) {
+    use crate::stylesheet::MediaContext;
+    use bevy::prelude::Vec2;
+
+    let rules = StyleSheetParser::parse(
+        r#"
+        a { b: c; }
+        @media (min-width: 1280px) and (orientation: landscape) {
+            a { b: d; }
+            @media only screen and (aspect-ratio > 2) { e {} }
+        }
+        @media (400px <= width < 50em), (resolution: 2x) { f {} }
+        @media screen and (platform: steam-deck), (ui-scale >= 2), (touch) { g {} }
+        @media (min-width: 10qu), (unknown), not all and (max-height: 100vh) { h {} }
+        @media { i {} }
+        "#,
+    );
+
+    assert_eq!(rules.len(), 7, "Should flatten the rules of all @media rules");
+    assert!(rules[0].media.is_empty());
+    assert_eq!(rules[1].media.len(), 1);
+    assert_eq!(rules[2].media.len(), 2, "Should keep the queries of nested @media rules");
+    assert_eq!(rules[1].media[0].to_string(), "(min-width: 1280px) and (orientation: landscape)");
+    assert_ne!(rules[0].selectors[0], rules[1].selectors[0], "Should restrict the selectors to the @media rule");
+
+    let context = |width: f32, height: f32, scale_factor: f32, features: &[(&str, &str)]| MediaContext {
+        viewport: Vec2::new(width, height),
+        scale_factor,
+        root_font_size: 16.0,
+        custom_features: features.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect(),
+    };
+    let matching = |context: &MediaContext| rules.iter()
+        .filter(|rule| rule.matches_media(context))
+        .map(|rule| rule.selectors[0].to_string())
+        .collect::<Vec<_>>();
+
+    assert_eq!(matching(&context(1920.0, 1080.0, 1.0, &[])), ["a", "a", "i"]);
+    assert_eq!(matching(&context(2560.0, 1080.0, 1.0, &[])), ["a", "a", "e", "i"]);
+    assert_eq!(matching(&context(720.0, 1280.0, 1.0, &[])), ["a", "f", "i"]);
+    assert_eq!(matching(&context(800.0, 1280.0, 1.0, &[])), ["a", "i"], "Should resolve em lengths with the root font size");
+    assert_eq!(matching(&context(300.0, 200.0, 2.0, &[])), ["a", "f", "i"]);
+    assert_eq!(matching(&context(300.0, 200.0, 1.0, &[("platform", "steam-deck")])), ["a", "g", "i"]);
+    assert_eq!(matching(&context(300.0, 200.0, 1.0, &[("ui-scale", "2.5")])), ["a", "g", "i"]);
+    assert_eq!(matching(&context(300.0, 200.0, 1.0, &[("touch", "1")])), ["a", "g", "i"]);
+    assert_eq!(matching(&context(300.0, 200.0, 1.0, &[("touch", "none"), ("ui-scale", "1")])), ["a", "i"]);
+}
+
+#[test]
+fn parse_keyframes(
+    // no args
+) {
+    use crate::property::{StepPosition, TimingFunction};
+
+    let sheet = StyleSheetParser::parse_sheet(
+        r#"
+        @keyframes slide {
+            to { left: 100px; background-color: blue; }
+            from { left: 0px; background-color: red; display: flex; animation-timing-function: linear; }
+            50% { width: 50%; display: none; }
+            0%, 100% { width: 10px; height: 1px !important; }
+            150% { left: 3px; }
+        }
+        @keyframes "fade" { to { opacity: 1; } }
+        @keyframes none { to {} }
+        @media (min-width: 10px) { @keyframes late { to {} } }
+        a { animation-duration: 300ms; animation-timing-function: steps(4, jump-start); }
+        "#,
+    );
+
+    assert_eq!(sheet.rules.len(), 1);
+    assert!(sheet.keyframes.contains_key("fade"), "Should accept quoted names");
+    assert!(!sheet.keyframes.contains_key("none"), "Should reject the `none` name");
+    assert!(!sheet.keyframes.contains_key("late"), "Should ignore keyframes inside @media");
+
+    let keyframes = &sheet.keyframes["slide"];
+    let offsets = keyframes.frames.iter().map(|frame| frame.offset).collect::<Vec<_>>();
+    assert_eq!(offsets, [0.0, 0.5, 1.0], "Should sort and merge keyframes, ignoring invalid offsets");
+    assert_eq!(keyframes.frames[0].timing_function, Some(TimingFunction::Linear));
+    assert!(!keyframes.frames[0].properties.contains_key("height"), "Should ignore important declarations");
+
+    use PropertyToken::*;
+    let sample = |name: &str, progress: f32| keyframes.sample(name, progress, TimingFunction::Linear).unwrap().0.into_vec();
+    assert_eq!(sample("left", 0.25), [Dimension(25.0, Unit::Px)]);
+    assert_eq!(sample("left", 1.5), [Dimension(100.0, Unit::Px)], "Should hold the last value");
+    assert_eq!(sample("display", 0.2), [Identifier("flex".to_string())]);
+    assert_eq!(sample("display", 0.3), [Identifier("none".to_string())], "Should flip discrete values at half");
+    assert!(matches!(sample("width", 0.25)[0], Math(_)), "Should keep lengths of different units as calc()");
+
+    let [Color(color)] = sample("background-color", 0.5)[..]
+    else
+    {
+        panic!("Should interpolate colors");
+    };
+    assert_eq!(Some(color), parse_property_values("color-mix(in oklab, red, blue)").color(), "Should mix colors on Oklab");
+
+    let rule = &sheet.rules[0];
+    assert_eq!(rule.properties["animation-duration"].time(), Some(0.3));
+    assert_eq!(rule.properties["animation-timing-function"].timing_function(), Some(TimingFunction::Steps(4, StepPosition::JumpStart)));
+
+    assert_eq!(TimingFunction::Steps(4, StepPosition::JumpStart).ease(0.0), 0.25);
+    assert_eq!(TimingFunction::Steps(4, StepPosition::JumpEnd).ease(0.99), 0.75);
+    assert!((TimingFunction::from_name("ease-in").unwrap().ease(0.5) - 0.3153).abs() < 0.001);
+}
+
+#[test]
+fn parse_animation_lists(
+    // no args
+) {
+    use crate::property::{
+        impls::animation::{AnimationDurationProperty, AnimationNameProperty, AnimationTimingFunctionProperty},
+        Property,
+    };
+
+    assert_eq!(AnimationNameProperty::parse(&parse_property_values("fade")).unwrap(), Some("fade".to_string()));
+    assert!(AnimationNameProperty::parse(&parse_property_values("fade, slide")).is_err(), "Should reject lists of animations");
+    assert!(AnimationDurationProperty::parse(&parse_property_values("1s, 2s")).is_err());
+    assert!(
+        AnimationTimingFunctionProperty::parse(&parse_property_values("cubic-bezier(0.1, 0.7, 1, 0.1)")).is_ok(),
+        "Should accept commas inside functions"
+    );
+}
+
+#[test]
+fn animation_progress(
+    // no args
+) {
+    use crate::component::{Animation, AnimationDirection, AnimationFillMode};
+
+    let progress = |elapsed: f32, animation: &Animation| Animation { elapsed, ..animation.clone() }.progress();
+
+    let animation = Animation { name: Some("a".to_string()), duration: 2.0, delay: 1.0, iteration_count: 2.0, ..Default::default() };
+    assert_eq!(progress(0.5, &animation), None, "Should wait the delay without a backwards fill mode");
+    assert_eq!(progress(2.0, &animation), Some(0.5));
+    assert_eq!(progress(4.0, &animation), Some(0.5), "Should repeat the animation");
+    assert_eq!(progress(5.0, &animation), None, "Should stop without a forwards fill mode");
+
+    let animation = Animation { direction: AnimationDirection::Alternate, fill_mode: AnimationFillMode::Both, ..animation };
+    assert_eq!(progress(0.5, &animation), Some(0.0));
+    assert_eq!(progress(4.0, &animation), Some(0.5));
+    assert_eq!(progress(3.5, &animation), Some(0.75), "Should play odd iterations backwards");
+    assert_eq!(progress(9.0, &animation), Some(0.0), "Should hold the end of the last iteration");
+
+    let animation = Animation { iteration_count: 1.5, direction: AnimationDirection::Reverse, ..animation };
+    assert_eq!(progress(9.0, &animation), Some(0.5), "Should hold partial iterations");
+
+    let animation = Animation { iteration_count: f32::INFINITY, delay: -1.0, ..animation };
+    assert_eq!(progress(1000.0, &animation), Some(0.5), "Should start partway with negative delays");
+    assert_eq!(Animation { name: None, ..animation }.progress(), None);
+}
+
+#[test]
+fn parse_transitions(
+    // no args
+) {
+    use crate::{
+        component::Transition,
+        property::{impls::transition::TransitionProperty, Property, TimingFunction},
+    };
+
+    let parse = |value: &str| TransitionProperty::parse(&parse_property_values(value));
+
+    assert_eq!(
+        parse("background-color 150ms ease-out, width 300ms cubic-bezier(0.1, 0.7, 1, 0.1) 1s").unwrap(),
+        [
+            Transition {
+                property: "background-color".to_string(),
+                duration: 0.15,
+                timing_function: TimingFunction::from_name("ease-out").unwrap(),
+                delay: 0.0,
+            },
+            Transition {
+                property: "width".to_string(),
+                duration: 0.3,
+                timing_function: TimingFunction::CubicBezier(0.1, 0.7, 1.0, 0.1),
+                delay: 1.0,
+            },
+        ]
+    );
+    assert_eq!(parse("300ms").unwrap()[0].property, "all", "Should transition all properties without a name");
+    assert!(parse("none").unwrap().is_empty());
+    assert!(parse("width 1s 2s 3s").is_err(), "Should reject more than two times");
+    assert!(parse("width -1s").is_err(), "Should reject negative durations");
+    assert!(parse("width 10px").is_err());
+    assert!(parse("width height 1s").is_err(), "Should reject many properties on a single transition");
+    assert!(parse("width 1s,").is_err(), "Should reject empty transitions");
+    assert_eq!(parse("1s, height 2s").unwrap()[1].duration, 2.0);
+    assert_eq!(
+        parse("width 1s, height 2s ease-in").unwrap(),
+        [
+            Transition { property: "width".to_string(), duration: 1.0, ..Default::default() },
+            Transition {
+                property: "height".to_string(),
+                duration: 2.0,
+                timing_function: TimingFunction::from_name("ease-in").unwrap(),
+                delay: 0.0,
+            },
+        ]
+    );
+}
+
+#[test]
+fn property_transitions(
+    // no args
+) {
+    use crate::{
+        component::Transition,
+        property::{Interpolate, LengthValue, PropertyTransitions, TimingFunction},
+    };
+    use bevy::{prelude::Entity, ui::Val};
+
+    let px = |value: f32| LengthValue::Val(Val::Px(value));
+    assert_eq!(px(10.0).interpolate(&px(20.0), 0.25), px(12.5));
+    assert!(matches!(px(10.0).interpolate(&LengthValue::Val(Val::Percent(50.0)), 0.5), LengthValue::Relative(_)));
+    assert_eq!(LengthValue::Val(Val::Auto).interpolate(&px(20.0), 0.4), LengthValue::Val(Val::Auto));
+
+    let entity = Entity::from_raw(1);
+    let transition = Transition {
+        property: "all".to_string(),
+        duration: 1.0,
+        timing_function: TimingFunction::Linear,
+        delay: 0.5,
+    };
+
+    let mut transitions = PropertyTransitions::<f32>::default();
+    transitions.set_target(entity, 0.0, Some(&transition));
+    assert!(transitions.advance(0.5).is_empty(), "Should not transition the first value");
+
+    transitions.set_target(entity, 10.0, Some(&transition));
+    let values = (0..5).map(|_| transitions.advance(0.5)).collect::<Vec<_>>();
+    assert_eq!(values[0], [(entity, 0.0)], "Should start from the current value");
+    assert_eq!(values[1], [(entity, 0.0)], "Should wait the delay");
+    assert_eq!(values[2], [(entity, 5.0)]);
+    assert_eq!(values[3], [(entity, 10.0)]);
+    assert!(values[4].is_empty(), "Should stop at the target");
+
+    transitions.set_target(entity, 20.0, Some(&transition));
+    transitions.advance(1.0);
+    transitions.set_target(entity, 0.0, None);
+    assert!(transitions.advance(0.5).is_empty(), "Should snap to values without a transition");
+    transitions.set_target(entity, 10.0, Some(&Transition { delay: 0.0, ..transition }));
+    assert_eq!(transitions.advance(0.5), [(entity, 0.0)], "Should start from the snapped value");
+}
+
+#[test]
+fn parse_font_faces(
+    // no args
+) {
+    use crate::{
+        property::{text::{FontFamilyProperty, FontWeightProperty}, Property},
+        stylesheet::FontFace,
+    };
+
+    let sheet = StyleSheetParser::parse_sheet(
+        r#"
+        @font-face { font-family: "Fira"; src: url("fonts/FiraSans-Bold.ttf"); font-weight: 700; }
+        @font-face { font-family: Fira; src: local("Fira Sans"), url(fonts/FiraSans-Regular.ttf) format("truetype"); }
+        @font-face { font-family: Fira Mono; src: url("fonts/FiraMono.ttf"); font-weight: 900 300; font-display: swap; }
+        @font-face { font-family: "Missing"; }
+        @media (min-width: 10px) { @font-face { font-family: "Late"; src: url("late.ttf"); } }
+        @import "late.css";
+        "#,
+    );
+
+    assert!(sheet.imports.is_empty(), "Should ignore imports after @font-face");
+    assert_eq!(
+        sheet.font_faces,
+        [
+            FontFace { family: "Fira".to_string(), path: "fonts/FiraSans-Bold.ttf".to_string(), weight: (700.0, 700.0) },
+            FontFace { family: "Fira".to_string(), path: "fonts/FiraSans-Regular.ttf".to_string(), weight: (400.0, 400.0) },
+            FontFace { family: "Fira Mono".to_string(), path: "fonts/FiraMono.ttf".to_string(), weight: (300.0, 900.0) },
+        ],
+        "Should skip local fonts, swap reversed weights and ignore font-faces without src or inside @media"
+    );
+
+    let find = |family: &str, weight: f32| FontFace::find(&sheet.font_faces, family, weight).map(|face| face.path.as_str());
+    assert_eq!(find("fira", 700.0), Some("fonts/FiraSans-Bold.ttf"), "Should match families ignoring case");
+    assert_eq!(find("Fira", 800.0), Some("fonts/FiraSans-Bold.ttf"));
+    assert_eq!(find("Fira", 600.0), Some("fonts/FiraSans-Bold.ttf"), "Should prefer bolder fonts above 500");
+    assert_eq!(find("Fira", 450.0), Some("fonts/FiraSans-Regular.ttf"), "Should prefer lighter fonts between 400 and 500");
+    assert_eq!(find("Fira", 100.0), Some("fonts/FiraSans-Regular.ttf"));
+    assert_eq!(find("Fira Mono", 500.0), Some("fonts/FiraMono.ttf"));
+    assert_eq!(find("serif", 400.0), None);
+
+    let families = FontFamilyProperty::parse(&parse_property_values(r#""Fira Sans", Fira Mono, serif"#)).unwrap();
+    assert_eq!(families, ["Fira Sans", "Fira Mono", "serif"]);
+    assert!(FontFamilyProperty::parse(&parse_property_values("Fira, 10px")).is_err());
+    assert!(FontFamilyProperty::parse(&parse_property_values("Fira,")).is_err(), "Should reject empty families");
+
+    let weight = |value: &str| FontWeightProperty::parse(&parse_property_values(value)).ok();
+    assert_eq!(weight("bold"), Some(700.0));
+    assert_eq!(weight("normal"), Some(400.0));
+    assert_eq!(weight("350"), Some(350.0));
+    assert_eq!(weight("1200"), None);
+    assert_eq!(weight("bolder"), None);
+}
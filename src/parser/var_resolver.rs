@@ -0,0 +1,90 @@
+use cssparser::{
+    ParseError, Parser, ParserInput,
+    Token,
+};
+
+/// Looks up the resolved value of a custom property by its name, like `--accent`.
+pub(crate) type CustomPropertyLookup<'a> = dyn FnMut(&str) -> Option<String> + 'a;
+
+/// Replaces every `var()` function on a raw property value, like `var(--accent, #fff)`, by the value of the custom property.
+///
+/// When the custom property isn't found, the fallback value is used instead.
+/// If there is no fallback value, [`None`] is returned since the whole value is invalid.
+pub(crate) fn resolve_vars(
+    content: &str,
+    lookup: &mut CustomPropertyLookup,
+) -> Option<String> {
+    let mut input = ParserInput::new(content);
+    let mut parser = Parser::new(&mut input);
+    let mut output = String::with_capacity(content.len());
+
+    substitute_vars(&mut parser, lookup, &mut output)
+        .ok()
+        .map(|_| output.trim().to_string())
+}
+
+/// Copies every token to the `output`, replacing `var()` functions, including nested ones like `calc(var(--gap) * 2)`.
+fn substitute_vars<'i>(
+    input: &mut Parser<'i, '_>,
+    lookup: &mut CustomPropertyLookup,
+    output: &mut String,
+) -> Result<(), ParseError<'i, ()>> {
+    loop
+    {
+        let start = input.position();
+        let token = match input.next_including_whitespace()
+        {
+            Ok(token) => token.clone(),
+            Err(_) => break Ok(()),
+        };
+
+        let closing = match token
+        {
+            Token::Function(name) if name.eq_ignore_ascii_case("var") => {
+                let value = input.parse_nested_block(|input| resolve_var(input, lookup))?;
+                output.push_str(&value);
+                continue;
+            }
+
+            Token::Function(_) | Token::ParenthesisBlock => ')',
+            Token::SquareBracketBlock => ']',
+            Token::CurlyBracketBlock => '}',
+
+            _ => {
+                output.push_str(input.slice_from(start));
+                continue;
+            }
+        };
+
+        output.push_str(input.slice_from(start));
+        input.parse_nested_block(|input| substitute_vars(input, lookup, output))?;
+        output.push(closing);
+    }
+}
+
+/// Resolves the arguments of a single `var()` function, like `--accent, #fff`.
+fn resolve_var<'i>(
+    input: &mut Parser<'i, '_>,
+    lookup: &mut CustomPropertyLookup,
+) -> Result<String, ParseError<'i, ()>> {
+    let name = input.expect_ident()?.clone();
+
+    if !name.starts_with("--")
+    {
+        return Err(input.new_custom_error(()));
+    }
+
+    // The fallback value is ignored when the custom property is found, but it still must be consumed
+    if let Some(value) = lookup(&name)
+    {
+        while input.next().is_ok() {}
+        return Ok(value);
+    }
+
+    input.expect_comma()?;
+
+    let mut fallback = String::new();
+    substitute_vars(input, lookup, &mut fallback)?;
+
+    Ok(fallback.trim().to_string())
+}
@@ -1,6 +1,7 @@
 use crate::{
     prelude::{
         Class,
+        CustomProperties,
         StyleSheet,
     },
     property::{
@@ -8,17 +9,35 @@ use crate::{
         StyleSheetState,
     },
     stylesheet::{
+        MediaContext,
         StyleSheetAsset,
         StyleSheetLoader,
     },
     system::{
         self,
-        ComponentFilterRegistry, PrepareParams,
+        AttributeFilterRegistry,
+        ComponentFilterRegistry,
+        CustomMediaFeatures,
+        PrepareParams,
+        RootFontSize,
     },
+    RegisterAttributeSelector,
     RegisterComponentSelector,
     RegisterProperty,
 };
 
+#[cfg(feature = "pseudo_class")]
+use crate::{
+    prelude::{
+        Checked,
+        Disabled,
+        Focusable,
+        FocusState,
+    },
+    system::PseudoClassFilterRegistry,
+    RegisterPseudoClass,
+};
+
 use bevy::prelude::*;
 
 /// Plugin which add all types, assets, systems and internal resources needed by `tomt_bevycss`.
@@ -49,6 +68,27 @@ impl BevyCssPlugin
         app.register_component_selector::<Interaction>("interaction");
     }
 
+    fn register_attribute_selector(
+        app: &mut bevy::prelude::App
+    ) {
+        app.register_attribute_selector::<Text>("value", |text| Some(
+            text.sections.iter()
+                .map(|section| section.value.as_str())
+                .collect()
+        ));
+    }
+
+    #[cfg(feature = "pseudo_class")]
+    fn register_pseudo_class(
+        app: &mut bevy::prelude::App
+    ) {
+        app.register_pseudo_class::<Interaction>("hover", |interaction| *interaction == Interaction::Hovered);
+        app.register_pseudo_class::<Interaction>("active", |interaction| *interaction == Interaction::Pressed);
+
+        // Non-standard alias of `:active`, kept for compatibility
+        app.register_pseudo_class::<Interaction>("click", |interaction| *interaction == Interaction::Pressed);
+    }
+
     fn register_properties(
         app: &mut bevy::prelude::App
     ) {
@@ -66,37 +106,57 @@ impl BevyCssPlugin
         app.register_property::<OverflowXProperty>();
         app.register_property::<OverflowYProperty>();
 
-        app.register_property::<LeftProperty>();
-        app.register_property::<RightProperty>();
-        app.register_property::<TopProperty>();
-        app.register_property::<BottomProperty>();
-        app.register_property::<WidthProperty>();
-        app.register_property::<HeightProperty>();
-        app.register_property::<MinWidthProperty>();
-        app.register_property::<MinHeightProperty>();
-        app.register_property::<MaxWidthProperty>();
-        app.register_property::<MaxHeightProperty>();
-        app.register_property::<FlexBasisProperty>();
-        app.register_property::<FlexGrowProperty>();
-        app.register_property::<FlexShrinkProperty>();
-        app.register_property::<AspectRatioProperty>();
-
-        app.register_property::<MarginProperty>();
-        app.register_property::<PaddingProperty>();
-        app.register_property::<BorderProperty>();
+        app.register_interpolated_property::<LeftProperty>();
+        app.register_interpolated_property::<RightProperty>();
+        app.register_interpolated_property::<TopProperty>();
+        app.register_interpolated_property::<BottomProperty>();
+        app.register_interpolated_property::<WidthProperty>();
+        app.register_interpolated_property::<HeightProperty>();
+        app.register_interpolated_property::<MinWidthProperty>();
+        app.register_interpolated_property::<MinHeightProperty>();
+        app.register_interpolated_property::<MaxWidthProperty>();
+        app.register_interpolated_property::<MaxHeightProperty>();
+        app.register_interpolated_property::<FlexBasisProperty>();
+        app.register_interpolated_property::<FlexGrowProperty>();
+        app.register_interpolated_property::<FlexShrinkProperty>();
+        app.register_interpolated_property::<AspectRatioProperty>();
+
+        app.register_interpolated_property::<MarginProperty>();
+        app.register_interpolated_property::<PaddingProperty>();
+        app.register_interpolated_property::<BorderProperty>();
 
         {
             use property::text::*;
 
-            app.register_property::<FontColorProperty>();
+            app.register_interpolated_property::<FontColorProperty>();
             app.register_property::<FontProperty>();
-            app.register_property::<FontSizeProperty>();
+            app.register_property::<FontFamilyProperty>();
+            app.register_property::<FontWeightProperty>();
+            app.register_interpolated_property::<FontSizeProperty>();
             app.register_property::<TextAlignProperty>();
             app.register_property::<TextContentProperty>();
+
+            #[cfg(feature = "pseudo_prop")]
+            app.register_property::<ContentProperty>();
+        }
+
+        {
+            use property::impls::animation::*;
+
+            app.register_property::<AnimationNameProperty>();
+            app.register_property::<AnimationDurationProperty>();
+            app.register_property::<AnimationTimingFunctionProperty>();
+            app.register_property::<AnimationDelayProperty>();
+            app.register_property::<AnimationIterationCountProperty>();
+            app.register_property::<AnimationDirectionProperty>();
+            app.register_property::<AnimationFillModeProperty>();
         }
 
+        use property::impls::transition::TransitionProperty;
+        app.register_property::<TransitionProperty>();
+
         use property::impls::BackgroundColorProperty;
-        app.register_property::<BackgroundColorProperty>();
+        app.register_interpolated_property::<BackgroundColorProperty>();
     }
 }
 
@@ -109,16 +169,31 @@ for BevyCssPlugin
     ) {
         // Type registration
         app.register_type::<Class>()
+            .register_type::<CustomProperties>()
             .register_type::<StyleSheet>();
 
+        #[cfg(feature = "pseudo_prop")]
+        app.register_type::<crate::prelude::PseudoElement>();
+
         // Resources
         let prepared_state = PrepareParams::new(&mut app.world);
         app.init_asset_loader::<StyleSheetLoader>()
             .init_asset::<StyleSheetAsset>()
             .init_resource::<StyleSheetState>()
             .init_resource::<ComponentFilterRegistry>()
+            .init_resource::<AttributeFilterRegistry>()
+            .init_resource::<RootFontSize>()
+            .init_resource::<CustomMediaFeatures>()
+            .init_resource::<MediaContext>()
             .insert_resource(prepared_state);
 
+        #[cfg(feature = "pseudo_class")]
+        app.init_resource::<PseudoClassFilterRegistry>()
+            .init_resource::<FocusState>()
+            .register_type::<Checked>()
+            .register_type::<Disabled>()
+            .register_type::<Focusable>();
+
         // Schedules
         use system::sets::*;
         app.configure_sets(PreUpdate, (
@@ -129,7 +204,22 @@ for BevyCssPlugin
 
         // Systems
         app.add_systems(PreUpdate, system::prepare.in_set(BevyCssSet::Prepare))
-            .add_systems(PostUpdate, system::clear_state.in_set(BevyCssSet::Cleanup));
+            .add_systems(PreUpdate, system::update_media_context.before(BevyCssSet::Prepare))
+            .add_systems(Update, system::animate.before(BevyCssSet::Apply))
+            .add_systems(Update, system::resolve_font_faces.after(BevyCssSet::Apply))
+            .add_systems(PostUpdate, system::clear_state.in_set(BevyCssSet::Cleanup))
+            .add_systems(PostUpdate, system::resolve_relative_font_sizes.before(bevy::ui::UiSystem::Layout))
+            .add_systems(PostUpdate, system::resolve_viewport_and_font_lengths
+                .after(system::resolve_relative_font_sizes)
+                .before(bevy::ui::UiSystem::Layout)
+            )
+            .add_systems(PostUpdate, system::resolve_relative_lengths.after(bevy::ui::UiSystem::Layout));
+
+        #[cfg(feature = "pseudo_class")]
+        app.add_systems(PreUpdate, system::focus::update_focus
+            .after(bevy::ui::UiSystem::Focus)
+            .before(BevyCssSet::Prepare)
+        );
 
         if self.hot_reload
         {
@@ -139,6 +229,9 @@ for BevyCssPlugin
 
         // CSS registrations
         Self::register_component_selector(app);
+        Self::register_attribute_selector(app);
+        #[cfg(feature = "pseudo_class")]
+        Self::register_pseudo_class(app);
         Self::register_properties(app);
     }
 }
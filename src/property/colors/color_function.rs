@@ -0,0 +1,275 @@
+use super::{
+    color_mix::parse_color_mix,
+    parse_hex_color,
+    parse_named_color,
+    ColorSpace,
+};
+use crate::{
+    parser::{
+        is_math_function,
+        parse_math_function,
+    },
+    prelude::BevyCssError,
+};
+
+use bevy::{
+    prelude::Color,
+    ui::Val,
+};
+use cssparser::{
+    match_ignore_ascii_case,
+    ParseError, Parser, ParserInput,
+    Token,
+};
+
+/// A single argument of a color function, like `50%` on `hsl(120 50% 50%)`.
+#[derive(Clone, Copy, Debug)]
+enum Channel
+{
+    Number(f32),
+    Percentage(f32),
+    Angle(f32),
+    None,
+}
+
+impl Channel
+{
+    /// Gets the value of a number or percent channel, where `100%` is the given value.
+    fn scalar(
+        self,
+        percent_reference: f32
+    ) -> Option<f32> {
+        match self
+        {
+            Channel::Number(value) => Some(value),
+            Channel::Percentage(percent) => Some(percent * percent_reference),
+            Channel::None => Some(0.0),
+            Channel::Angle(_) => None,
+        }
+    }
+
+    /// Gets the value of a hue channel in degrees.
+    fn hue(
+        self
+    ) -> Option<f32> {
+        match self
+        {
+            Channel::Number(degrees) | Channel::Angle(degrees) => Some(degrees.rem_euclid(360.0)),
+            Channel::None => Some(0.0),
+            Channel::Percentage(_) => None,
+        }
+    }
+
+    /// Gets the value of an alpha channel, where `100%` is fully opaque.
+    fn alpha(
+        self
+    ) -> Option<f32> {
+        self.scalar(1.0).map(|alpha| alpha.clamp(0.0, 1.0))
+    }
+}
+
+/// Checks if the function name is a color function, like `rgb`, `oklch` or `color-mix`.
+pub(crate) fn is_color_function(
+    name: &str
+) -> bool {
+    match_ignore_ascii_case! { name,
+        "rgb" | "rgba" | "hsl" | "hsla" | "hwb" | "lab" | "lch" | "oklab" | "oklch" | "color-mix" => true,
+        _ => false,
+    }
+}
+
+/// Parses a single color, like `red`, `#ff0000` or `rgb(255 0 0)`.
+pub(super) fn parse_color<'i>(
+    input: &mut Parser<'i, '_>,
+) -> Result<Color, ParseError<'i, BevyCssError>> {
+    let token = input.next()?.clone();
+
+    let color = match &token
+    {
+        Token::Ident(name) => parse_named_color(name),
+        Token::Hash(hash) | Token::IDHash(hash) => parse_hex_color(hash),
+        Token::Function(name) if is_color_function(name) => {
+            return input.parse_nested_block(|input| parse_color_function(name, input));
+        }
+        _ => None,
+    };
+
+    color.ok_or_else(|| input.new_unexpected_token_error(token))
+}
+
+/// Parses the arguments of a color function, like `255 0 0 / 50%` inside `rgb(...)`, into a [`Color`].
+///
+/// Both the comma separated syntax, like `rgba(255, 0, 0, 0.5)`, and the space separated syntax, like `rgb(255 0 0 / 0.5)`,
+/// are supported, although the former only by `rgb()`, `rgba()`, `hsl()` and `hsla()` as on CSS.
+///
+/// Space separated colors can also be relative to another color, like `hsl(from red h s calc(l - 10))`,
+/// where the name of each component is replaced by its value on the origin color.
+pub(crate) fn parse_color_function<'i>(
+    name: &str,
+    input: &mut Parser<'i, '_>,
+) -> Result<Color, ParseError<'i, BevyCssError>> {
+    if name.eq_ignore_ascii_case("color-mix")
+    {
+        return parse_color_mix(input);
+    }
+
+    let space = ColorSpace::from_function(name)
+        .ok_or_else(|| input.new_custom_error(BevyCssError::InvalidPropertyValue(format!("{name}()"))))?;
+
+    if input.try_parse(|input| input.expect_ident_matching("from")).is_ok()
+    {
+        return parse_relative_color(name, space, input);
+    }
+
+    let (color, alpha) = parse_components(name, space, true, input)?;
+    Ok(color.with_a(alpha.unwrap_or(1.0)))
+}
+
+/// Parses the components of a relative color, like `red r g b / 50%` after `rgb(from ...)`.
+fn parse_relative_color<'i>(
+    name: &str,
+    space: ColorSpace,
+    input: &mut Parser<'i, '_>,
+) -> Result<Color, ParseError<'i, BevyCssError>> {
+    let location = input.current_source_location();
+    let origin = parse_color(input)?;
+
+    let [first, second, third] = space.components(origin);
+    let [first_name, second_name, third_name] = space.component_names();
+    let keywords = [(first_name, first), (second_name, second), (third_name, third), ("alpha", origin.a())];
+
+    let mut arguments = String::new();
+    substitute_keywords(input, &keywords, &mut arguments)?;
+
+    // Components are parsed again once the keywords are replaced, so they also work inside math functions
+    let mut arguments_input = ParserInput::new(&arguments);
+    let mut parser = Parser::new(&mut arguments_input);
+
+    parser.parse_entirely(|input| parse_components(name, space, false, input))
+        .map(|(color, alpha)| color.with_a(alpha.unwrap_or(origin.a())))
+        .map_err(|_| location.new_custom_error(BevyCssError::InvalidPropertyValue(format!("{name}()"))))
+}
+
+/// Copies every token to the `output`, replacing the component keywords of a relative color, like `l` on `calc(l - 10)`.
+fn substitute_keywords<'i>(
+    input: &mut Parser<'i, '_>,
+    keywords: &[(&str, f32)],
+    output: &mut String,
+) -> Result<(), ParseError<'i, BevyCssError>> {
+    loop
+    {
+        let start = input.position();
+        let token = match input.next_including_whitespace()
+        {
+            Ok(token) => token.clone(),
+            Err(_) => break Ok(()),
+        };
+
+        let closing = match token
+        {
+            Token::Ident(ref ident) => {
+                match keywords.iter().find(|(name, _)| ident.eq_ignore_ascii_case(name))
+                {
+                    Some((_, value)) => output.push_str(&value.to_string()),
+                    None => output.push_str(input.slice_from(start)),
+                }
+                continue;
+            }
+
+            Token::Function(_) | Token::ParenthesisBlock => ')',
+            Token::SquareBracketBlock => ']',
+            Token::CurlyBracketBlock => '}',
+
+            _ => {
+                output.push_str(input.slice_from(start));
+                continue;
+            }
+        };
+
+        output.push_str(input.slice_from(start));
+        input.parse_nested_block(|input| substitute_keywords(input, keywords, output))?;
+        output.push(closing);
+    }
+}
+
+/// Parses the three components of a color and its optional alpha, like `120 50% 50% / 0.5`.
+fn parse_components<'i>(
+    name: &str,
+    space: ColorSpace,
+    allow_commas: bool,
+    input: &mut Parser<'i, '_>,
+) -> Result<(Color, Option<f32>), ParseError<'i, BevyCssError>> {
+    let location = input.current_source_location();
+    let invalid = || location.new_custom_error(BevyCssError::InvalidPropertyValue(format!("{name}()")));
+
+    let first = parse_channel(input)?;
+    let commas = allow_commas
+        && matches!(space, ColorSpace::Srgb | ColorSpace::Hsl)
+        && input.try_parse(|input| input.expect_comma()).is_ok();
+
+    let second = parse_channel(input)?;
+    if commas
+    {
+        input.expect_comma()?;
+    }
+    let third = parse_channel(input)?;
+
+    let alpha = match commas
+    {
+        true => input.try_parse(|input| input.expect_comma()).ok(),
+        false => input.try_parse(|input| input.expect_delim('/')).ok(),
+    };
+    let alpha = match alpha
+    {
+        Some(_) => Some(parse_channel(input)?.alpha().ok_or_else(invalid)?),
+        None => None,
+    };
+
+    let references = space.percent_references();
+    let mut components = [0.0; 3];
+    for (index, channel) in [first, second, third].into_iter().enumerate()
+    {
+        let component = match space.hue_index() == Some(index)
+        {
+            true => channel.hue(),
+            false => channel.scalar(references[index]),
+        };
+        components[index] = component.ok_or_else(invalid)?;
+    }
+
+    Ok((space.color(components), alpha))
+}
+
+fn parse_channel<'i>(
+    input: &mut Parser<'i, '_>,
+) -> Result<Channel, ParseError<'i, BevyCssError>> {
+    let token = input.next()?.clone();
+
+    match token
+    {
+        Token::Number { value, .. } => Ok(Channel::Number(value)),
+        Token::Percentage { unit_value, .. } => Ok(Channel::Percentage(unit_value)),
+        Token::Ident(ref ident) if ident.eq_ignore_ascii_case("none") => Ok(Channel::None),
+        Token::Dimension { value, ref unit, .. } => {
+            let degrees = match_ignore_ascii_case! { unit.as_ref(),
+                "deg" => value,
+                "rad" => value.to_degrees(),
+                "grad" => value * 0.9,
+                "turn" => value * 360.0,
+                _ => return Err(input.new_unexpected_token_error(token.clone())),
+            };
+            Ok(Channel::Angle(degrees))
+        }
+        Token::Function(ref name) if is_math_function(name) => {
+            let expression = input.parse_nested_block(|input| parse_math_function(name, input))?;
+
+            match (expression.val(), expression.f32())
+            {
+                (Some(Val::Percent(percent)), _) => Ok(Channel::Percentage(percent / 100.0)),
+                (_, Some(value)) => Ok(Channel::Number(value)),
+                _ => Err(input.new_custom_error(BevyCssError::InvalidPropertyValue(format!("{name}()")))),
+            }
+        }
+        token => Err(input.new_unexpected_token_error(token)),
+    }
+}
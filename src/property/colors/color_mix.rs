@@ -0,0 +1,170 @@
+use super::{
+    color_function::parse_color,
+    ColorSpace,
+};
+use crate::prelude::BevyCssError;
+
+use bevy::prelude::Color;
+use cssparser::{
+    match_ignore_ascii_case,
+    ParseError, Parser,
+};
+
+/// How hues are interpolated by `color-mix()`, like `longer hue` on `color-mix(in oklch longer hue, red, blue)`.
+#[derive(Clone, Copy, Debug, Default)]
+enum HueInterpolation
+{
+    #[default]
+    Shorter,
+    Longer,
+    Increasing,
+    Decreasing,
+}
+
+impl HueInterpolation
+{
+    fn from_name(
+        name: &str
+    ) -> Option<Self> {
+        match_ignore_ascii_case! { name,
+            "shorter" => Some(HueInterpolation::Shorter),
+            "longer" => Some(HueInterpolation::Longer),
+            "increasing" => Some(HueInterpolation::Increasing),
+            "decreasing" => Some(HueInterpolation::Decreasing),
+            _ => None,
+        }
+    }
+
+    /// Adjusts both hues, in degrees, so interpolating between them follows this method.
+    fn adjust(
+        self,
+        first: f32,
+        second: f32
+    ) -> (f32, f32) {
+        let difference = second - first;
+
+        match self
+        {
+            HueInterpolation::Shorter if difference > 180.0 => (first + 360.0, second),
+            HueInterpolation::Shorter if difference < -180.0 => (first, second + 360.0),
+            HueInterpolation::Longer if difference > 0.0 && difference < 180.0 => (first + 360.0, second),
+            HueInterpolation::Longer if difference > -180.0 && difference <= 0.0 => (first, second + 360.0),
+            HueInterpolation::Increasing if second < first => (first, second + 360.0),
+            HueInterpolation::Decreasing if first < second => (first + 360.0, second),
+            _ => (first, second),
+        }
+    }
+}
+
+/// Parses the arguments of `color-mix()`, like `in oklab, red 70%, black`, into the mixed [`Color`].
+pub(super) fn parse_color_mix<'i>(
+    input: &mut Parser<'i, '_>,
+) -> Result<Color, ParseError<'i, BevyCssError>> {
+    let location = input.current_source_location();
+    let invalid = || location.new_custom_error(BevyCssError::InvalidPropertyValue("color-mix()".to_string()));
+
+    input.expect_ident_matching("in")?;
+    let space = ColorSpace::from_name(input.expect_ident()?)
+        .ok_or_else(invalid)?;
+
+    let hue_interpolation = match space.hue_index()
+    {
+        Some(_) => input.try_parse(|input|
+        {
+            let hue_interpolation = HueInterpolation::from_name(input.expect_ident()?)
+                .ok_or_else(invalid)?;
+            input.expect_ident_matching("hue")?;
+            Ok::<_, ParseError<'i, BevyCssError>>(hue_interpolation)
+        }).unwrap_or_default(),
+        None => HueInterpolation::default(),
+    };
+
+    input.expect_comma()?;
+    let (first, first_percent) = parse_mix_item(input)?;
+    input.expect_comma()?;
+    let (second, second_percent) = parse_mix_item(input)?;
+
+    let (first_percent, second_percent) = match (first_percent, second_percent)
+    {
+        (None, None) => (0.5, 0.5),
+        (Some(first), None) => (first, 1.0 - first),
+        (None, Some(second)) => (1.0 - second, second),
+        (Some(first), Some(second)) => (first, second),
+    };
+
+    let total = first_percent + second_percent;
+    if !(0.0..=1.0).contains(&first_percent) || !(0.0..=1.0).contains(&second_percent) || total <= 0.0
+    {
+        return Err(invalid());
+    }
+
+    // Percents which sums less than 100% makes the result transparent, while larger ones are scaled down
+    let mixed = mix(space, hue_interpolation, first, second, first_percent / total);
+    Ok(mixed.with_a(mixed.a() * total.min(1.0)))
+}
+
+/// Parses a color with an optional percent before or after it, like `red 70%`.
+fn parse_mix_item<'i>(
+    input: &mut Parser<'i, '_>,
+) -> Result<(Color, Option<f32>), ParseError<'i, BevyCssError>> {
+    let percent = input.try_parse(|input| input.expect_percentage()).ok();
+    let color = parse_color(input)?;
+
+    let percent = percent.or_else(|| input.try_parse(|input| input.expect_percentage()).ok());
+    Ok((color, percent))
+}
+
+/// Interpolates from one color to another on the Oklab color space, as done by animations,
+/// where a `progress` of `0.0` is the first color and `1.0` is the second one.
+pub(crate) fn interpolate_color(
+    from: Color,
+    to: Color,
+    progress: f32
+) -> Color {
+    mix(ColorSpace::Oklab, HueInterpolation::default(), from, to, 1.0 - progress)
+}
+
+/// Interpolates both colors on the given color space, using premultiplied alpha as on CSS.
+fn mix(
+    space: ColorSpace,
+    hue_interpolation: HueInterpolation,
+    first: Color,
+    second: Color,
+    amount_of_first: f32
+) -> Color {
+    let weight = |first: f32, second: f32| first * amount_of_first + second * (1.0 - amount_of_first);
+
+    let (first_alpha, second_alpha) = (first.a(), second.a());
+    let alpha = weight(first_alpha, second_alpha);
+
+    let first_components = space.components(first);
+    let second_components = space.components(second);
+
+    let mut components = [0.0; 3];
+    for (index, component) in components.iter_mut().enumerate()
+    {
+        let (first, second) = (first_components[index], second_components[index]);
+
+        *component = if space.hue_index() == Some(index)
+        {
+            // Colors without a hue, like white, takes the hue of the other color
+            let (first, second) = match (space.is_achromatic(first_components), space.is_achromatic(second_components))
+            {
+                (true, false) => (second, second),
+                (false, true) => (first, first),
+                _ => hue_interpolation.adjust(first, second),
+            };
+            weight(first, second)
+        }
+        else if alpha > 0.0
+        {
+            weight(first * first_alpha, second * second_alpha) / alpha
+        }
+        else
+        {
+            weight(first, second)
+        };
+    }
+
+    space.color(components).with_a(alpha)
+}
@@ -0,0 +1,327 @@
+use bevy::prelude::Color;
+use cssparser::match_ignore_ascii_case;
+
+/// A color space which colors can be parsed from or mixed in, like `oklch`.
+///
+/// Each color is made of three components, in the same range used by its CSS function,
+/// like `0` to `255` for `rgb()` or `0` to `100` for the saturation and lightness of `hsl()`.
+#[derive(Clone, Copy, Debug)]
+#[derive(PartialEq, Eq)]
+pub(super) enum ColorSpace
+{
+    Srgb,
+    SrgbLinear,
+    Hsl,
+    Hwb,
+    Lab,
+    Lch,
+    Oklab,
+    Oklch,
+}
+
+impl ColorSpace
+{
+    /// Gets the color space by its name on `color-mix()`, like `srgb` or `oklch`.
+    pub(super) fn from_name(
+        name: &str
+    ) -> Option<Self> {
+        match_ignore_ascii_case! { name,
+            "srgb" => Some(ColorSpace::Srgb),
+            "srgb-linear" => Some(ColorSpace::SrgbLinear),
+            "hsl" => Some(ColorSpace::Hsl),
+            "hwb" => Some(ColorSpace::Hwb),
+            "lab" => Some(ColorSpace::Lab),
+            "lch" => Some(ColorSpace::Lch),
+            "oklab" => Some(ColorSpace::Oklab),
+            "oklch" => Some(ColorSpace::Oklch),
+            _ => None,
+        }
+    }
+
+    /// Gets the color space of a color function, like `rgb` or `oklch`.
+    pub(super) fn from_function(
+        name: &str
+    ) -> Option<Self> {
+        match_ignore_ascii_case! { name,
+            "rgb" | "rgba" => Some(ColorSpace::Srgb),
+            "hsl" | "hsla" => Some(ColorSpace::Hsl),
+            _ => Self::from_name(name).filter(|space| *space != ColorSpace::Srgb && *space != ColorSpace::SrgbLinear),
+        }
+    }
+
+    /// Names of each component, used by relative colors like `rgb(from red r g b / 50%)`.
+    pub(super) fn component_names(
+        &self
+    ) -> [&'static str; 3] {
+        match self
+        {
+            ColorSpace::Srgb | ColorSpace::SrgbLinear => ["r", "g", "b"],
+            ColorSpace::Hsl => ["h", "s", "l"],
+            ColorSpace::Hwb => ["h", "w", "b"],
+            ColorSpace::Lab | ColorSpace::Oklab => ["l", "a", "b"],
+            ColorSpace::Lch | ColorSpace::Oklch => ["l", "c", "h"],
+        }
+    }
+
+    /// Value of each component which is equivalent to `100%`.
+    pub(super) fn percent_references(
+        &self
+    ) -> [f32; 3] {
+        match self
+        {
+            ColorSpace::Srgb => [255.0, 255.0, 255.0],
+            ColorSpace::SrgbLinear => [1.0, 1.0, 1.0],
+            ColorSpace::Hsl | ColorSpace::Hwb => [360.0, 100.0, 100.0],
+            ColorSpace::Lab => [100.0, 125.0, 125.0],
+            ColorSpace::Lch => [100.0, 150.0, 360.0],
+            ColorSpace::Oklab => [1.0, 0.4, 0.4],
+            ColorSpace::Oklch => [1.0, 0.4, 360.0],
+        }
+    }
+
+    /// Index of the hue component, for color spaces which have one.
+    pub(super) fn hue_index(
+        &self
+    ) -> Option<usize> {
+        match self
+        {
+            ColorSpace::Hsl | ColorSpace::Hwb => Some(0),
+            ColorSpace::Lch | ColorSpace::Oklch => Some(2),
+            _ => None,
+        }
+    }
+
+    /// Checks if the hue of the color is meaningless, like on white or gray, which only happens for color spaces with a hue.
+    pub(super) fn is_achromatic(
+        &self,
+        components: [f32; 3]
+    ) -> bool {
+        match self
+        {
+            ColorSpace::Hsl => components[1] < 0.01,
+            ColorSpace::Hwb => components[1] + components[2] > 99.99,
+            ColorSpace::Lch => components[1] < 0.05,
+            ColorSpace::Oklch => components[1] < 0.0005,
+            _ => false,
+        }
+    }
+
+    /// Converts the components of a color in this color space into an opaque sRGB [`Color`],
+    /// clipping colors outside of the sRGB gamut.
+    pub(super) fn color(
+        &self,
+        [c0, c1, c2]: [f32; 3]
+    ) -> Color {
+        match self
+        {
+            ColorSpace::Srgb => Color::rgb(
+                (c0 / 255.0).clamp(0.0, 1.0),
+                (c1 / 255.0).clamp(0.0, 1.0),
+                (c2 / 255.0).clamp(0.0, 1.0),
+            ),
+            ColorSpace::SrgbLinear => linear_to_srgb([c0, c1, c2]),
+            ColorSpace::Hsl => Color::hsl(
+                c0.rem_euclid(360.0),
+                (c1 / 100.0).clamp(0.0, 1.0),
+                (c2 / 100.0).clamp(0.0, 1.0),
+            ).as_rgba(),
+            ColorSpace::Hwb => hwb_to_srgb(c0.rem_euclid(360.0), (c1 / 100.0).clamp(0.0, 1.0), (c2 / 100.0).clamp(0.0, 1.0)),
+            ColorSpace::Lab => cie_lab_to_srgb(c0, c1, c2),
+            ColorSpace::Lch => {
+                let (a, b) = polar_to_cartesian(c1, c2);
+                cie_lab_to_srgb(c0, a, b)
+            }
+            ColorSpace::Oklab => oklab_to_srgb(c0, c1, c2),
+            ColorSpace::Oklch => {
+                let (a, b) = polar_to_cartesian(c1, c2);
+                oklab_to_srgb(c0, a, b)
+            }
+        }
+    }
+
+    /// Converts a [`Color`] into the components of this color space, ignoring its alpha.
+    pub(super) fn components(
+        &self,
+        color: Color
+    ) -> [f32; 3] {
+        let [red, green, blue, _] = color.as_rgba_f32();
+        let [linear_red, linear_green, linear_blue, _] = color.as_linear_rgba_f32();
+        let linear = [linear_red, linear_green, linear_blue];
+
+        match self
+        {
+            ColorSpace::Srgb => [red * 255.0, green * 255.0, blue * 255.0],
+            ColorSpace::SrgbLinear => linear,
+            ColorSpace::Hsl => match color.as_hsla()
+            {
+                Color::Hsla { hue, saturation, lightness, .. } => [hue, saturation * 100.0, lightness * 100.0],
+                _ => unreachable!(),
+            },
+            ColorSpace::Hwb => {
+                let hue = ColorSpace::Hsl.components(color)[0];
+                let whiteness = red.min(green).min(blue);
+                let blackness = 1.0 - red.max(green).max(blue);
+                [hue, whiteness * 100.0, blackness * 100.0]
+            }
+            ColorSpace::Lab => linear_to_cie_lab(linear),
+            ColorSpace::Lch => cartesian_to_polar(linear_to_cie_lab(linear)),
+            ColorSpace::Oklab => linear_to_oklab(linear),
+            ColorSpace::Oklch => cartesian_to_polar(linear_to_oklab(linear)),
+        }
+    }
+}
+
+/// CIE Lab constants, as on CSS. Source: https://www.w3.org/TR/css-color-4/#color-conversion-code
+const EPSILON: f32 = 216.0 / 24389.0;
+const KAPPA: f32 = 24389.0 / 27.0;
+const D50: [f32; 3] = [0.3457 / 0.3585, 1.0, (1.0 - 0.3457 - 0.3585) / 0.3585];
+
+fn hwb_to_srgb(
+    hue: f32,
+    whiteness: f32,
+    blackness: f32
+) -> Color {
+    if whiteness + blackness >= 1.0
+    {
+        let gray = whiteness / (whiteness + blackness);
+        return Color::rgb(gray, gray, gray);
+    }
+
+    let [red, green, blue, _] = Color::hsl(hue, 1.0, 0.5).as_rgba_f32();
+    let scale = |value: f32| value * (1.0 - whiteness - blackness) + whiteness;
+
+    Color::rgb(scale(red), scale(green), scale(blue))
+}
+
+fn polar_to_cartesian(
+    chroma: f32,
+    hue: f32
+) -> (f32, f32) {
+    let hue = hue.to_radians();
+    (chroma.max(0.0) * hue.cos(), chroma.max(0.0) * hue.sin())
+}
+
+fn cartesian_to_polar(
+    [lightness, a, b]: [f32; 3]
+) -> [f32; 3] {
+    [lightness, a.hypot(b), b.atan2(a).to_degrees().rem_euclid(360.0)]
+}
+
+/// Converts a color from CIE Lab, with a D50 white point as on CSS, into sRGB.
+fn cie_lab_to_srgb(
+    lightness: f32,
+    a: f32,
+    b: f32
+) -> Color {
+    let fy = (lightness + 16.0) / 116.0;
+    let fx = fy + a / 500.0;
+    let fz = fy - b / 200.0;
+
+    let inverse = |f: f32| match f.powi(3)
+    {
+        cube if cube > EPSILON => cube,
+        _ => (116.0 * f - 16.0) / KAPPA,
+    };
+    let y = match lightness > KAPPA * EPSILON
+    {
+        true => fy.powi(3),
+        false => lightness / KAPPA,
+    };
+
+    let xyz_d50 = [inverse(fx) * D50[0], y * D50[1], inverse(fz) * D50[2]];
+
+    // Bradford chromatic adaptation from D50 to D65
+    let xyz_d65 = multiply(&[
+        [0.955_473_4, -0.023_098_537, 0.063_259_31],
+        [-0.028_369_706, 1.009_995_5, 0.021_041_399],
+        [0.012_314_002, -0.020_507_697, 1.330_366],
+    ], xyz_d50);
+
+    let linear = multiply(&[
+        [3.240_97, -1.537_383_2, -0.498_610_76],
+        [-0.969_243_65, 1.875_967_5, 0.041_555_06],
+        [0.055_630_08, -0.203_976_96, 1.056_971_5],
+    ], xyz_d65);
+
+    linear_to_srgb(linear)
+}
+
+/// Converts a linear sRGB color into CIE Lab, with a D50 white point as on CSS.
+fn linear_to_cie_lab(
+    linear: [f32; 3]
+) -> [f32; 3] {
+    let xyz_d65 = multiply(&[
+        [0.412_390_8, 0.357_584_33, 0.180_480_8],
+        [0.212_639, 0.715_168_7, 0.072_192_32],
+        [0.019_330_818, 0.119_194_78, 0.950_532_14],
+    ], linear);
+
+    // Bradford chromatic adaptation from D65 to D50
+    let xyz_d50 = multiply(&[
+        [1.047_929_8, 0.022_946_793, -0.050_192_23],
+        [0.029_627_815, 0.990_434_5, -0.017_073_825],
+        [-0.009_243_058, 0.015_055_145, 0.751_874_3],
+    ], xyz_d65);
+
+    let [fx, fy, fz] = [0, 1, 2].map(|i| match xyz_d50[i] / D50[i]
+    {
+        value if value > EPSILON => value.cbrt(),
+        value => (KAPPA * value + 16.0) / 116.0,
+    });
+
+    [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+
+/// Converts a color from Oklab into sRGB.
+///
+/// Source: https://bottosson.github.io/posts/oklab/
+fn oklab_to_srgb(
+    lightness: f32,
+    a: f32,
+    b: f32
+) -> Color {
+    let lms = multiply(&[
+        [1.0, 0.396_337_78, 0.215_803_76],
+        [1.0, -0.105_561_346, -0.063_854_17],
+        [1.0, -0.089_484_18, -1.291_485_5],
+    ], [lightness, a, b]);
+
+    let linear = multiply(&[
+        [4.076_741_7, -3.307_711_6, 0.230_969_94],
+        [-1.268_438, 2.609_757_4, -0.341_319_38],
+        [-0.004_196_086_3, -0.703_418_6, 1.707_614_7],
+    ], lms.map(|value| value.powi(3)));
+
+    linear_to_srgb(linear)
+}
+
+/// Converts a linear sRGB color into Oklab.
+fn linear_to_oklab(
+    linear: [f32; 3]
+) -> [f32; 3] {
+    let lms = multiply(&[
+        [0.412_221_46, 0.536_332_55, 0.051_445_995],
+        [0.211_903_5, 0.680_699_5, 0.107_396_96],
+        [0.088_302_46, 0.281_718_85, 0.629_978_7],
+    ], linear);
+
+    multiply(&[
+        [0.210_454_26, 0.793_617_8, -0.004_072_047],
+        [1.977_998_5, -2.428_592_2, 0.450_593_7],
+        [0.025_904_037, 0.782_771_77, -0.808_675_77],
+    ], lms.map(f32::cbrt))
+}
+
+fn multiply(
+    matrix: &[[f32; 3]; 3],
+    vector: [f32; 3]
+) -> [f32; 3] {
+    matrix.map(|row| row[0] * vector[0] + row[1] * vector[1] + row[2] * vector[2])
+}
+
+/// Converts a linear sRGB color into sRGB, clipping colors outside of the sRGB gamut.
+fn linear_to_srgb(
+    [red, green, blue]: [f32; 3]
+) -> Color {
+    Color::rgba_linear(red.clamp(0.0, 1.0), green.clamp(0.0, 1.0), blue.clamp(0.0, 1.0), 1.0).as_rgba()
+}
@@ -1,3 +1,15 @@
+mod color_function;
+pub(crate) use color_function::{
+    is_color_function,
+    parse_color_function,
+};
+
+mod color_mix;
+pub(crate) use color_mix::interpolate_color;
+
+mod color_space;
+use color_space::ColorSpace;
+
 use bevy::{
     log::error,
     prelude::Color,
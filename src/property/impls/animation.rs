@@ -0,0 +1,132 @@
+use super::{Property, PropertyValues};
+use crate::{
+    component::{
+        Animation,
+        AnimationDirection,
+        AnimationFillMode,
+    },
+    prelude::BevyCssError,
+    property::{
+        PropertyToken,
+        TimingFunction,
+    },
+};
+
+use bevy::{ecs::query::QueryItem, prelude::*};
+
+/// Implements a new property for [`Animation`] component, which is inserted when missing.
+macro_rules! impl_animation_property
+{
+    ($name:expr, $struct:ident, $cache:ty, $parse:expr, $apply:expr) => {
+        #[doc = "Applies the `"]
+        #[doc = $name]
+        #[doc = "` property on the [`Animation`] component of matched entities."]
+        #[derive(Default)]
+        pub(crate) struct $struct;
+
+        impl Property for $struct
+        {
+            type Cache = $cache;
+            type Components = (Entity, Option<&'static mut Animation>);
+            type Filters = With<Node>;
+
+            fn name(
+                // no args
+            ) -> &'static str {
+                $name
+            }
+
+            fn parse<'a>(
+                values: &PropertyValues
+            ) -> Result<Self::Cache, BevyCssError> {
+                // Only a single animation is supported, so lists like `fade, slide` are rejected instead of applying the first one
+                if values.comma_separated().len() > 1
+                {
+                    return Err(BevyCssError::InvalidPropertyValue(Self::name().to_string()));
+                }
+
+                let parse: fn(&PropertyValues) -> Option<$cache> = $parse;
+                parse(values).ok_or_else(|| BevyCssError::InvalidPropertyValue(Self::name().to_string()))
+            }
+
+            fn apply<'w>(
+                cache: &Self::Cache,
+                components: QueryItem<Self::Components>,
+                _asset_server: &AssetServer,
+                commands: &mut Commands,
+            ) {
+                let (entity, animation) = components;
+                let value = cache.clone();
+                let apply: fn(&mut Animation, $cache) = $apply;
+
+                Animation::apply(entity, animation, commands, move |animation| apply(animation, value));
+            }
+        }
+    };
+}
+
+impl_animation_property!(
+    "animation-name", AnimationNameProperty, Option<String>,
+    |values| match values.identifier()
+    {
+        Some("none") => Some(None),
+        Some(name) => Some(Some(name.to_string())),
+        None => values.string().map(Some),
+    },
+    |animation, name| animation.set_name(name)
+);
+
+impl_animation_property!(
+    "animation-duration", AnimationDurationProperty, f32,
+    |values| values.time().filter(|duration| *duration >= 0.0),
+    |animation, duration| animation.duration = duration
+);
+
+impl_animation_property!(
+    "animation-timing-function", AnimationTimingFunctionProperty, TimingFunction,
+    PropertyValues::timing_function,
+    |animation, timing_function| animation.timing_function = timing_function
+);
+
+impl_animation_property!(
+    "animation-delay", AnimationDelayProperty, f32,
+    PropertyValues::time,
+    |animation, delay| animation.delay = delay
+);
+
+impl_animation_property!(
+    "animation-iteration-count", AnimationIterationCountProperty, f32,
+    |values| values.iter().find_map(|token| match token
+    {
+        PropertyToken::Number(count) if *count >= 0.0 => Some(*count),
+        PropertyToken::Identifier(name) if name == "infinite" => Some(f32::INFINITY),
+        _ => None,
+    }),
+    |animation, count| animation.iteration_count = count
+);
+
+impl_animation_property!(
+    "animation-direction", AnimationDirectionProperty, AnimationDirection,
+    |values| match values.identifier()?
+    {
+        "normal" => Some(AnimationDirection::Normal),
+        "reverse" => Some(AnimationDirection::Reverse),
+        "alternate" => Some(AnimationDirection::Alternate),
+        "alternate-reverse" => Some(AnimationDirection::AlternateReverse),
+        _ => None,
+    },
+    |animation, direction| animation.direction = direction
+);
+
+impl_animation_property!(
+    "animation-fill-mode", AnimationFillModeProperty, AnimationFillMode,
+    |values| match values.identifier()?
+    {
+        "none" => Some(AnimationFillMode::None),
+        "forwards" => Some(AnimationFillMode::Forwards),
+        "backwards" => Some(AnimationFillMode::Backwards),
+        "both" => Some(AnimationFillMode::Both),
+        _ => None,
+    },
+    |animation, fill_mode| animation.fill_mode = fill_mode
+);
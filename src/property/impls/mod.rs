@@ -1,9 +1,15 @@
 use super::{Property, PropertyValues};
 use crate::prelude::BevyCssError;
 
+/// Impls for [`Animation`](crate::prelude::Animation) component
+pub mod animation;
+
 /// Impls for `bevy_ui` [`Style`] component
 pub mod style;
 
+/// Impls for [`Transitions`](crate::prelude::Transitions) component
+pub mod transition;
+
 use bevy::{ecs::query::QueryItem, prelude::*};
 
 /// Applies the `background-color` property on [`BackgroundColor`] component of matched entities.
@@ -1,4 +1,6 @@
 /// Implements a new property for [`Style`] component which expects a rect value.
+///
+/// Percents on all sides are resolved against the width of the parent node.
 macro_rules! impl_style_rect
 {
     ($name:expr, $struct:ident, $style_prop:ident$(.$style_field:ident)*) => {
@@ -13,8 +15,8 @@ macro_rules! impl_style_rect
 
         impl Property for $struct
         {
-            type Cache = UiRect;
-            type Components = &'static mut Style;
+            type Cache = LengthRect;
+            type Components = (Entity, &'static mut Style, Option<&'static mut RelativeLengths>);
             type Filters = With<Node>;
 
             fn name()
@@ -25,7 +27,7 @@ macro_rules! impl_style_rect
             fn parse<'a>(
                 values: &PropertyValues
             ) -> Result<Self::Cache, BevyCssError> {
-                if let Some(val) = values.rect()
+                if let Some(val) = values.length_rect()
                 {
                     Ok(val)
                 }
@@ -37,11 +39,92 @@ macro_rules! impl_style_rect
 
             fn apply<'w>(
                 cache: &Self::Cache,
-                mut components: QueryItem<Self::Components>,
+                components: QueryItem<Self::Components>,
                 _asset_server: &AssetServer,
-                _commands: &mut Commands,
+                commands: &mut Commands,
             ) {
-                components.$style_prop$(.$style_field)? = *cache;
+                let (entity, mut style, mut lengths) = components;
+
+                RelativeLengths::apply(
+                    concat!($name, "-left"), &cache.left, PercentBase::Width,
+                    |style, val| style.$style_prop$(.$style_field)?.left = val,
+                    entity, &mut style, lengths.as_mut(), commands
+                );
+                RelativeLengths::apply(
+                    concat!($name, "-right"), &cache.right, PercentBase::Width,
+                    |style, val| style.$style_prop$(.$style_field)?.right = val,
+                    entity, &mut style, lengths.as_mut(), commands
+                );
+                RelativeLengths::apply(
+                    concat!($name, "-top"), &cache.top, PercentBase::Width,
+                    |style, val| style.$style_prop$(.$style_field)?.top = val,
+                    entity, &mut style, lengths.as_mut(), commands
+                );
+                RelativeLengths::apply(
+                    concat!($name, "-bottom"), &cache.bottom, PercentBase::Width,
+                    |style, val| style.$style_prop$(.$style_field)?.bottom = val,
+                    entity, &mut style, lengths.as_mut(), commands
+                );
+            }
+        }
+    };
+}
+
+/// Implements a new property for [`Style`] component which expects a length value.
+///
+/// Percents are resolved against the given size of the parent node, either [`Width`](PercentBase::Width)
+/// or [`Height`](PercentBase::Height).
+macro_rules! impl_style_length
+{
+    ($name:expr, $struct:ident, $base:ident, $style_prop:ident$(.$style_field:ident)*) => {
+        #[doc = "Applies the `"]
+        #[doc = $name]
+        #[doc = "` property on [Style::"]
+        #[doc = stringify!($style_prop)]
+        $(#[doc = concat!("::",stringify!($style_field))])*
+        #[doc = "](`Style`) field of all sections on matched [`Style`] components."]
+        #[derive(Default)]
+        pub(crate) struct $struct;
+
+        impl Property for $struct
+        {
+            type Cache = LengthValue;
+            type Components = (Entity, &'static mut Style, Option<&'static mut RelativeLengths>);
+            type Filters = With<Node>;
+
+            fn name()
+            -> &'static str
+            {
+                $name
+            }
+
+            fn parse<'a>(
+                values: &PropertyValues
+            ) -> Result<Self::Cache, BevyCssError>
+            {
+                if let Some(val) = values.length()
+                {
+                    Ok(val)
+                }
+                else
+                {
+                    Err(BevyCssError::InvalidPropertyValue(Self::name().to_string()))
+                }
+            }
+
+            fn apply<'w>(
+                cache: &Self::Cache,
+                components: QueryItem<Self::Components>,
+                _asset_server: &AssetServer,
+                commands: &mut Commands,
+            ) {
+                let (entity, mut style, mut lengths) = components;
+
+                RelativeLengths::apply(
+                    $name, cache, PercentBase::$base,
+                    |style, val| style.$style_prop$(.$style_field)? = val,
+                    entity, &mut style, lengths.as_mut(), commands
+                );
             }
         }
     };
@@ -3,8 +3,17 @@ mod macros;
 
 use super::*;
 use crate::{
+    component::{
+        PercentBase,
+        RelativeLengths,
+    },
     prelude::BevyCssError,
-    property::{Property, PropertyValues},
+    property::{
+        LengthRect,
+        LengthValue,
+        Property,
+        PropertyValues,
+    },
 };
 
 // Rect type property fields
@@ -13,21 +22,21 @@ impl_style_rect!("padding", PaddingProperty, padding);
 impl_style_rect!("border", BorderProperty, border);
 
 // Val (number) type property fields
-impl_style_single_value!("left", LeftProperty, Val, val, left);
-impl_style_single_value!("right", RightProperty, Val, val, right);
-impl_style_single_value!("top", TopProperty, Val, val, top);
-impl_style_single_value!("bottom", BottomProperty, Val, val, bottom);
+impl_style_length!("left", LeftProperty, Width, left);
+impl_style_length!("right", RightProperty, Width, right);
+impl_style_length!("top", TopProperty, Height, top);
+impl_style_length!("bottom", BottomProperty, Height, bottom);
 
-impl_style_single_value!("width", WidthProperty, Val, val, width);
-impl_style_single_value!("height", HeightProperty, Val, val, height);
+impl_style_length!("width", WidthProperty, Width, width);
+impl_style_length!("height", HeightProperty, Height, height);
 
-impl_style_single_value!("min-width", MinWidthProperty, Val, val, min_width);
-impl_style_single_value!("min-height", MinHeightProperty, Val, val, min_height);
+impl_style_length!("min-width", MinWidthProperty, Width, min_width);
+impl_style_length!("min-height", MinHeightProperty, Height, min_height);
 
-impl_style_single_value!("max-width", MaxWidthProperty, Val, val, max_width);
-impl_style_single_value!("max-height", MaxHeightProperty, Val, val, max_height);
+impl_style_length!("max-width", MaxWidthProperty, Width, max_width);
+impl_style_length!("max-height", MaxHeightProperty, Height, max_height);
 
-impl_style_single_value!("flex-basis", FlexBasisProperty, Val, val, max_height);
+impl_style_length!("flex-basis", FlexBasisProperty, Width, flex_basis);
 
 // f32 (number) type property fields
 impl_style_single_value!("flex-grow", FlexGrowProperty, f32, f32, flex_grow);
@@ -0,0 +1,119 @@
+use super::{Property, PropertyValues};
+use crate::{
+    component::{
+        Transition,
+        Transitions,
+    },
+    prelude::BevyCssError,
+    property::{
+        PropertyToken,
+        TimingFunction,
+    },
+};
+
+use bevy::{ecs::query::QueryItem, prelude::*};
+
+/// Applies the `transition` property on the [`Transitions`] component of matched entities.
+#[derive(Default)]
+pub(crate) struct TransitionProperty;
+
+impl TransitionProperty
+{
+    /// Parses a list of transitions, like `background-color 150ms ease-out, width 300ms`.
+    ///
+    /// Times are the duration and then the delay of each transition.
+    fn parse_transitions(
+        values: &PropertyValues
+    ) -> Option<Vec<Transition>> {
+        if values.len() == 1 && values.identifier() == Some("none")
+        {
+            return Some(Vec::new());
+        }
+
+        values.comma_separated()
+            .iter()
+            .map(Self::parse_transition)
+            .collect()
+    }
+
+    /// Parses a single transition of a list, like `width 300ms ease-in 1s`.
+    fn parse_transition(
+        values: &PropertyValues
+    ) -> Option<Transition> {
+        if values.is_empty()
+        {
+            return None;
+        }
+
+        let mut property = None;
+        let mut transition = Transition::default();
+        let mut times = 0;
+
+        for token in values.iter()
+        {
+            match token
+            {
+                PropertyToken::Identifier(name) => match TimingFunction::from_name(name)
+                {
+                    Some(timing_function) => transition.timing_function = timing_function,
+                    None if name == "none" || property.is_some() => return None,
+                    None => property = Some(name.clone()),
+                },
+
+                PropertyToken::TimingFunction(timing_function) => transition.timing_function = *timing_function,
+
+                PropertyToken::Time(duration) if times == 0 && *duration >= 0.0 => {
+                    transition.duration = *duration;
+                    times += 1;
+                }
+                PropertyToken::Time(delay) if times == 1 => {
+                    transition.delay = *delay;
+                    times += 1;
+                }
+
+                _ => return None,
+            }
+        }
+
+        // Transitions without a property name applies to all of them, like `transition: 300ms`
+        Some(Transition {
+            property: property.unwrap_or_else(|| "all".to_string()),
+            ..transition
+        })
+    }
+}
+
+impl Property for TransitionProperty
+{
+    type Cache = Vec<Transition>;
+    type Components = (Entity, Option<&'static mut Transitions>);
+    type Filters = With<Node>;
+
+    fn name(
+        // no args
+    ) -> &'static str {
+        "transition"
+    }
+
+    fn parse<'a>(
+        values: &PropertyValues
+    ) -> Result<Self::Cache, BevyCssError> {
+        Self::parse_transitions(values)
+            .ok_or_else(|| BevyCssError::InvalidPropertyValue(Self::name().to_string()))
+    }
+
+    fn apply<'w>(
+        cache: &Self::Cache,
+        components: QueryItem<Self::Components>,
+        _asset_server: &AssetServer,
+        commands: &mut Commands,
+    ) {
+        match components
+        {
+            (_, Some(mut transitions)) => transitions.0.clone_from(cache),
+            (entity, None) => {
+                commands.entity(entity).insert(Transitions(cache.clone()));
+            }
+        }
+    }
+}
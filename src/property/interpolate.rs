@@ -0,0 +1,149 @@
+use super::{
+    colors,
+    LengthRect,
+    LengthValue,
+    MathExpression,
+    Unit,
+};
+
+use bevy::{
+    prelude::Color,
+    ui::Val,
+};
+
+/// A [`Cache`](super::Property::Cache) value which can be smoothly changed by a `transition`, like a [`Color`] or a [`LengthValue`].
+///
+/// Properties with these values are registered with
+/// [`register_interpolated_property`](crate::RegisterProperty::register_interpolated_property).
+pub trait Interpolate:
+    Clone + PartialEq
+{
+    /// Interpolates from this value to the given one, where a `progress` of `0.0` is this value and `1.0` is the given one.
+    ///
+    /// The progress may be outside of that range, like on `cubic-bezier(0.5, -0.5, 0.5, 1.5)`.
+    fn interpolate(
+        &self,
+        to: &Self,
+        progress: f32
+    ) -> Self;
+}
+
+impl Interpolate
+for f32
+{
+    fn interpolate(
+        &self,
+        to: &Self,
+        progress: f32
+    ) -> Self {
+        self + (to - self) * progress
+    }
+}
+
+impl Interpolate
+for Option<f32>
+{
+    fn interpolate(
+        &self,
+        to: &Self,
+        progress: f32
+    ) -> Self {
+        match (self, to)
+        {
+            (Some(from), Some(to)) => Some(from.interpolate(to, progress)),
+            _ if progress < 0.5 => *self,
+            _ => *to,
+        }
+    }
+}
+
+impl Interpolate
+for Color
+{
+    fn interpolate(
+        &self,
+        to: &Self,
+        progress: f32
+    ) -> Self {
+        colors::interpolate_color(*self, *to, progress)
+    }
+}
+
+impl Interpolate
+for LengthValue
+{
+    fn interpolate(
+        &self,
+        to: &Self,
+        progress: f32
+    ) -> Self {
+        let lerp = |from: &f32, to: &f32| from.interpolate(to, progress);
+
+        if let (LengthValue::Val(from), LengthValue::Val(to)) = (self, to)
+        {
+            let val = match (from, to)
+            {
+                (Val::Px(from), Val::Px(to)) => Some(Val::Px(lerp(from, to))),
+                (Val::Percent(from), Val::Percent(to)) => Some(Val::Percent(lerp(from, to))),
+                (Val::Vw(from), Val::Vw(to)) => Some(Val::Vw(lerp(from, to))),
+                (Val::Vh(from), Val::Vh(to)) => Some(Val::Vh(lerp(from, to))),
+                (Val::VMin(from), Val::VMin(to)) => Some(Val::VMin(lerp(from, to))),
+                (Val::VMax(from), Val::VMax(to)) => Some(Val::VMax(lerp(from, to))),
+                _ => None,
+            };
+
+            if let Some(val) = val
+            {
+                return LengthValue::Val(val);
+            }
+        }
+
+        // Lengths of different units, like `50%` to `100px`, are only known at layout, while `auto` can't be interpolated at all
+        match (self.expression(), to.expression())
+        {
+            (Some(from), Some(to)) => LengthValue::Relative(MathExpression::interpolate(from, to, progress)),
+            _ if progress < 0.5 => self.clone(),
+            _ => to.clone(),
+        }
+    }
+}
+
+impl LengthValue
+{
+    /// Converts this length into a [`MathExpression`], or [`None`] for `auto`.
+    pub(crate) fn expression(
+        &self
+    ) -> Option<MathExpression> {
+        match self
+        {
+            LengthValue::Relative(expression) => Some(expression.clone()),
+            LengthValue::Val(val) => match *val
+            {
+                Val::Auto => None,
+                Val::Percent(percent) => Some(MathExpression::Percentage(percent)),
+                Val::Px(value) => Some(MathExpression::Dimension(value, Unit::Px)),
+                Val::Vw(value) => Some(MathExpression::Dimension(value, Unit::Vw)),
+                Val::Vh(value) => Some(MathExpression::Dimension(value, Unit::Vh)),
+                Val::VMin(value) => Some(MathExpression::Dimension(value, Unit::VMin)),
+                Val::VMax(value) => Some(MathExpression::Dimension(value, Unit::VMax)),
+            },
+        }
+    }
+}
+
+impl Interpolate
+for LengthRect
+{
+    fn interpolate(
+        &self,
+        to: &Self,
+        progress: f32
+    ) -> Self {
+        Self {
+            left: self.left.interpolate(&to.left, progress),
+            right: self.right.interpolate(&to.right, progress),
+            top: self.top.interpolate(&to.top, progress),
+            bottom: self.bottom.interpolate(&to.bottom, progress),
+        }
+    }
+}
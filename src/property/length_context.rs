@@ -0,0 +1,18 @@
+use bevy::prelude::Vec2;
+
+/// Sizes used to resolve lengths which aren't known when parsed, like `calc(100% - 20px)` or `2em`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LengthContext
+{
+    /// Size which percents are relative to, usually the width or height of the parent node.
+    pub percent_base: f32,
+
+    /// Size of the viewport, used by `vw`, `vh`, `vmin` and `vmax`.
+    pub viewport: Vec2,
+
+    /// Inherited font size, used by `em`.
+    pub font_size: f32,
+
+    /// Root font size, used by `rem`.
+    pub root_font_size: f32,
+}
@@ -0,0 +1,69 @@
+use super::MathExpression;
+
+use bevy::ui::{
+    UiRect,
+    Val,
+};
+
+/// A length value of a property, like `width` or `margin`.
+#[derive(Clone, Debug)]
+#[derive(PartialEq)]
+pub enum LengthValue
+{
+    /// A length which is known when parsed, like `10px`, `50%` or `calc(100% / 3)`.
+    Val(Val),
+
+    /// A length which depends on the size of the parent node or on font sizes, like `calc(100% - 20px)` or `2em`, so it's only known when applied.
+    Relative(MathExpression),
+}
+
+impl Default
+for LengthValue
+{
+    fn default(
+        // no args
+    ) -> Self {
+        Self::Val(Val::default())
+    }
+}
+
+/// A [`LengthValue`] for each side of an area, like [`UiRect`].
+#[derive(Clone, Debug, Default)]
+#[derive(PartialEq)]
+pub struct LengthRect
+{
+    pub left: LengthValue,
+    pub right: LengthValue,
+    pub top: LengthValue,
+    pub bottom: LengthValue,
+}
+
+impl LengthRect
+{
+    /// Creates a new [`LengthRect`] with the same value on all sides.
+    pub fn all(
+        value: LengthValue
+    ) -> Self {
+        Self {
+            left: value.clone(),
+            right: value.clone(),
+            top: value.clone(),
+            bottom: value,
+        }
+    }
+}
+
+impl From<UiRect>
+for LengthRect
+{
+    fn from(
+        rect: UiRect
+    ) -> Self {
+        Self {
+            left: LengthValue::Val(rect.left),
+            right: LengthValue::Val(rect.right),
+            top: LengthValue::Val(rect.top),
+            bottom: LengthValue::Val(rect.bottom),
+        }
+    }
+}
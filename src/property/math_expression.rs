@@ -0,0 +1,306 @@
+use super::{
+    LengthContext,
+    Unit,
+};
+
+use bevy::ui::Val;
+
+/// A math expression which was parsed from a math function, like `calc(100% - 20px)`, `min(50%, 300px)`,
+/// `max(10px, 5%)` or `clamp(100px, 50%, 400px)`.
+#[derive(Clone, Debug)]
+#[derive(PartialEq, PartialOrd)]
+pub enum MathExpression
+{
+    /// A numeric float value, like `2` on `calc(100% / 2)`.
+    Number(f32),
+
+    /// A percent value, like `100%` on `calc(100% - 20px)`.
+    Percentage(f32),
+
+    /// A dimension value, like `20px` on `calc(100% - 20px)`.
+    Dimension(f32, Unit),
+
+    /// The sum of both expressions, like `a + b`.
+    Sum(Box<MathExpression>, Box<MathExpression>),
+
+    /// The difference of both expressions, like `a - b`.
+    Difference(Box<MathExpression>, Box<MathExpression>),
+
+    /// The product of both expressions, like `a * b`. At least one of them must be a number.
+    Product(Box<MathExpression>, Box<MathExpression>),
+
+    /// The quotient of both expressions, like `a / b`. The divisor must be a number.
+    Quotient(Box<MathExpression>, Box<MathExpression>),
+
+    /// The smallest of all expressions, like `min(a, b)`.
+    Min(Vec<MathExpression>),
+
+    /// The largest of all expressions, like `max(a, b)`.
+    Max(Vec<MathExpression>),
+
+    /// The preferred value limited by the minimum and maximum values, like `clamp(min, value, max)`.
+    Clamp(Box<MathExpression>, Box<MathExpression>, Box<MathExpression>),
+}
+
+/// Result of evaluating a [`MathExpression`].
+///
+/// Lengths are kept as a sum of each unit, since percents and relative units are only known at layout.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Term
+{
+    Number(f32),
+    Length(Length),
+}
+
+/// A sum of lengths, where the first component is a percent and the others are each [`Unit`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Length([f32; Length::COMPONENTS]);
+
+impl Length
+{
+    const COMPONENTS: usize = Unit::ALL.len() + 1;
+    const PERCENT: usize = 0;
+
+    fn new(
+        component: usize,
+        value: f32
+    ) -> Self {
+        let mut length = Self::default();
+        length.0[component] = value;
+        length
+    }
+
+    fn unit(
+        unit: Unit,
+        value: f32
+    ) -> Self {
+        Self::new(unit as usize + 1, value)
+    }
+
+    fn map(
+        mut self,
+        f: impl Fn(f32) -> f32
+    ) -> Self {
+        self.0.iter_mut().for_each(|value| *value = f(*value));
+        self
+    }
+
+    fn zip(
+        mut self,
+        other: Length,
+        f: impl Fn(f32, f32) -> f32
+    ) -> Self {
+        self.0.iter_mut().zip(other.0).for_each(|(value, other)| *value = f(*value, other));
+        self
+    }
+
+    /// The only non zero component of this length, `Some(None)` when all are zero
+    /// or [`None`] when it mixes many of them.
+    fn component(
+        &self
+    ) -> Option<Option<(usize, f32)>> {
+        let mut components = self.0.iter()
+            .copied()
+            .enumerate()
+            .filter(|(_, value)| *value != 0.0);
+
+        match (components.next(), components.next())
+        {
+            (component, None) => Some(component),
+            _ => None,
+        }
+    }
+}
+
+impl MathExpression
+{
+    /// Evaluates this expression into a single [`Val`], when it can be known ahead, like `calc(100% / 3)` or `calc(10vw * 2)`.
+    ///
+    /// Expressions mixing units, like `calc(100% - 20px)`, or using font relative units, like `calc(2em)`,
+    /// returns [`None`], since they must be resolved at layout with [`resolve`](MathExpression::resolve).
+    pub fn val(
+        &self
+    ) -> Option<Val> {
+        let Term::Length(length) = self.evaluate(None)?
+        else
+        {
+            return None;
+        };
+
+        match length.component()?
+        {
+            None => Some(Val::Px(0.0)),
+            Some((Length::PERCENT, percent)) => Some(Val::Percent(percent)),
+            Some((component, value)) => Unit::ALL[component - 1].val(value),
+        }
+    }
+
+    /// Evaluates this expression into a single [`f32`], like `calc(10px + 2px)`.
+    ///
+    /// Units other than pixels or percents, or expressions mixing them, returns [`None`].
+    pub fn f32(
+        &self
+    ) -> Option<f32> {
+        match self.evaluate(None)?
+        {
+            Term::Number(value) => Some(value),
+            Term::Length(length) => match length.component()?
+            {
+                None => Some(0.0),
+                Some((Length::PERCENT, value)) => Some(value),
+                Some((component, value)) if Unit::ALL[component - 1] == Unit::Px => Some(value),
+                Some(_) => None,
+            },
+        }
+    }
+
+    /// Evaluates this expression into pixels, using the given [`LengthContext`] for percents and relative units.
+    ///
+    /// Returns [`None`] when this expression isn't a valid length, like `calc(10px * 5px)` or `calc(2 + 3)`.
+    pub fn resolve(
+        &self,
+        context: &LengthContext
+    ) -> Option<f32> {
+        match self.evaluate(Some(context))?
+        {
+            Term::Length(length) => Some(length.0.iter().sum()),
+            Term::Number(_) => None,
+        }
+    }
+
+    /// Interpolates from one length to another, like `50%` to `100px`, where a `progress` of `0.0` is the first length
+    /// and `1.0` is the second one, as a sum of both which is only known at layout, like `calc(50% * 0.5 + 100px * 0.5)`.
+    pub(crate) fn interpolate(
+        from: MathExpression,
+        to: MathExpression,
+        progress: f32
+    ) -> MathExpression {
+        MathExpression::Sum(
+            Box::new(MathExpression::Product(Box::new(MathExpression::Number(1.0 - progress)), Box::new(from))),
+            Box::new(MathExpression::Product(Box::new(MathExpression::Number(progress)), Box::new(to))),
+        )
+    }
+
+    /// Checks if this expression is a valid length, even if it can only be resolved at layout.
+    pub fn is_length(
+        &self
+    ) -> bool {
+        // Any context works, since it only changes the result, not whether the expression is valid
+        self.resolve(&LengthContext::default()).is_some()
+    }
+
+    /// Checks if this expression has any percent, which depends on the size of the parent node, like `calc(100% - 20px)`.
+    pub fn has_percentage(
+        &self
+    ) -> bool {
+        use MathExpression::*;
+
+        match self
+        {
+            Number(_) | Dimension(..) => false,
+            Percentage(_) => true,
+            Sum(a, b) | Difference(a, b) | Product(a, b) | Quotient(a, b) => a.has_percentage() || b.has_percentage(),
+            Min(expressions) | Max(expressions) => expressions.iter().any(MathExpression::has_percentage),
+            Clamp(min, value, max) => min.has_percentage() || value.has_percentage() || max.has_percentage(),
+        }
+    }
+
+    fn evaluate(
+        &self,
+        context: Option<&LengthContext>
+    ) -> Option<Term> {
+        use MathExpression::*;
+
+        let term = match self
+        {
+            Number(value) => Term::Number(*value),
+            Dimension(value, unit) => match context
+            {
+                Some(context) => Term::Length(Length::unit(Unit::Px, unit.px(*value, context))),
+                None => Term::Length(Length::unit(*unit, *value)),
+            },
+            Percentage(percent) => match context
+            {
+                Some(context) => Term::Length(Length::unit(Unit::Px, context.percent_base * percent / 100.0)),
+                None => Term::Length(Length::new(Length::PERCENT, *percent)),
+            },
+
+            Sum(a, b) | Difference(a, b) => {
+                let sign = match self
+                {
+                    Difference(..) => -1.0,
+                    _ => 1.0,
+                };
+
+                match (a.evaluate(context)?, b.evaluate(context)?)
+                {
+                    (Term::Number(a), Term::Number(b)) => Term::Number(a + sign * b),
+                    (Term::Length(a), Term::Length(b)) => Term::Length(a.zip(b, |a, b| a + sign * b)),
+                    _ => return None,
+                }
+            }
+
+            Product(a, b) => match (a.evaluate(context)?, b.evaluate(context)?)
+            {
+                (Term::Number(a), Term::Number(b)) => Term::Number(a * b),
+                (Term::Number(factor), Term::Length(length))
+                | (Term::Length(length), Term::Number(factor)) => Term::Length(length.map(|value| value * factor)),
+                _ => return None,
+            },
+
+            Quotient(a, b) => match (a.evaluate(context)?, b.evaluate(context)?)
+            {
+                (_, Term::Number(0.0)) => return None,
+                (Term::Number(a), Term::Number(divisor)) => Term::Number(a / divisor),
+                (Term::Length(length), Term::Number(divisor)) => Term::Length(length.map(|value| value / divisor)),
+                _ => return None,
+            },
+
+            Min(expressions) => Self::select(expressions, context, f32::min)?,
+            Max(expressions) => Self::select(expressions, context, f32::max)?,
+
+            Clamp(min, value, max) => {
+                let value = Self::select_terms(&[value.evaluate(context)?, max.evaluate(context)?], f32::min)?;
+                Self::select_terms(&[min.evaluate(context)?, value], f32::max)?
+            }
+        };
+
+        Some(term)
+    }
+
+    /// Evaluates all expressions and select one of them, like the smallest on `min(a, b)`.
+    fn select(
+        expressions: &[MathExpression],
+        context: Option<&LengthContext>,
+        select: fn(f32, f32) -> f32
+    ) -> Option<Term> {
+        let terms = expressions.iter()
+            .map(|expression| expression.evaluate(context))
+            .collect::<Option<Vec<_>>>()?;
+
+        Self::select_terms(&terms, select)
+    }
+
+    /// Select one of the terms, which can only be compared when all of them are of the same kind.
+    ///
+    /// Lengths mixing units can't be compared without resolving them first.
+    fn select_terms(
+        terms: &[Term],
+        select: fn(f32, f32) -> f32
+    ) -> Option<Term> {
+        let (first, rest) = terms.split_first()?;
+
+        rest.iter().try_fold(*first, |selected, term| match (selected, term)
+        {
+            (Term::Number(a), Term::Number(b)) => Some(Term::Number(select(a, *b))),
+
+            (Term::Length(a), Term::Length(b)) => match (a.component()?, b.component()?)
+            {
+                (Some((a_component, _)), Some((b_component, _))) if a_component != b_component => None,
+                _ => Some(Term::Length(a.zip(*b, select))),
+            },
+
+            _ => None,
+        })
+    }
+}
@@ -5,6 +5,22 @@ mod cached_properties;
 pub use cached_properties::*;
 
 mod colors;
+pub(crate) use colors::{
+    is_color_function,
+    parse_color_function,
+};
+
+mod interpolate;
+pub use interpolate::*;
+
+mod length_context;
+pub use length_context::*;
+
+mod length_value;
+pub use length_value::*;
+
+mod math_expression;
+pub use math_expression::*;
 
 mod property_meta;
 pub use property_meta::*;
@@ -12,6 +28,9 @@ pub use property_meta::*;
 mod property_token;
 pub use property_token::*;
 
+mod property_transitions;
+pub use property_transitions::*;
+
 mod property_values;
 pub use property_values::*;
 
@@ -21,6 +40,12 @@ pub use selected_entities::*;
 mod stylesheet_state;
 pub use stylesheet_state::*;
 
+mod timing_function;
+pub use timing_function::*;
+
+mod unit;
+pub use unit::*;
+
 pub(crate) mod impls;
 /// Impls for `bevy_text` [`Text`] component
 pub(crate) mod text;
@@ -29,9 +54,11 @@ pub(crate) mod text;
 use crate::prelude::{
     BevyCssError,
     StyleSheetAsset,
+    Transitions,
 };
 
 use bevy::{
+    log::error,
     ecs::query::{
         QueryItem,
         QueryFilter,
@@ -42,6 +69,7 @@ use bevy::{
         Local,
         Query,
         Res,
+        Time,
     },
 };
 use std::any::Any;
@@ -69,9 +97,11 @@ use bevy::ecs::query::QueryData;
 /// - [`apply`](Property::apply) applies on the given [`Components`](Property::Components) the [`Cache`](Property::Cache) value.
 ///   Additionally, an [`AssetServer`] and [`Commands`] parameters are provided for more complex use cases.
 ///
-/// Also, there one function which have default implementations:
+/// Also, there are two functions which have default implementations:
 /// - [`apply_system`](Property::apply_system) is a [`system`](https://docs.rs/bevy_ecs/0.8.1/bevy_ecs/system/index.html) which interacts with
 ///   [ecs world](`bevy::prelude::World`) and call the [`apply`](Property::apply) function on every matched entity.
+/// - [`transition_system`](Property::transition_system) is a system which interpolates the property when it changes,
+///   only available when the [`Cache`](Property::Cache) is [`Interpolate`].
 pub trait Property:
     Default + Sized + Send + Sync + 'static
 {
@@ -134,10 +164,27 @@ pub trait Property:
                 None => continue,
             };
 
-            let cached_value = match local.get_or_parse(rules, &source.selector)
+            // Values resolved from custom properties differs for each entity, so they aren't cached
+            let resolved;
+            let cached_value = match &source.values
             {
-                CacheState::Ok(cached) => cached,
-                _other => continue,
+                Some(values) => match Self::parse(values)
+                {
+                    Ok(value) => {
+                        resolved = value;
+                        &resolved
+                    }
+                    Err(err) => {
+                        error!("Failed to parse property {}. Error: {}", Self::name(), err);
+                        continue;
+                    }
+                },
+
+                None => match local.get_or_parse(rules, &source.selector)
+                {
+                    CacheState::Ok(cached) => cached,
+                    _other => continue,
+                },
             };
 
             let components = match q_nodes.get_mut(*entity)
@@ -149,4 +196,75 @@ pub trait Property:
             Self::apply(cached_value, components, &asset_server, &mut commands);
         }
     }
+
+    /// The [`system`](https://docs.rs/bevy_ecs/0.8.1/bevy_ecs/system/index.html) which interpolates the property
+    /// when its value changes on an entity with a matching [`Transitions`], like `transition: width 300ms`.
+    ///
+    /// It runs after [`apply_system`](Property::apply_system), keeping track of the value applied on each entity,
+    /// and calls [`apply`](Property::apply) again with the interpolated value while transitioning.
+    #[allow(clippy::too_many_arguments)]
+    fn transition_system(
+        mut local: Local<PropertyMeta<Self>>,
+        mut transitions: Local<PropertyTransitions<Self::Cache>>,
+        time: Res<Time>,
+        assets: Res<Assets<StyleSheetAsset>>,
+        apply_sheets: Res<StyleSheetState>,
+        q_transitions: Query<&Transitions>,
+        mut q_nodes: Query<Self::Components, Self::Filters>,
+        asset_server: Res<AssetServer>,
+        mut commands: Commands,
+    )
+    where
+        Self::Cache: Interpolate,
+    {
+        // Forget entities which no longer exists, only when the style sheet is applied again to avoid checking every frame
+        if !apply_sheets.is_empty()
+        {
+            transitions.retain(|entity, _| q_nodes.contains(*entity));
+        }
+
+        for (entity, style) in apply_sheets.iter()
+        {
+            let Some(source) = style.get(Self::name())
+            else
+            {
+                continue;
+            };
+
+            // As on CSS, animated values aren't transitioned
+            if source.animated
+            {
+                transitions.remove(entity);
+                continue;
+            }
+
+            let target = match &source.values
+            {
+                Some(values) => Self::parse(values).ok(),
+                None => assets.get(&source.styleheet)
+                    .and_then(|rules| match local.get_or_parse(rules, &source.selector)
+                    {
+                        CacheState::Ok(cached) => Some(cached.clone()),
+                        _ => None,
+                    }),
+            };
+
+            if let Some(target) = target
+            {
+                let transition = q_transitions.get(*entity)
+                    .ok()
+                    .and_then(|transitions| transitions.get(Self::name()));
+
+                transitions.set_target(*entity, target, transition);
+            }
+        }
+
+        for (entity, value) in transitions.advance(time.delta_seconds())
+        {
+            if let Ok(components) = q_nodes.get_mut(entity)
+            {
+                Self::apply(&value, components, &asset_server, &mut commands);
+            }
+        }
+    }
 }
@@ -1,17 +1,22 @@
+use super::{
+    MathExpression,
+    TimingFunction,
+    Unit,
+};
+
+use bevy::prelude::Color;
 use cssparser::Token;
 
 /// A property value token which was parsed from a CSS rule.
 #[derive(Clone, Debug)]
-#[derive(PartialEq, PartialOrd)]
+#[derive(PartialEq)]
 pub enum PropertyToken
 {
     /// A value which was parsed percent value, like `100%` or `73.23%`.
     Percentage(f32),
 
-    /// A value which was parsed dimension value, like `10px` or `35em.
-    ///
-    /// Currently there is no distinction between [`length-values`](https://developer.mozilla.org/en-US/docs/Web/CSS/length).
-    Dimension(f32),
+    /// A value which was parsed dimension value, like `10px` or `35em`, with its [`Unit`].
+    Dimension(f32, Unit),
 
     /// A numeric float value, like `31.1` or `43`.
     Number(f32),
@@ -24,6 +29,21 @@ pub enum PropertyToken
 
     /// A quoted string, like `"some value"`.
     String(String),
+
+    /// A math expression, like `calc(100% - 20px)` or `min(50%, 300px)`.
+    Math(MathExpression),
+
+    /// A color which was parsed from a color function, like `rgb(255 0 0 / 50%)` or `oklch(70% 0.1 200)`.
+    Color(Color),
+
+    /// A time value in seconds, like `0.5s` or `300ms`.
+    Time(f32),
+
+    /// A timing function which was parsed from an easing function, like `cubic-bezier(0.1, 0.7, 1, 0.1)` or `steps(4, end)`.
+    TimingFunction(TimingFunction),
+
+    /// A comma separating a list of values, like on `font-family: "Fira Sans", serif`.
+    Comma,
 }
 
 impl<'i> TryFrom<Token<'i>>
@@ -40,9 +60,14 @@ for PropertyToken
             Token::Hash(val) => Ok(Self::Hash(val.to_string())),
             Token::IDHash(val) => Ok(Self::Hash(val.to_string())),
             Token::QuotedString(val) => Ok(Self::String(val.to_string())),
+            Token::Comma => Ok(Self::Comma),
             Token::Number { value, .. } => Ok(Self::Number(value)),
             Token::Percentage { unit_value, .. } => Ok(Self::Percentage(unit_value * 100.0)),
-            Token::Dimension { value, .. } => Ok(Self::Dimension(value)),
+            Token::Dimension { value, unit, .. } if unit.eq_ignore_ascii_case("s") => Ok(Self::Time(value)),
+            Token::Dimension { value, unit, .. } if unit.eq_ignore_ascii_case("ms") => Ok(Self::Time(value / 1000.0)),
+            Token::Dimension { value, unit, .. } => Unit::from_name(&unit)
+                .map(|unit| Self::Dimension(value, unit))
+                .ok_or(()),
             _ => Err(()),
         }
     }
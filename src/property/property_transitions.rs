@@ -0,0 +1,121 @@
+use super::Interpolate;
+use crate::component::Transition;
+
+use bevy::{
+    prelude::{
+        Deref, DerefMut,
+        Entity,
+    },
+    utils::HashMap,
+};
+
+/// The value of a property on a single entity, which is interpolated from the previous value while transitioning.
+#[derive(Debug)]
+pub struct TransitionState<T>
+{
+    /// Value currently applied, which is the starting value when the property changes again.
+    value: T,
+    /// Value applied by the style sheet, which is the ending value of the transition.
+    target: T,
+    /// The starting value, the running [`Transition`] and the time since it started, when transitioning.
+    running: Option<(T, Transition, f32)>,
+}
+
+/// Internal transitions state of a property. Used by [`Property::transition_system`](super::Property::transition_system)
+/// to keep track of the value applied on each entity, so it's known where to start from when it changes.
+#[derive(Debug)]
+#[derive(Deref, DerefMut)]
+pub struct PropertyTransitions<T>(
+    HashMap<Entity, TransitionState<T>>
+);
+
+impl<T> Default
+for PropertyTransitions<T>
+{
+    fn default(
+        // no args
+    ) -> Self {
+        Self(HashMap::default())
+    }
+}
+
+impl<T: Interpolate> PropertyTransitions<T>
+{
+    /// Sets the value applied by the style sheet on the entity, starting a transition from the current value
+    /// when it changed and the given [`Transition`] is some.
+    ///
+    /// The first value of each entity is never transitioned, since there is nothing to start from.
+    pub(crate) fn set_target(
+        &mut self,
+        entity: Entity,
+        target: T,
+        transition: Option<&Transition>
+    ) {
+        let Some(state) = self.get_mut(&entity)
+        else
+        {
+            self.insert(entity, TransitionState {
+                value: target.clone(),
+                target,
+                running: None,
+            });
+            return;
+        };
+
+        if state.target == target
+        {
+            return;
+        }
+
+        state.running = transition
+            .filter(|transition| transition.duration.max(0.0) + transition.delay > 0.0)
+            .map(|transition| (state.value.clone(), transition.clone(), 0.0));
+
+        if state.running.is_none()
+        {
+            state.value = target.clone();
+        }
+        state.target = target;
+    }
+
+    /// Advances all running transitions by the given seconds, returning the interpolated value of each entity to be applied.
+    pub(crate) fn advance(
+        &mut self,
+        delta: f32
+    ) -> Vec<(Entity, T)> {
+        let mut values = Vec::new();
+
+        for (entity, state) in self.iter_mut()
+        {
+            let Some((from, transition, elapsed)) = &mut state.running
+            else
+            {
+                continue;
+            };
+
+            // Transitions starts from the current value on the frame they're started
+            let active_time = *elapsed - transition.delay;
+            *elapsed += delta;
+
+            let progress = match transition.duration > 0.0
+            {
+                _ if active_time < 0.0 => 0.0,
+                true => (active_time / transition.duration).min(1.0),
+                false => 1.0,
+            };
+
+            state.value = match progress < 1.0
+            {
+                true => from.interpolate(&state.target, transition.timing_function.ease(progress)),
+                false => {
+                    state.running = None;
+                    state.target.clone()
+                }
+            };
+
+            values.push((*entity, state.value.clone()));
+        }
+
+        values
+    }
+}
@@ -2,7 +2,12 @@ use crate::DynArray;
 
 use super::{
     colors,
+    LengthRect,
+    LengthValue,
+    MathExpression,
     PropertyToken,
+    TimingFunction,
+    Unit,
 };
 
 use bevy::{
@@ -44,28 +49,59 @@ impl PropertyValues
 
     /// Tries to parses the current values as a single [`Color`].
     ///
-    /// Currently only [named colors](https://developer.mozilla.org/en-US/docs/Web/CSS/named-color)
-    /// and [hex-colors](https://developer.mozilla.org/en-US/docs/Web/CSS/hex-color) are supported.
+    /// Supports [named colors](https://developer.mozilla.org/en-US/docs/Web/CSS/named-color),
+    /// [hex-colors](https://developer.mozilla.org/en-US/docs/Web/CSS/hex-color)
+    /// and [color functions](https://developer.mozilla.org/en-US/docs/Web/CSS/color_value), like `hsl(120 50% 50%)`.
     pub fn color(
         &self
     ) -> Option<Color> {
         if self.0.len() == 1
         {
-            match &self.0[0]
-            {
-                PropertyToken::Identifier(name) => colors::parse_named_color(name.as_str()),
-                PropertyToken::Hash(hash) => colors::parse_hex_color(hash.as_str()),
-                _ => None,
-            }
+            Self::token_color(&self.0[0])
         }
         else
         {
-            // TODO: Implement color function like rgba(255, 255, 255, 255)
-            // https://developer.mozilla.org/en-US/docs/Web/CSS/color_value
             None
         }
     }
 
+    /// Splits the current values by their commas, like `"Fira Sans"` and `serif` on `"Fira Sans", serif`.
+    pub fn comma_separated(
+        &self
+    ) -> Vec<PropertyValues> {
+        self.0.split(|token| matches!(token, PropertyToken::Comma))
+            .map(|tokens| PropertyValues(tokens.iter().cloned().collect()))
+            .collect()
+    }
+
+    /// Tries to parses the current values as a single time, in seconds, like `0.5s` or `300ms`.
+    pub fn time(
+        &self
+    ) -> Option<f32> {
+        self.0.iter()
+            .find_map(|token| match token
+            {
+                PropertyToken::Time(seconds) => Some(*seconds),
+                _ => None,
+            })
+    }
+
+    /// Tries to parses the current values as a single [`TimingFunction`].
+    ///
+    /// Both keywords, like `ease-in-out`, and easing functions, like `cubic-bezier(0.1, 0.7, 1, 0.1)` or `steps(4, end)`,
+    /// are considered valid values.
+    pub fn timing_function(
+        &self
+    ) -> Option<TimingFunction> {
+        self.0.iter()
+            .find_map(|token| match token
+            {
+                PropertyToken::Identifier(name) => TimingFunction::from_name(name),
+                PropertyToken::TimingFunction(timing_function) => Some(*timing_function),
+                _ => None,
+            })
+    }
+
     /// Tries to parse the current value as a single [`OverflowAxis`].
     pub fn overflow(
         &self
@@ -108,7 +144,9 @@ impl PropertyValues
     /// Tries to parses the current values as a single [`Val`].
     ///
     /// Only [`Percentage`](PropertyToken::Percentage) and [`Dimension`](PropertyToken::Dimension`) are considered valid values,
-    /// where former is converted to [`Val::Percent`] and latter is converted to [`Val::Px`].
+    /// where former is converted to [`Val::Percent`] and latter is converted by its [`Unit`], like [`Val::Px`] or [`Val::Vw`].
+    ///
+    /// Font relative units, like `2em`, aren't valid values, since they can only be resolved at layout.
     pub fn val(
         &self
     ) -> Option<Val> {
@@ -116,15 +154,26 @@ impl PropertyValues
             .find_map(|token| match token
             {
                 PropertyToken::Percentage(val) => Some(Val::Percent(*val)),
-                PropertyToken::Dimension(val) => Some(Val::Px(*val)),
+                PropertyToken::Dimension(val, unit) => unit.val(*val),
                 PropertyToken::Identifier(val) if val == "auto" => Some(Val::Auto),
+                PropertyToken::Math(expression) => expression.val(),
                 _ => None,
             })
     }
 
+    /// Tries to parses the current values as a single [`LengthValue`].
+    ///
+    /// Same as [`val`](PropertyValues::val), but font relative units and math expressions which can only be resolved at layout,
+    /// like `2em` or `calc(100% - 20px)`, are also considered valid values.
+    pub fn length(
+        &self
+    ) -> Option<LengthValue> {
+        self.0.iter().find_map(Self::token_length)
+    }
+
     /// Tries to parses the current values as a single [`f32`].
     ///
-    /// Only [`Percentage`](PropertyToken::Percentage), [`Dimension`](PropertyToken::Dimension`) and [`Number`](PropertyToken::Number`)
+    /// Only [`Percentage`](PropertyToken::Percentage), [`Dimension`](PropertyToken::Dimension`) in pixels and [`Number`](PropertyToken::Number`)
     /// are considered valid values.
     pub fn f32(
         &self
@@ -133,8 +182,9 @@ impl PropertyValues
             .find_map(|token| match token
             {
                 PropertyToken::Percentage(val)
-                | PropertyToken::Dimension(val)
+                | PropertyToken::Dimension(val, Unit::Px)
                 | PropertyToken::Number(val) => Some(*val),
+                PropertyToken::Math(expression) => expression.f32(),
                 _ => None,
             })
     }
@@ -145,7 +195,7 @@ impl PropertyValues
     ///
     /// If a [`Option::None`] is returned, it means some invalid value was found.
     ///
-    /// If there is a [`Percentage`](PropertyToken::Percentage), [`Dimension`](PropertyToken::Dimension`) or [`Number`](PropertyToken::Number`) token,
+    /// If there is a [`Percentage`](PropertyToken::Percentage), [`Dimension`](PropertyToken::Dimension`) in pixels or [`Number`](PropertyToken::Number`) token,
     /// a [`Option::Some`] with parsed [`Option<f32>`] is returned.
     /// If there is a identifier with a `none` value, then [`Option::Some`] with [`None`] is returned.
     pub fn option_f32(
@@ -155,9 +205,11 @@ impl PropertyValues
             .find_map(|token| match token
             {
                 PropertyToken::Percentage(val)
-                | PropertyToken::Dimension(val)
+                | PropertyToken::Dimension(val, Unit::Px)
                 | PropertyToken::Number(val) => Some(Some(*val)),
 
+                PropertyToken::Math(expression) => expression.f32().map(Some),
+
                 PropertyToken::Identifier(ident) => match ident.as_str()
                 {
                     "none" => Some(None),
@@ -189,8 +241,17 @@ impl PropertyValues
                     let val = match token
                     {
                         PropertyToken::Percentage(val) => Val::Percent(*val),
-                        PropertyToken::Dimension(val) => Val::Px(*val),
+                        PropertyToken::Dimension(val, unit) => match unit.val(*val)
+                        {
+                            Some(val) => val,
+                            None => return (rect, idx),
+                        },
                         PropertyToken::Identifier(val) if val == "auto" => Val::Auto,
+                        PropertyToken::Math(expression) => match expression.val()
+                        {
+                            Some(val) => val,
+                            None => return (rect, idx),
+                        },
                         _ => return (rect, idx),
                     };
                     let mut rect: UiRect = rect.unwrap_or_default();
@@ -207,4 +268,148 @@ impl PropertyValues
                 }).0
         }
     }
+
+    /// Tries to parses the current values as a single [`LengthRect`].
+    ///
+    /// Same as [`rect`](PropertyValues::rect), but font relative units and math expressions which can only be resolved at layout,
+    /// like `2em` or `calc(100% - 20px)`, are also considered valid values.
+    pub fn length_rect(
+        &self
+    ) -> Option<LengthRect> {
+        if self.0.len() == 1
+        {
+            self.length().map(LengthRect::all)
+        }
+        else
+        {
+            self.0.iter()
+                .filter_map(Self::token_length)
+                .take(4)
+                .enumerate()
+                .fold(None, |rect, (idx, length)|
+                {
+                    let mut rect: LengthRect = rect.unwrap_or_else(|| UiRect::default().into());
+
+                    match idx
+                    {
+                        0 => rect.top = length,
+                        1 => rect.right = length,
+                        2 => rect.bottom = length,
+                        _ => rect.left = length,
+                    }
+                    Some(rect)
+                })
+        }
+    }
+
+    /// Interpolates from the current values to the given ones, where a `progress` of `0.0` is the current values
+    /// and `1.0` is the given ones.
+    ///
+    /// Numbers, lengths and colors are interpolated token by token, while values which can't be interpolated,
+    /// like `auto` to `10px`, flips from one to another at half of the progress.
+    pub(crate) fn interpolate(
+        &self,
+        to: &PropertyValues,
+        progress: f32
+    ) -> PropertyValues {
+        if self.0.len() == to.0.len()
+        {
+            let tokens = self.0.iter()
+                .zip(to.0.iter())
+                .map(|(from, to)| Self::interpolate_token(from, to, progress))
+                .collect::<Option<DynArray<_>>>();
+
+            if let Some(tokens) = tokens
+            {
+                return PropertyValues(tokens);
+            }
+        }
+
+        match progress < 0.5
+        {
+            true => self.clone(),
+            false => to.clone(),
+        }
+    }
+
+    fn interpolate_token(
+        from: &PropertyToken,
+        to: &PropertyToken,
+        progress: f32
+    ) -> Option<PropertyToken> {
+        let lerp = |from: f32, to: f32| from + (to - from) * progress;
+
+        let token = match (from, to)
+        {
+            (PropertyToken::Number(from), PropertyToken::Number(to)) => PropertyToken::Number(lerp(*from, *to)),
+            (PropertyToken::Percentage(from), PropertyToken::Percentage(to)) => PropertyToken::Percentage(lerp(*from, *to)),
+            (PropertyToken::Time(from), PropertyToken::Time(to)) => PropertyToken::Time(lerp(*from, *to)),
+            (PropertyToken::Dimension(from, from_unit), PropertyToken::Dimension(to, to_unit)) if from_unit == to_unit => {
+                PropertyToken::Dimension(lerp(*from, *to), *from_unit)
+            }
+
+            _ if from == to => from.clone(),
+
+            _ => match (Self::token_color(from), Self::token_color(to))
+            {
+                (Some(from), Some(to)) => PropertyToken::Color(colors::interpolate_color(from, to, progress)),
+
+                // Lengths of different units, like `50%` to `100px`, are only known at layout, so they are kept as `calc()`
+                _ => {
+                    let from = Self::token_math_length(from)?;
+                    let to = Self::token_math_length(to)?;
+
+                    PropertyToken::Math(MathExpression::interpolate(from, to, progress))
+                }
+            },
+        };
+
+        Some(token)
+    }
+
+    fn token_math_length(
+        token: &PropertyToken
+    ) -> Option<MathExpression> {
+        match token
+        {
+            PropertyToken::Percentage(val) => Some(MathExpression::Percentage(*val)),
+            PropertyToken::Dimension(val, unit) => Some(MathExpression::Dimension(*val, *unit)),
+            PropertyToken::Math(expression) if expression.is_length() => Some(expression.clone()),
+            _ => None,
+        }
+    }
+
+    fn token_color(
+        token: &PropertyToken
+    ) -> Option<Color> {
+        match token
+        {
+            PropertyToken::Identifier(name) => colors::parse_named_color(name.as_str()),
+            PropertyToken::Hash(hash) => colors::parse_hex_color(hash.as_str()),
+            PropertyToken::Color(color) => Some(*color),
+            _ => None,
+        }
+    }
+
+    fn token_length(
+        token: &PropertyToken
+    ) -> Option<LengthValue> {
+        match token
+        {
+            PropertyToken::Percentage(val) => Some(LengthValue::Val(Val::Percent(*val))),
+            PropertyToken::Dimension(val, unit) => match unit.val(*val)
+            {
+                Some(val) => Some(LengthValue::Val(val)),
+                None => Some(LengthValue::Relative(MathExpression::Dimension(*val, *unit))),
+            },
+            PropertyToken::Identifier(val) if val == "auto" => Some(LengthValue::Val(Val::Auto)),
+            PropertyToken::Math(expression) => match expression.val()
+            {
+                Some(val) => Some(LengthValue::Val(val)),
+                None if expression.is_length() => Some(LengthValue::Relative(expression.clone())),
+                None => None,
+            },
+            _ => None,
+        }
+    }
 }
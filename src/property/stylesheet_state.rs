@@ -1,4 +1,7 @@
-use super::SelectedEntities;
+use super::{
+    PropertyValues,
+    SelectedEntities,
+};
 use crate::{
     selector::Selector,
     stylesheet::StyleSheetAsset,
@@ -6,6 +9,7 @@ use crate::{
 
 use bevy::{
     prelude::{
+        AssetId,
         Assets,
         Deref, DerefMut,
         Entity,
@@ -20,6 +24,10 @@ pub struct StyleSource
 {
     pub styleheet: Handle<StyleSheetAsset>,
     pub selector: Selector,
+    /// Values resolved for this entity, when the property references custom properties like `var(--accent)`.
+    pub values: Option<PropertyValues>,
+    /// Whether the values are interpolated by an [`Animation`](crate::prelude::Animation), which doesn't start transitions.
+    pub animated: bool,
 }
 
 #[derive(Debug, Clone, Default, Deref, DerefMut)]
@@ -32,12 +40,20 @@ pub struct ComputedStyle(
 
 /// Maps sheets for each [`StyleSheetAsset`].
 #[derive(Debug, Clone, Default, Deref, DerefMut, Resource)]
-pub struct StyleSheetStateBuilder(
-    HashMap<
+pub struct StyleSheetStateBuilder
+{
+    #[deref]
+    sheets: HashMap<
         Handle<StyleSheetAsset>,
         SelectedEntities
-    >
-);
+    >,
+
+    /// Position of each sheet on the chain of nested sheets of each entity, where nested sheets comes after their ancestors.
+    depths: HashMap<
+        (Entity, AssetId<StyleSheetAsset>),
+        usize
+    >,
+}
 
 #[derive(Debug, Clone, Default, Deref, DerefMut, Resource)]
 pub struct StyleSheetState(
@@ -49,44 +65,76 @@ pub struct StyleSheetState(
 
 impl StyleSheetStateBuilder
 {
+    /// Sets the position of the sheet on the chain of nested sheets of the given entities, used to sort rules of different sheets
+    /// with the same specificity. When the same sheet is attached at many depths above an entity, the deepest one is kept.
+    pub(crate) fn set_depth(
+        &mut self,
+        handle: &Handle<StyleSheetAsset>,
+        depth: usize,
+        entities: impl IntoIterator<Item = Entity>
+    ) {
+        for entity in entities
+        {
+            let current = self.depths.entry((entity, handle.id())).or_default();
+            *current = depth.max(*current);
+        }
+    }
+
     pub(crate) fn build(
         &mut self,
         assets: &Assets<StyleSheetAsset>
     ) -> StyleSheetState {
         let mut result = StyleSheetState::default();
 
-        for (handle, selected) in self.iter()
+        // Invert list of entities for each selector, into a list of selectors for each entity, from all sheets
+        let mut inverted = HashMap::<Entity, Vec<(usize, &Handle<StyleSheetAsset>, &StyleSheetAsset, &Selector)>>::new();
+        for (handle, selected) in self.sheets.iter()
         {
-            if let Some(sheet) = assets.get(handle)
+            let Some(sheet) = assets.get(handle)
+            else
             {
-                // Invert list of entities for each selector, into a list of selectors for each entity
-                let mut inverted = HashMap::<Entity, Vec<Selector>>::new();
-                for (selector, entities) in selected.iter()
+                continue;
+            };
+
+            for (selector, entities) in selected.iter()
+            {
+                for entity in entities.iter()
                 {
-                    for entity in entities.iter()
-                    {
-                        inverted.entry(*entity)
-                            .or_default()
-                            .push(selector.clone());
-                    }
+                    let depth = self.depths.get(&(*entity, handle.id())).copied().unwrap_or_default();
+                    inverted.entry(*entity)
+                        .or_default()
+                        .push((depth, handle, sheet, selector));
                 }
+            }
+        }
 
-                // "Pre-apply" the selectors to get a list of properties without duplicates
-                for (entity, mut selectors) in inverted
-                {
-                    let style = result.entry(entity).or_default();
+        // "Pre-apply" the selectors to get a list of properties without duplicates
+        for (entity, mut selectors) in inverted
+        {
+            let style = result.entry(entity).or_default();
+
+            // Sort by specificity, then nested sheets and then load order, so the winning selector is applied last
+            selectors.sort_by_key(|(depth, _, _, selector)| (selector.specificity(), *depth, selector.load_order()));
 
-                    selectors.sort();
-                    for selector in selectors.iter()
+            // Important declarations are applied after all normal ones, so they win regardless of specificity
+            for important in [false, true]
+            {
+                for (_, handle, sheet, selector) in selectors.iter()
+                {
+                    for prop in sheet.get_property_names(selector).unwrap_or_default()
                     {
-                        for prop in sheet.get_property_names(selector).unwrap_or_default()
+                        if sheet.is_important(selector, &prop) != important
                         {
-                            style.insert(prop, StyleSource
-                                {
-                                    styleheet: handle.clone(),
-                                    selector: selector.clone(),
-                                });
+                            continue;
                         }
+
+                        style.insert(prop, StyleSource
+                            {
+                                styleheet: (*handle).clone(),
+                                selector: (*selector).clone(),
+                                values: None,
+                                animated: false,
+                            });
                     }
                 }
             }
@@ -0,0 +1,62 @@
+use crate::{
+    prelude::{
+        BevyCssError,
+        PseudoElement,
+    },
+    property::{Property, PropertyToken, PropertyValues},
+};
+
+use bevy::{
+    ecs::query::QueryItem,
+    prelude::{
+        AssetServer,
+        Commands,
+        Text,
+        With,
+    },
+};
+
+/// Apply a `content` which updates [`TextSection::value`](`bevy::prelude::TextSection`) of pseudo-elements,
+/// like `::before` or `::after`
+#[derive(Default)]
+pub struct ContentProperty;
+
+impl Property
+for ContentProperty
+{
+    type Cache = String;
+    type Components = &'static mut Text;
+    type Filters = With<PseudoElement>;
+
+    fn name(
+        // no args
+    ) -> &'static str {
+        "content"
+    }
+
+    fn parse<'a>(
+        values: &PropertyValues
+    ) -> Result<Self::Cache, BevyCssError> {
+        // Unlike other string properties, empty content is allowed, like `content: ""` for decorative boxes
+        values.iter()
+            .find_map(|token| match token
+            {
+                PropertyToken::String(content) => Some(content.clone()),
+                _ => None,
+            })
+            .ok_or_else(|| BevyCssError::InvalidPropertyValue(Self::name().to_string()))
+    }
+
+    fn apply<'w>(
+        cache: &Self::Cache,
+        mut components: QueryItem<Self::Components>,
+        _asset_server: &AssetServer,
+        _commands: &mut Commands,
+    ) {
+        // Pseudo-elements are spawned with a single section, which style is set by other text properties
+        for section in components.sections.iter_mut()
+        {
+            section.value = cache.clone()
+        }
+    }
+}
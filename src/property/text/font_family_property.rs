@@ -0,0 +1,80 @@
+use crate::{
+    component::FontSelection,
+    prelude::BevyCssError,
+    property::{Property, PropertyToken, PropertyValues},
+};
+use bevy::{
+    ecs::query::QueryItem,
+    prelude::{
+        AssetServer,
+        Commands,
+        Entity,
+        Node,
+        Text,
+        With,
+    },
+};
+
+/// Applies the `font-family` property on [`FontSelection::families`] of matched [`Text`] entities,
+/// which is resolved into the fonts declared by `@font-face` rules.
+#[derive(Default)]
+pub struct FontFamilyProperty;
+
+impl FontFamilyProperty
+{
+    /// Parses a single family of the list, like `"Fira Sans"` or `Fira Sans`, where unquoted names are joined by a single space.
+    fn parse_family(
+        values: &PropertyValues
+    ) -> Option<String> {
+        if let [PropertyToken::String(name)] = values.as_slice()
+        {
+            return Some(name.clone());
+        }
+
+        values.iter()
+            .map(|token| match token
+            {
+                PropertyToken::Identifier(name) => Some(name.as_str()),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()
+            .filter(|names| !names.is_empty())
+            .map(|names| names.join(" "))
+    }
+}
+
+impl Property
+for FontFamilyProperty
+{
+    type Cache = Vec<String>;
+    type Components = (Entity, Option<&'static mut FontSelection>);
+    type Filters = (With<Node>, With<Text>);
+
+    fn name(
+        // no args
+    ) -> &'static str {
+        "font-family"
+    }
+
+    fn parse<'a>(
+        values: &PropertyValues
+    ) -> Result<Self::Cache, BevyCssError> {
+        values.comma_separated()
+            .iter()
+            .map(Self::parse_family)
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| BevyCssError::InvalidPropertyValue(Self::name().to_string()))
+    }
+
+    fn apply<'w>(
+        cache: &Self::Cache,
+        components: QueryItem<Self::Components>,
+        _asset_server: &AssetServer,
+        commands: &mut Commands,
+    ) {
+        let (entity, selection) = components;
+        let families = cache.clone();
+
+        FontSelection::apply(entity, selection, commands, move |selection| selection.families = families);
+    }
+}
@@ -1,27 +1,32 @@
 use crate::{
+    component::RelativeLengths,
     prelude::BevyCssError,
-    property::{Property, PropertyValues},
+    property::{LengthValue, Property, PropertyValues},
 };
 use bevy::{
     ecs::query::QueryItem,
     prelude::{
         AssetServer,
         Commands,
+        Entity,
         Node,
         Text,
         With,
     },
+    ui::Val,
 };
 
 /// Applies the `font-size` property on [`TextStyle::font_size`](`TextStyle`) property of all sections on matched [`Text`] components.
+///
+/// Relative lengths, like `2em` or `1.5rem`, are resolved against the font size of the parent or the [`RootFontSize`](crate::prelude::RootFontSize).
 #[derive(Default)]
-pub struct FontSizeProperty;
+pub(crate) struct FontSizeProperty;
 
 impl Property
 for FontSizeProperty
 {
-    type Cache = f32;
-    type Components = &'static mut Text;
+    type Cache = LengthValue;
+    type Components = (Entity, &'static mut Text, Option<&'static mut RelativeLengths>);
     type Filters = With<Node>;
 
     fn name(
@@ -33,22 +38,26 @@ for FontSizeProperty
     fn parse<'a>(
         values: &PropertyValues
     ) -> Result<Self::Cache, BevyCssError> {
-        match values.f32()
+        match values.length()
         {
-            Some(size) => Ok(size),
-            None => Err(BevyCssError::InvalidPropertyValue(Self::name().to_string())),
+            Some(LengthValue::Val(Val::Auto | Val::Percent(_))) | None => match values.f32()
+            {
+                Some(size) => Ok(LengthValue::Val(Val::Px(size))),
+                None => Err(BevyCssError::InvalidPropertyValue(Self::name().to_string())),
+            },
+
+            Some(length) => Ok(length),
         }
     }
 
     fn apply<'w>(
         cache: &Self::Cache,
-        mut components: QueryItem<Self::Components>,
+        components: QueryItem<Self::Components>,
         _asset_server: &AssetServer,
-        _commands: &mut Commands,
+        commands: &mut Commands,
     ) {
-        for section in components.sections.iter_mut()
-        {
-            section.style.font_size = *cache
-        }
+        let (entity, mut text, mut lengths) = components;
+
+        RelativeLengths::apply_font_size(cache, entity, &mut text, lengths.as_mut(), commands);
     }
 }
@@ -0,0 +1,61 @@
+use crate::{
+    component::FontSelection,
+    prelude::BevyCssError,
+    property::{Property, PropertyToken, PropertyValues},
+};
+use bevy::{
+    ecs::query::QueryItem,
+    prelude::{
+        AssetServer,
+        Commands,
+        Entity,
+        Node,
+        Text,
+        With,
+    },
+};
+
+/// Applies the `font-weight` property on [`FontSelection::weight`] of matched [`Text`] entities,
+/// which picks between the fonts of the same family declared by `@font-face` rules.
+#[derive(Default)]
+pub struct FontWeightProperty;
+
+impl Property
+for FontWeightProperty
+{
+    type Cache = f32;
+    type Components = (Entity, Option<&'static mut FontSelection>);
+    type Filters = (With<Node>, With<Text>);
+
+    fn name(
+        // no args
+    ) -> &'static str {
+        "font-weight"
+    }
+
+    fn parse<'a>(
+        values: &PropertyValues
+    ) -> Result<Self::Cache, BevyCssError> {
+        let weight = match values.as_slice()
+        {
+            [PropertyToken::Identifier(name)] if name == "normal" => Some(400.0),
+            [PropertyToken::Identifier(name)] if name == "bold" => Some(700.0),
+            [PropertyToken::Number(weight)] if (1.0..=1000.0).contains(weight) => Some(*weight),
+            _ => None,
+        };
+
+        weight.ok_or_else(|| BevyCssError::InvalidPropertyValue(Self::name().to_string()))
+    }
+
+    fn apply<'w>(
+        cache: &Self::Cache,
+        components: QueryItem<Self::Components>,
+        _asset_server: &AssetServer,
+        commands: &mut Commands,
+    ) {
+        let (entity, selection) = components;
+        let weight = *cache;
+
+        FontSelection::apply(entity, selection, commands, move |selection| selection.weight = weight);
+    }
+}
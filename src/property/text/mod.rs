@@ -1,11 +1,22 @@
+#[cfg(feature = "pseudo_prop")]
+mod content_property;
+#[cfg(feature = "pseudo_prop")]
+pub use content_property::*;
+
 mod font_color_property;
 pub use font_color_property::*;
 
+mod font_family_property;
+pub use font_family_property::*;
+
 mod font_property;
 pub use font_property::*;
 
 mod font_size_property;
-pub use font_size_property::*;
+pub(crate) use font_size_property::*;
+
+mod font_weight_property;
+pub use font_weight_property::*;
 
 mod text_align_property;
 pub use text_align_property::*;
@@ -0,0 +1,211 @@
+use crate::prelude::BevyCssError;
+
+use cssparser::{
+    match_ignore_ascii_case,
+    ParseError, Parser,
+};
+
+/// When the jumps of a [`steps()`](TimingFunction::Steps) timing function happens, like `jump-start` on `steps(4, jump-start)`.
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(PartialEq)]
+pub enum StepPosition
+{
+    /// The first jump happens at the start, like `start`.
+    JumpStart,
+    /// The last jump happens at the end, like `end`.
+    #[default]
+    JumpEnd,
+    /// There are no jumps at the start nor at the end.
+    JumpNone,
+    /// There are jumps at both the start and the end.
+    JumpBoth,
+}
+
+/// An easing function which changes the speed of an animation, like `ease-in-out` or `cubic-bezier(0.1, 0.7, 1, 0.1)`.
+#[derive(Clone, Copy, Debug)]
+#[derive(PartialEq)]
+pub enum TimingFunction
+{
+    /// Constant speed, like `linear`.
+    Linear,
+
+    /// A cubic Bézier curve with the given control points, like `cubic-bezier(0.42, 0, 1, 1)` or `ease-in`.
+    CubicBezier(f32, f32, f32, f32),
+
+    /// Jumps between the given number of equal steps, like `steps(4, jump-end)` or `step-start`.
+    Steps(u32, StepPosition),
+}
+
+impl Default
+for TimingFunction
+{
+    fn default(
+        // no args
+    ) -> Self {
+        Self::CubicBezier(0.25, 0.1, 0.25, 1.0)
+    }
+}
+
+impl TimingFunction
+{
+    /// Gets the timing function of a keyword, like `ease-in` or `step-end`.
+    pub fn from_name(
+        name: &str
+    ) -> Option<Self> {
+        match_ignore_ascii_case! { name,
+            "linear" => Some(TimingFunction::Linear),
+            "ease" => Some(TimingFunction::default()),
+            "ease-in" => Some(TimingFunction::CubicBezier(0.42, 0.0, 1.0, 1.0)),
+            "ease-out" => Some(TimingFunction::CubicBezier(0.0, 0.0, 0.58, 1.0)),
+            "ease-in-out" => Some(TimingFunction::CubicBezier(0.42, 0.0, 0.58, 1.0)),
+            "step-start" => Some(TimingFunction::Steps(1, StepPosition::JumpStart)),
+            "step-end" => Some(TimingFunction::Steps(1, StepPosition::JumpEnd)),
+            _ => None,
+        }
+    }
+
+    /// Eases the progress of an animation, from `0.0` to `1.0`, where the result may be outside of that range,
+    /// like on `cubic-bezier(0.5, -0.5, 0.5, 1.5)`.
+    pub fn ease(
+        &self,
+        progress: f32
+    ) -> f32 {
+        match *self
+        {
+            TimingFunction::Linear => progress,
+            TimingFunction::CubicBezier(x1, y1, x2, y2) => cubic_bezier(x1, y1, x2, y2, progress),
+            TimingFunction::Steps(steps, position) => {
+                let steps = steps.max(1) as f32;
+                let mut step = (progress * steps).floor();
+
+                if matches!(position, StepPosition::JumpStart | StepPosition::JumpBoth)
+                {
+                    step += 1.0;
+                }
+
+                let jumps = match position
+                {
+                    StepPosition::JumpStart | StepPosition::JumpEnd => steps,
+                    StepPosition::JumpNone => (steps - 1.0).max(1.0),
+                    StepPosition::JumpBoth => steps + 1.0,
+                };
+
+                (step / jumps).clamp(0.0, 1.0)
+            }
+        }
+    }
+}
+
+/// Checks if the function name is an easing function, like `cubic-bezier` or `steps`.
+pub(crate) fn is_timing_function(
+    name: &str
+) -> bool {
+    match_ignore_ascii_case! { name,
+        "cubic-bezier" | "steps" => true,
+        _ => false,
+    }
+}
+
+/// Parses the arguments of an easing function, like `4, jump-end` inside `steps(...)`, into a [`TimingFunction`].
+pub(crate) fn parse_timing_function<'i>(
+    name: &str,
+    input: &mut Parser<'i, '_>,
+) -> Result<TimingFunction, ParseError<'i, BevyCssError>> {
+    if name.eq_ignore_ascii_case("cubic-bezier")
+    {
+        let x1 = input.expect_number()?;
+        input.expect_comma()?;
+        let y1 = input.expect_number()?;
+        input.expect_comma()?;
+        let x2 = input.expect_number()?;
+        input.expect_comma()?;
+        let y2 = input.expect_number()?;
+
+        // Both `x` must be on the `[0, 1]` range, otherwise the curve wouldn't be a function of time
+        if !(0.0..=1.0).contains(&x1) || !(0.0..=1.0).contains(&x2)
+        {
+            return Err(input.new_custom_error(BevyCssError::InvalidPropertyValue(format!("cubic-bezier({x1}, {y1}, {x2}, {y2})"))));
+        }
+
+        return Ok(TimingFunction::CubicBezier(x1, y1, x2, y2));
+    }
+
+    let steps = input.expect_integer()?;
+    let position = match input.try_parse(|input| input.expect_comma()).is_ok()
+    {
+        true => {
+            let location = input.current_source_location();
+            let ident = input.expect_ident_cloned()?;
+            match_ignore_ascii_case! { &ident,
+                "jump-start" | "start" => StepPosition::JumpStart,
+                "jump-end" | "end" => StepPosition::JumpEnd,
+                "jump-none" => StepPosition::JumpNone,
+                "jump-both" => StepPosition::JumpBoth,
+                _ => return Err(location.new_custom_error(BevyCssError::InvalidPropertyValue(ident.to_string()))),
+            }
+        }
+        false => StepPosition::JumpEnd,
+    };
+
+    // `jump-none` needs at least two steps, since it has no jumps at the start nor at the end
+    let minimum = match position
+    {
+        StepPosition::JumpNone => 2,
+        _ => 1,
+    };
+    if steps < minimum
+    {
+        return Err(input.new_custom_error(BevyCssError::InvalidPropertyValue(format!("steps({steps})"))));
+    }
+
+    Ok(TimingFunction::Steps(steps as u32, position))
+}
+
+/// Evaluates the `y` of a cubic Bézier curve from `(0, 0)` to `(1, 1)` at the given `x`.
+fn cubic_bezier(
+    x1: f32,
+    y1: f32,
+    x2: f32,
+    y2: f32,
+    x: f32
+) -> f32 {
+    if x <= 0.0 || x >= 1.0
+    {
+        return x;
+    }
+
+    let bezier = |p1: f32, p2: f32, t: f32| 3.0 * (1.0 - t).powi(2) * t * p1 + 3.0 * (1.0 - t) * t.powi(2) * p2 + t.powi(3);
+    let derivative = |p1: f32, p2: f32, t: f32| 3.0 * (1.0 - t).powi(2) * p1 + 6.0 * (1.0 - t) * t * (p2 - p1) + 3.0 * t.powi(2) * (1.0 - p2);
+
+    // Newton's method is usually enough, otherwise fallback to bisection, since `x` always increases with `t`
+    let mut t = x;
+    for _ in 0..8
+    {
+        let error = bezier(x1, x2, t) - x;
+        let slope = derivative(x1, x2, t);
+
+        if error.abs() < 1e-6
+        {
+            return bezier(y1, y2, t);
+        }
+        if slope.abs() < 1e-6
+        {
+            break;
+        }
+        t -= error / slope;
+    }
+
+    let (mut low, mut high) = (0.0, 1.0);
+    t = x;
+    for _ in 0..32
+    {
+        match bezier(x1, x2, t) < x
+        {
+            true => low = t,
+            false => high = t,
+        }
+        t = (low + high) / 2.0;
+    }
+
+    bezier(y1, y2, t)
+}
@@ -0,0 +1,96 @@
+use super::LengthContext;
+
+use bevy::ui::Val;
+
+/// The unit of a [`Dimension`](super::PropertyToken::Dimension) value, like `px` on `10px`.
+#[derive(Clone, Copy, Debug)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Unit
+{
+    /// Logical pixels, like `10px`.
+    Px,
+
+    /// Percent of the viewport width, like `10vw`.
+    Vw,
+
+    /// Percent of the viewport height, like `10vh`.
+    Vh,
+
+    /// Percent of the smallest viewport size, like `10vmin`.
+    VMin,
+
+    /// Percent of the largest viewport size, like `10vmax`.
+    VMax,
+
+    /// Multiples of the inherited font size, like `2em`.
+    Em,
+
+    /// Multiples of the root font size, like `2rem`.
+    Rem,
+}
+
+impl Unit
+{
+    /// All supported units.
+    pub const ALL: [Unit; 7] = [Unit::Px, Unit::Vw, Unit::Vh, Unit::VMin, Unit::VMax, Unit::Em, Unit::Rem];
+
+    /// Gets the unit by its css name, like `px` or `vmin`.
+    pub fn from_name(
+        name: &str
+    ) -> Option<Self> {
+        Self::ALL.into_iter()
+            .find(|unit| unit.name().eq_ignore_ascii_case(name))
+    }
+
+    /// The css name of this unit, like `px` or `vmin`.
+    pub fn name(
+        &self
+    ) -> &'static str {
+        match self
+        {
+            Unit::Px => "px",
+            Unit::Vw => "vw",
+            Unit::Vh => "vh",
+            Unit::VMin => "vmin",
+            Unit::VMax => "vmax",
+            Unit::Em => "em",
+            Unit::Rem => "rem",
+        }
+    }
+
+    /// Converts the value into a [`Val`], when there is an equivalent one.
+    ///
+    /// Font relative units, like `em`, returns [`None`] since they must be resolved with [`px`](Unit::px).
+    pub fn val(
+        &self,
+        value: f32
+    ) -> Option<Val> {
+        match self
+        {
+            Unit::Px => Some(Val::Px(value)),
+            Unit::Vw => Some(Val::Vw(value)),
+            Unit::Vh => Some(Val::Vh(value)),
+            Unit::VMin => Some(Val::VMin(value)),
+            Unit::VMax => Some(Val::VMax(value)),
+            Unit::Em | Unit::Rem => None,
+        }
+    }
+
+    /// Converts the value into logical pixels, using the given [`LengthContext`].
+    pub fn px(
+        &self,
+        value: f32,
+        context: &LengthContext
+    ) -> f32 {
+        match self
+        {
+            Unit::Px => value,
+            Unit::Vw => value * context.viewport.x / 100.0,
+            Unit::Vh => value * context.viewport.y / 100.0,
+            Unit::VMin => value * context.viewport.min_element() / 100.0,
+            Unit::VMax => value * context.viewport.max_element() / 100.0,
+            Unit::Em => value * context.font_size,
+            Unit::Rem => value * context.root_font_size,
+        }
+    }
+}
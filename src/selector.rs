@@ -6,7 +6,13 @@ use std::{
     sync::Mutex,
 };
 
-use crate::DynArray;
+use crate::{
+    DynArray,
+    stylesheet::MediaQueryList,
+};
+
+#[cfg(feature = "pseudo_prop")]
+use crate::component::PseudoElement;
 
 static RULE_COUNTER: Mutex<usize> = Mutex::new(0);
 
@@ -25,19 +31,299 @@ pub enum SelectorElement
     /// A component selector element, like `window` or `button`
     Component(String),
 
+    /// An universal selector element, `*`, which matches any entity
+    Universal,
+
     /// A class name component selector element, `.border`
     Class(String),
 
+    /// An attribute selector element, like `[disabled]` or `[rarity="epic"]`.
+    /// Attributes are resolved from registered attribute selectors or reflected component fields.
+    Attribute(String, AttributeMatch),
+
     #[cfg(feature = "pseudo_class")]
     /// A class name component selector element, like `:hover` or `:first-child` or `:empty`
     PseudoClass(String),
 
+    #[cfg(feature = "pseudo_class")]
+    /// A structural pseudo-class selector element which counts siblings from the first child, like `:nth-child(2n+1)`
+    NthChild(Nth),
+
+    #[cfg(feature = "pseudo_class")]
+    /// A structural pseudo-class selector element which counts siblings from the last child, like `:nth-last-child(2)`
+    NthLastChild(Nth),
+
+    #[cfg(feature = "pseudo_class")]
+    /// A negation pseudo-class selector element, which matches entities not matching any of the selectors, like `:not(.hidden)`
+    Not(Vec<Selector>),
+
+    #[cfg(feature = "pseudo_class")]
+    /// A matching-list pseudo-class selector element, which matches entities matching any of the selectors, like `:is(button, .slot)`
+    Is(Vec<Selector>),
+
+    #[cfg(feature = "pseudo_class")]
+    /// Same as [`Is`](SelectorElement::Is), but without adding any specificity, like `:where(button, .slot)`
+    Where(Vec<Selector>),
+
+    #[cfg(feature = "pseudo_class")]
+    /// A relational pseudo-class selector element, which matches entities with any related entity matching
+    /// one of the relative selectors, like `:has(> .item)`. Each relative selector starts with its combinator.
+    Has(Vec<Selector>),
+
     #[cfg(feature = "pseudo_prop")]
-    /// A class name component selector element, like `::first-line` or `::first-letter` or `::marker`
-    PseudoProp(String),
+    /// A pseudo-element selector element, like `::before`, `::after` or `::marker`.
+    /// It must be the last element of a selector.
+    PseudoProp(PseudoElement),
 
     /// Indicates a parent-child relation between previous elements and next elements, like `window .border`
     Child,
+
+    /// Indicates a direct parent-child relation between previous elements and next elements, like `window > .border`
+    DirectChild,
+
+    /// Indicates next elements immediately follows previous elements under the same parent, like `.selected + .slot`
+    AdjacentSibling,
+
+    /// Indicates next elements follows previous elements under the same parent, like `.active ~ .tab`
+    GeneralSibling,
+}
+
+impl SelectorElement
+{
+    /// Checks if this element is a combinator, which separates compound selectors, like [`Child`](SelectorElement::Child)
+    pub fn is_combinator(
+        &self
+    ) -> bool {
+        matches!(
+            self,
+            SelectorElement::Child
+            | SelectorElement::DirectChild
+            | SelectorElement::AdjacentSibling
+            | SelectorElement::GeneralSibling
+        )
+    }
+}
+
+/// Represents how an [`Attribute`](SelectorElement::Attribute) selector element compares the attribute value.
+#[derive(Clone, Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(PartialOrd, Ord)]
+#[derive(Hash)]
+pub enum AttributeMatch
+{
+    /// Matches when the attribute is present, like `[disabled]`
+    Exists,
+
+    /// Matches when the attribute is exactly the given value, like `[rarity="epic"]`
+    Equals(String),
+
+    /// Matches when any of the whitespace separated words of the attribute is the given value, like `[tags~="heavy"]`
+    Includes(String),
+
+    /// Matches when the attribute is the given value or starts with it followed by `-`, like `[lang|="en"]`
+    DashMatch(String),
+
+    /// Matches when the attribute starts with the given value, like `[value^="Warning"]`
+    Prefix(String),
+
+    /// Matches when the attribute ends with the given value, like `[value$="!"]`
+    Suffix(String),
+
+    /// Matches when the attribute contains the given value, like `[value*="gold"]`
+    Substring(String),
+}
+
+impl AttributeMatch
+{
+    /// Checks if the given attribute value satisfies this match.
+    pub fn matches(
+        &self,
+        attribute: &str
+    ) -> bool {
+        match self
+        {
+            AttributeMatch::Exists => true,
+            AttributeMatch::Equals(value) => attribute == value,
+            AttributeMatch::Includes(value) => attribute.split_ascii_whitespace().any(|word| word == value),
+            AttributeMatch::DashMatch(value) => attribute == value
+                || attribute.strip_prefix(value.as_str()).is_some_and(|rest| rest.starts_with('-')),
+            // Empty values never matches, as on web CSS
+            AttributeMatch::Prefix(value) => !value.is_empty() && attribute.starts_with(value.as_str()),
+            AttributeMatch::Suffix(value) => !value.is_empty() && attribute.ends_with(value.as_str()),
+            AttributeMatch::Substring(value) => !value.is_empty() && attribute.contains(value.as_str()),
+        }
+    }
+}
+
+impl std::fmt::Display
+for AttributeMatch
+{
+    fn fmt(
+        &self,
+        formatter: &mut std::fmt::Formatter<'_>
+    ) -> std::fmt::Result {
+        let (operator, value) = match self
+        {
+            AttributeMatch::Exists => return Ok(()),
+            AttributeMatch::Equals(value) => ("=", value),
+            AttributeMatch::Includes(value) => ("~=", value),
+            AttributeMatch::DashMatch(value) => ("|=", value),
+            AttributeMatch::Prefix(value) => ("^=", value),
+            AttributeMatch::Suffix(value) => ("$=", value),
+            AttributeMatch::Substring(value) => ("*=", value),
+        };
+
+        write!(formatter, "{}{:?}", operator, value)
+    }
+}
+
+/// The [specificity](https://developer.mozilla.org/en-US/docs/Web/CSS/Specificity) of a [`Selector`],
+/// used to determine which rule wins when many rules applies the same property on an entity.
+///
+/// Specificities are compared by `ids` first, then `classes` and at last `types`.
+#[derive(Clone, Copy, Debug, Default)]
+#[derive(PartialEq, Eq)]
+#[derive(PartialOrd, Ord)]
+#[derive(Hash)]
+pub struct Specificity
+{
+    /// Number of [`Name`](SelectorElement::Name) elements, like `#inventory`
+    pub ids: u32,
+
+    /// Number of [`Class`](SelectorElement::Class), [`Attribute`](SelectorElement::Attribute) and pseudo-class elements,
+    /// like `.border`, `[disabled]` or `:hover`
+    pub classes: u32,
+
+    /// Number of [`Component`](SelectorElement::Component) and pseudo-element elements, like `button` or `::before`
+    pub types: u32,
+}
+
+impl Specificity
+{
+    /// Computes the specificity of the given selector elements.
+    ///
+    /// [`Universal`](SelectorElement::Universal), [`Where`](SelectorElement::Where) and combinator elements doesn't add any specificity.
+    pub fn of<'a>(
+        elements: impl IntoIterator<Item = &'a SelectorElement>
+    ) -> Self {
+        elements.into_iter()
+            .fold(Self::default(), |mut specificity, element|
+            {
+                match element
+                {
+                    SelectorElement::Name(_) => specificity.ids += 1,
+
+                    SelectorElement::Class(_)
+                    | SelectorElement::Attribute(..) => specificity.classes += 1,
+
+                    #[cfg(feature = "pseudo_class")]
+                    SelectorElement::PseudoClass(_)
+                    | SelectorElement::NthChild(_)
+                    | SelectorElement::NthLastChild(_) => specificity.classes += 1,
+
+                    // Takes the specificity of the most specific selector in the list
+                    #[cfg(feature = "pseudo_class")]
+                    SelectorElement::Not(selectors)
+                    | SelectorElement::Is(selectors)
+                    | SelectorElement::Has(selectors) => specificity += selectors.iter()
+                        .map(Selector::specificity)
+                        .max()
+                        .unwrap_or_default(),
+
+                    #[cfg(feature = "pseudo_class")]
+                    SelectorElement::Where(_) => (),
+
+                    SelectorElement::Component(_) => specificity.types += 1,
+
+                    #[cfg(feature = "pseudo_prop")]
+                    SelectorElement::PseudoProp(_) => specificity.types += 1,
+
+                    SelectorElement::Universal
+                    | SelectorElement::Child
+                    | SelectorElement::DirectChild
+                    | SelectorElement::AdjacentSibling
+                    | SelectorElement::GeneralSibling => (),
+                }
+
+                specificity
+            })
+    }
+}
+
+impl std::ops::AddAssign
+for Specificity
+{
+    fn add_assign(
+        &mut self,
+        other: Self
+    ) {
+        self.ids += other.ids;
+        self.classes += other.classes;
+        self.types += other.types;
+    }
+}
+
+impl std::fmt::Display
+for Specificity
+{
+    fn fmt(
+        &self,
+        formatter: &mut std::fmt::Formatter<'_>
+    ) -> std::fmt::Result {
+        write!(formatter, "({}, {}, {})", self.ids, self.classes, self.types)
+    }
+}
+
+/// Represents the [`An+B`](https://developer.mozilla.org/en-US/docs/Web/CSS/:nth-child#functional_notation) microsyntax,
+/// used by structural pseudo-classes like `:nth-child(2n+1)`.
+#[cfg(feature = "pseudo_class")]
+#[derive(Clone, Copy, Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(PartialOrd, Ord)]
+#[derive(Hash)]
+pub struct Nth
+{
+    /// Step size, the `A` in `An+B`
+    pub a: i32,
+
+    /// Offset, the `B` in `An+B`
+    pub b: i32,
+}
+
+#[cfg(feature = "pseudo_class")]
+impl Nth
+{
+    /// Checks if the given 1-based index matches `An+B` for any non negative `n`.
+    pub fn matches(
+        &self,
+        index: i32
+    ) -> bool {
+        match self.a
+        {
+            0 => index == self.b,
+            a => {
+                let offset = index - self.b;
+                offset % a == 0 && offset / a >= 0
+            }
+        }
+    }
+}
+
+#[cfg(feature = "pseudo_class")]
+impl std::fmt::Display
+for Nth
+{
+    fn fmt(
+        &self,
+        formatter: &mut std::fmt::Formatter<'_>
+    ) -> std::fmt::Result {
+        match (self.a, self.b)
+        {
+            (0, b) => write!(formatter, "{b}"),
+            (a, 0) => write!(formatter, "{a}n"),
+            (a, b) => write!(formatter, "{a}n{b:+}"),
+        }
+    }
 }
 
 /// A selector parsed from a `css` rule. Each selector has a internal hash used to differentiate between many rules in the same sheet.
@@ -46,6 +332,8 @@ pub struct Selector
 {
     hash: u64,
     elements: DynArray<SelectorElement>,
+    /// Specificity computed from elements, used to sort rules
+    specificity: Specificity,
     /// Rule loading order from parser
     load_order: usize,
 }
@@ -66,16 +354,140 @@ impl Selector
 
         let hash = hasher.finish();
         Self{
+            specificity: Specificity::of(&elements),
             elements,
             hash,
-            load_order: RULE_COUNTER
-                .lock()
-                .map(|mut lock|
-                {
-                    *lock += 1;
-                    *lock
-                })
-                .unwrap_or_default(),
+            load_order: Self::next_load_order(),
+        }
+    }
+
+    fn next_load_order(
+        // no args
+    ) -> usize {
+        RULE_COUNTER
+            .lock()
+            .map(|mut lock|
+            {
+                *lock += 1;
+                *lock
+            })
+            .unwrap_or_default()
+    }
+
+    /// Makes this selector distinct from the same selector on other rules, like `h1` on `h1, h2 { ... } h1 { ... }`,
+    /// so each rule is matched, cached and sorted independently.
+    pub(crate) fn restrict_to_rule(
+        &mut self
+    ) {
+        let mut hasher = AHasher::default();
+        self.hash.hash(&mut hasher);
+        self.load_order.hash(&mut hasher);
+        self.hash = hasher.finish();
+    }
+
+    /// Makes this selector distinct from the same selector outside the given `@media` rule,
+    /// so each one is matched and cached independently.
+    pub(crate) fn restrict_to_media(
+        &mut self,
+        media: &MediaQueryList
+    ) {
+        let mut hasher = AHasher::default();
+        self.hash.hash(&mut hasher);
+        media.hash(&mut hasher);
+        self.hash = hasher.finish();
+    }
+
+    /// Assigns a new load order to this selector, as if it was just parsed, so it's sorted after all existing rules.
+    pub(crate) fn reset_load_order(
+        &mut self
+    ) {
+        self.load_order = Self::next_load_order();
+    }
+
+    /// The [`Specificity`] of this selector, used to sort rules which applies on the same entity.
+    pub fn specificity(
+        &self
+    ) -> Specificity {
+        self.specificity
+    }
+
+    /// Rule loading order from parser, used to sort rules with the same [`Specificity`].
+    pub fn load_order(
+        &self
+    ) -> usize {
+        self.load_order
+    }
+
+    /// Checks if this selector depends on entities related to the selected ones, like `.slot:has(.item)`,
+    /// so it must be matched again when any of those entities changes.
+    #[cfg(feature = "pseudo_class")]
+    pub fn is_relational(
+        &self
+    ) -> bool {
+        self.elements.iter()
+            .any(|element| match element
+            {
+                SelectorElement::Has(_) => true,
+                SelectorElement::Not(selectors)
+                | SelectorElement::Is(selectors)
+                | SelectorElement::Where(selectors) => selectors.iter().any(Selector::is_relational),
+                _ => false,
+            })
+    }
+
+    /// Checks if this selector uses the given pseudo-class, like `root` for `:root > .header`.
+    #[cfg(feature = "pseudo_class")]
+    pub fn has_pseudo_class(
+        &self,
+        name: &str
+    ) -> bool {
+        self.elements.iter()
+            .any(|element| match element
+            {
+                SelectorElement::PseudoClass(pseudo_class) => pseudo_class == name,
+                SelectorElement::Not(selectors)
+                | SelectorElement::Is(selectors)
+                | SelectorElement::Where(selectors)
+                | SelectorElement::Has(selectors) => selectors.iter().any(|selector| selector.has_pseudo_class(name)),
+                _ => false,
+            })
+    }
+
+    /// Collects names of the attributes used by this selector, like `rarity` for `.slot[rarity="epic"]`.
+    pub fn attribute_names(
+        &self
+    ) -> DynArray<&str> {
+        self.elements.iter()
+            .flat_map(|element| match element
+            {
+                SelectorElement::Attribute(name, _) => DynArray::from_elem(name.as_str(), 1),
+                #[cfg(feature = "pseudo_class")]
+                SelectorElement::Not(selectors)
+                | SelectorElement::Is(selectors)
+                | SelectorElement::Where(selectors)
+                | SelectorElement::Has(selectors) => selectors.iter().flat_map(Selector::attribute_names).collect(),
+                _ => DynArray::new(),
+            })
+            .collect()
+    }
+
+    /// Splits this selector into the selector of the originating entity and its pseudo-element,
+    /// like `.item` and [`Before`](PseudoElement::Before) for `.item::before`.
+    #[cfg(feature = "pseudo_prop")]
+    pub fn split_pseudo_element(
+        &self
+    ) -> Option<(Selector, PseudoElement)> {
+        match self.elements.last()
+        {
+            Some(SelectorElement::PseudoProp(element)) => {
+                let originating = Selector {
+                    elements: self.elements[..self.elements.len() - 1].into(),
+                    ..self.clone()
+                };
+
+                Some((originating, *element))
+            }
+            _ => None,
         }
     }
 
@@ -84,21 +496,35 @@ impl Selector
     pub fn get_parent_tree(
         &self
     ) -> DynArray<DynArray<&SelectorElement>> {
+        self.get_combinator_tree()
+            .into_iter()
+            .map(|(_combinator, node)| node)
+            .collect()
+    }
+
+    /// Builds a selector tree for this selector, keeping the combinator which relates each node to the previous one.
+    ///
+    /// The first node has no combinator, since there is no previous node to relate to.
+    pub fn get_combinator_tree(
+        &self
+    ) -> DynArray<(Option<&SelectorElement>, DynArray<&SelectorElement>)> {
         let mut tree = DynArray::new();
+        let mut combinator = None;
         let mut current_level = DynArray::new();
 
         for element in &self.elements
         {
-            match element
+            match element.is_combinator()
             {
-                SelectorElement::Child => {
-                    tree.push(current_level);
+                true => {
+                    tree.push((combinator, current_level));
+                    combinator = Some(element);
                     current_level = DynArray::new();
                 }
-                _ => current_level.push(element),
+                false => current_level.push(element),
             }
         }
-        tree.push(current_level);
+        tree.push((combinator, current_level));
 
         tree
     }
@@ -126,26 +552,75 @@ for Selector
                     buffer.push_str(c);
                 }
 
+                SelectorElement::Universal => {
+                    buffer.push('*');
+                }
+
                 SelectorElement::Class(c) => {
                     buffer.push('.');
                     buffer.push_str(c);
                 }
 
+                SelectorElement::Attribute(name, matching) => {
+                    buffer.push_str(&format!("[{name}{matching}]"));
+                }
+
                 #[cfg(feature = "pseudo_class")]
                 SelectorElement::PseudoClass(c) => {
                     buffer.push(':');
                     buffer.push_str(c);
                 }
 
+                #[cfg(feature = "pseudo_class")]
+                SelectorElement::NthChild(nth) => {
+                    buffer.push_str(&format!(":nth-child({nth})"));
+                }
+
+                #[cfg(feature = "pseudo_class")]
+                SelectorElement::NthLastChild(nth) => {
+                    buffer.push_str(&format!(":nth-last-child({nth})"));
+                }
+
+                #[cfg(feature = "pseudo_class")]
+                SelectorElement::Not(selectors) => {
+                    buffer.push_str(&format!(":not({})", join_selectors(selectors)));
+                }
+
+                #[cfg(feature = "pseudo_class")]
+                SelectorElement::Is(selectors) => {
+                    buffer.push_str(&format!(":is({})", join_selectors(selectors)));
+                }
+
+                #[cfg(feature = "pseudo_class")]
+                SelectorElement::Where(selectors) => {
+                    buffer.push_str(&format!(":where({})", join_selectors(selectors)));
+                }
+
+                #[cfg(feature = "pseudo_class")]
+                SelectorElement::Has(selectors) => {
+                    buffer.push_str(&format!(":has({})", join_selectors(selectors)));
+                }
+
                 #[cfg(feature = "pseudo_prop")]
                 SelectorElement::PseudoProp(p) => {
-                    buffer.push_str("::");
-                    buffer.push_str(p);
+                    buffer.push_str(&p.to_string());
                 }
 
                 SelectorElement::Child => {
                     buffer.push(' ');
                 }
+
+                SelectorElement::DirectChild => {
+                    buffer.push_str(" > ");
+                }
+
+                SelectorElement::AdjacentSibling => {
+                    buffer.push_str(" + ");
+                }
+
+                SelectorElement::GeneralSibling => {
+                    buffer.push_str(" ~ ");
+                }
             }
         }
 
@@ -153,6 +628,18 @@ for Selector
     }
 }
 
+/// Joins a list of selectors, like the ones used by `:is(a, b)`, separated by commas
+#[cfg(feature = "pseudo_class")]
+fn join_selectors(
+    selectors: &[Selector]
+) -> String {
+    // Relative selectors starts with a combinator, like `:has(> .item)`
+    selectors.iter()
+        .map(|selector| selector.to_string().trim_start().to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 impl PartialEq
 for Selector
 {
@@ -188,7 +675,7 @@ for Selector
         &self,
         other: &Self
     ) -> std::cmp::Ordering {
-        match self.elements.len().cmp(&other.elements.len())
+        match self.specificity.cmp(&other.specificity)
         {
             Ordering::Equal => self.load_order.cmp(&other.load_order),
             not_eq => not_eq,
@@ -0,0 +1,74 @@
+/// A font declared by a `@font-face` rule, like `@font-face { font-family: "Fira"; src: url("fonts/FiraSans-Bold.ttf"); }`.
+#[derive(Debug, Clone)]
+#[derive(PartialEq)]
+pub struct FontFace
+{
+    /// Name of the font family, like `Fira`, which is matched by `font-family` ignoring case.
+    pub family: String,
+
+    /// Asset path of the font, like `fonts/FiraSans-Bold.ttf`.
+    pub path: String,
+
+    /// Lightest and boldest weights of the font, which are the same unless a range is declared, like `font-weight: 100 900`.
+    pub weight: (f32, f32),
+}
+
+impl FontFace
+{
+    /// Finds the font of the given family which best matches the given weight, like `700` for `bold`.
+    ///
+    /// As on CSS, a font containing the weight is used, otherwise the nearest lighter or bolder one is preferred,
+    /// depending on whether the weight is below `400`, above `500` or in between, where bolder fonts up to `500` come first.
+    /// Later fonts wins when many are equally good.
+    pub fn find<'a>(
+        faces: impl IntoIterator<Item = &'a FontFace>,
+        family: &str,
+        weight: f32,
+    ) -> Option<&'a FontFace> {
+        faces.into_iter()
+            .filter(|face| face.family.eq_ignore_ascii_case(family))
+            .map(|face| (face.weight_distance(weight), face))
+            .reduce(|best, current| match current.0 <= best.0
+            {
+                true => current,
+                false => best,
+            })
+            .map(|(_, face)| face)
+    }
+
+    /// Distance of this font to the given weight, as the preference tier and then the difference between the weights.
+    fn weight_distance(
+        &self,
+        weight: f32
+    ) -> (u8, f32) {
+        let (min, max) = self.weight;
+
+        if (min..=max).contains(&weight)
+        {
+            return (0, 0.0);
+        }
+
+        let lighter = (weight - max).max(0.0);
+        let bolder = (min - weight).max(0.0);
+
+        match weight
+        {
+            _ if weight < 400.0 => match max < weight
+            {
+                true => (1, lighter),
+                false => (2, bolder),
+            },
+
+            _ if weight > 500.0 => match min > weight
+            {
+                true => (1, bolder),
+                false => (2, lighter),
+            },
+
+            // Between 400 and 500, bolder fonts up to 500 comes first, then lighter ones and then the remaining bolder ones
+            _ if min > weight && min <= 500.0 => (1, bolder),
+            _ if max < weight => (2, lighter),
+            _ => (3, bolder),
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use crate::property::{
+    PropertyValues,
+    TimingFunction,
+};
+
+use bevy::utils::HashMap;
+
+/// A single keyframe of a `@keyframes` rule, like `50% { width: 100px; }`.
+#[derive(Debug, Clone, Default)]
+pub struct Keyframe
+{
+    /// Offset of the keyframe on the animation, from `0.0`, like `from` or `0%`, to `1.0`, like `to` or `100%`.
+    pub offset: f32,
+
+    /// Properties values of the keyframe, which are interpolated until the next keyframe declaring them.
+    pub properties: HashMap<String, PropertyValues>,
+
+    /// Timing function used from this keyframe to the next one, like `animation-timing-function: ease-in`,
+    /// instead of the one of the animation.
+    pub timing_function: Option<TimingFunction>,
+}
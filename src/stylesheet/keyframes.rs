@@ -0,0 +1,93 @@
+use super::Keyframe;
+use crate::property::{
+    PropertyValues,
+    TimingFunction,
+};
+
+/// The keyframes of a `@keyframes` rule, like `@keyframes fade { from { opacity: 0; } to { opacity: 1; } }`,
+/// which are played by the [`Animation`](crate::prelude::Animation) with the same name.
+#[derive(Debug, Clone, Default)]
+pub struct Keyframes
+{
+    /// Keyframes sorted by their offset, where keyframes with the same offset are merged into a single one.
+    pub frames: Vec<Keyframe>,
+}
+
+impl Keyframes
+{
+    /// Sorts the given keyframes by their offset, merging the ones with the same offset,
+    /// where later declarations win, as on CSS.
+    pub fn new(
+        keyframes: impl IntoIterator<Item = Keyframe>
+    ) -> Self {
+        let mut frames: Vec<Keyframe> = Vec::new();
+
+        for keyframe in keyframes
+        {
+            match frames.iter_mut().find(|frame| frame.offset == keyframe.offset)
+            {
+                Some(frame) => {
+                    frame.properties.extend(keyframe.properties);
+                    frame.timing_function = keyframe.timing_function.or(frame.timing_function);
+                }
+                None => frames.push(keyframe),
+            }
+        }
+
+        frames.sort_by(|a, b| a.offset.total_cmp(&b.offset));
+        Self{ frames }
+    }
+
+    /// Names of all properties animated by any keyframe, without duplicates.
+    pub fn property_names(
+        &self
+    ) -> Vec<&str> {
+        let mut names = Vec::new();
+
+        for name in self.frames.iter().flat_map(|frame| frame.properties.keys())
+        {
+            if !names.contains(&name.as_str())
+            {
+                names.push(name.as_str());
+            }
+        }
+
+        names
+    }
+
+    /// Computes the value of the property with the given name at the given progress, from `0.0` to `1.0`,
+    /// interpolating between the keyframes around it which declare that property.
+    ///
+    /// Before the first and after the last keyframe declaring the property, their values are held,
+    /// since there is no underlying value to interpolate to, unlike on CSS.
+    pub fn sample(
+        &self,
+        name: &str,
+        progress: f32,
+        timing_function: TimingFunction
+    ) -> Option<PropertyValues> {
+        let mut declaring = self.frames.iter()
+            .filter_map(|frame| frame.properties.get(name).map(|values| (frame, values)));
+
+        let (mut from, mut from_values) = declaring.next()?;
+        if progress <= from.offset
+        {
+            return Some(from_values.clone());
+        }
+
+        for (to, to_values) in declaring
+        {
+            if progress < to.offset
+            {
+                let local = (progress - from.offset) / (to.offset - from.offset);
+                let eased = from.timing_function.unwrap_or(timing_function).ease(local);
+
+                return Some(from_values.interpolate(to_values, eased));
+            }
+
+            (from, from_values) = (to, to_values);
+        }
+
+        Some(from_values.clone())
+    }
+}
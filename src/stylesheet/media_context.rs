@@ -0,0 +1,25 @@
+use bevy::{
+    prelude::{
+        Resource,
+        Vec2,
+    },
+    utils::HashMap,
+};
+
+/// Current values of media features, used to evaluate [`MediaQueryList`](super::MediaQueryList)s of `@media` rules.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[derive(Resource)]
+pub(crate) struct MediaContext
+{
+    /// Logical size of the primary window, used by `width`, `height`, `aspect-ratio` and `orientation`.
+    pub viewport: Vec2,
+
+    /// Scale factor of the primary window, used by `resolution`.
+    pub scale_factor: f32,
+
+    /// Root font size, used by `em` and `rem` lengths.
+    pub root_font_size: f32,
+
+    /// Values of custom media features, like `steam-deck` for `(platform: steam-deck)`.
+    pub custom_features: HashMap<String, String>,
+}
@@ -0,0 +1,142 @@
+use super::MediaContext;
+use crate::property::{
+    LengthContext,
+    Unit,
+};
+
+use cssparser::match_ignore_ascii_case;
+
+/// How a media feature is compared to the value of a query, like `>=` on `(width >= 1280px)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum MediaComparison
+{
+    Equal,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+/// Value of a media feature on a query, like `1280px` on `(min-width: 1280px)`.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum MediaValue
+{
+    Number(f32),
+    Length(f32, Unit),
+    /// A ratio, like `16/9`, already divided.
+    Ratio(f32),
+    /// A resolution in dots per pixel, like `2dppx` or `2x`.
+    Resolution(f32),
+    Ident(String),
+}
+
+/// A single condition of a media query, like `(orientation: landscape)` or `(min-width: 1280px)`.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum MediaFeature
+{
+    /// Matches when the feature has a value which isn't zero or `none`, like `(orientation)`.
+    Boolean(String),
+
+    /// Matches when the feature compares to the value, like `(width >= 1280px)`.
+    /// Prefixed features, like `min-width`, are stored as a comparison of the unprefixed name.
+    Compare(String, MediaComparison, MediaValue),
+}
+
+/// Value of a media feature on the current [`MediaContext`].
+enum FeatureValue<'a>
+{
+    Number(f32),
+    Ident(&'a str),
+}
+
+impl MediaFeature
+{
+    /// Evaluates this feature, where unknown features never match, as on CSS.
+    pub(crate) fn matches(
+        &self,
+        context: &MediaContext
+    ) -> bool {
+        match self
+        {
+            MediaFeature::Boolean(name) => match Self::value(name, context)
+            {
+                Some(FeatureValue::Number(value)) => value != 0.0,
+                Some(FeatureValue::Ident(value)) => !value.is_empty()
+                    && !value.eq_ignore_ascii_case("none")
+                    && value != "0",
+                None => false,
+            },
+
+            MediaFeature::Compare(name, comparison, expected) => {
+                let actual = match Self::value(name, context)
+                {
+                    Some(actual) => actual,
+                    None => return false,
+                };
+
+                let length_context = LengthContext {
+                    percent_base: 0.0,
+                    viewport: context.viewport,
+                    font_size: context.root_font_size,
+                    root_font_size: context.root_font_size,
+                };
+
+                let expected = match expected
+                {
+                    MediaValue::Ident(expected) => {
+                        return *comparison == MediaComparison::Equal
+                            && matches!(actual, FeatureValue::Ident(actual) if actual.eq_ignore_ascii_case(expected));
+                    }
+                    MediaValue::Number(value) | MediaValue::Ratio(value) | MediaValue::Resolution(value) => *value,
+                    MediaValue::Length(value, unit) => unit.px(*value, &length_context),
+                };
+
+                // Custom features are compared as numbers when the query expects one, like `(ui-scale >= 2)`
+                let actual = match actual
+                {
+                    FeatureValue::Number(actual) => actual,
+                    FeatureValue::Ident(actual) => match actual.parse::<f32>()
+                    {
+                        Ok(actual) => actual,
+                        Err(_) => return false,
+                    },
+                };
+
+                match comparison
+                {
+                    MediaComparison::Equal => (actual - expected).abs() < 0.001,
+                    MediaComparison::Less => actual < expected,
+                    MediaComparison::LessOrEqual => actual <= expected,
+                    MediaComparison::Greater => actual > expected,
+                    MediaComparison::GreaterOrEqual => actual >= expected,
+                }
+            }
+        }
+    }
+
+    fn value<'a>(
+        name: &str,
+        context: &'a MediaContext
+    ) -> Option<FeatureValue<'a>> {
+        let viewport = context.viewport;
+
+        match_ignore_ascii_case! { name,
+            "width" => Some(FeatureValue::Number(viewport.x)),
+            "height" => Some(FeatureValue::Number(viewport.y)),
+            "aspect-ratio" => Some(FeatureValue::Number(match viewport.y > 0.0
+            {
+                true => viewport.x / viewport.y,
+                false => 0.0,
+            })),
+            "orientation" => Some(FeatureValue::Ident(match viewport.y >= viewport.x
+            {
+                true => "portrait",
+                false => "landscape",
+            })),
+            "resolution" => Some(FeatureValue::Number(context.scale_factor)),
+            _ => context.custom_features
+                .get(name)
+                .map(|value| FeatureValue::Ident(value.as_str())),
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use super::{
+    MediaContext,
+    MediaFeature,
+};
+
+/// The media type of a query, like `screen` on `screen and (min-width: 1280px)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum MediaType
+{
+    All,
+    Screen,
+    /// Any other media type, like `print`, which never matches.
+    Other,
+}
+
+/// A single media query, like `screen and (min-width: 1280px)`, which matches when all its features matches.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct MediaQuery
+{
+    /// If the query was prefixed with `not`, so it matches when the features doesn't.
+    pub negated: bool,
+    pub media_type: MediaType,
+    pub features: Vec<MediaFeature>,
+}
+
+impl MediaQuery
+{
+    /// A query which never matches, used in place of invalid queries, as on CSS.
+    pub(crate) fn never(
+        // no args
+    ) -> Self {
+        Self {
+            negated: false,
+            media_type: MediaType::Other,
+            features: Vec::new(),
+        }
+    }
+
+    pub(crate) fn matches(
+        &self,
+        context: &MediaContext
+    ) -> bool {
+        let matches = self.media_type != MediaType::Other
+            && self.features.iter().all(|feature| feature.matches(context));
+
+        matches != self.negated
+    }
+}
@@ -0,0 +1,62 @@
+use super::{
+    MediaContext,
+    MediaQuery,
+};
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+};
+
+/// A comma separated list of media queries of a `@media` rule, like `(min-width: 1280px), (orientation: landscape)`,
+/// which matches when any of its queries matches.
+#[derive(Clone, Debug)]
+pub struct MediaQueryList
+{
+    pub(crate) queries: Vec<MediaQuery>,
+    /// The list as written on the sheet, used to identify it.
+    pub(crate) text: String,
+}
+
+impl MediaQueryList
+{
+    pub(crate) fn matches(
+        &self,
+        context: &MediaContext
+    ) -> bool {
+        // An empty list, like on `@media { ... }`, always matches
+        self.queries.is_empty() || self.queries.iter().any(|query| query.matches(context))
+    }
+}
+
+impl PartialEq
+for MediaQueryList
+{
+    fn eq(
+        &self,
+        other: &Self
+    ) -> bool {
+        self.text == other.text
+    }
+}
+
+impl Hash
+for MediaQueryList
+{
+    fn hash<H: Hasher>(
+        &self,
+        state: &mut H
+    ) {
+        self.text.hash(state);
+    }
+}
+
+impl fmt::Display
+for MediaQueryList
+{
+    fn fmt(
+        &self,
+        formatter: &mut fmt::Formatter<'_>
+    ) -> fmt::Result {
+        write!(formatter, "{}", self.text)
+    }
+}
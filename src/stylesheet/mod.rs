@@ -1,3 +1,31 @@
+mod font_face;
+pub use font_face::FontFace;
+
+mod keyframe;
+pub use keyframe::Keyframe;
+
+mod keyframes;
+pub use keyframes::Keyframes;
+
+mod media_context;
+pub(crate) use media_context::MediaContext;
+
+mod media_feature;
+pub(crate) use media_feature::{
+    MediaComparison,
+    MediaFeature,
+    MediaValue,
+};
+
+mod media_query;
+pub(crate) use media_query::{
+    MediaQuery,
+    MediaType,
+};
+
+mod media_query_list;
+pub use media_query_list::MediaQueryList;
+
 mod style_rule;
 pub use style_rule::StyleRule;
 
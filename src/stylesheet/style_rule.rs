@@ -1,35 +1,91 @@
+use super::{
+    MediaContext,
+    MediaQueryList,
+};
 use crate::{
+    DynArray,
     property::PropertyValues,
     selector::Selector,
 };
 
-use bevy::utils::HashMap;
+use bevy::utils::{
+    HashMap,
+    HashSet,
+};
 use std::fmt;
 
-/// Represents a single rule inside a style sheet with a [`Selector`] which determines which entities
+/// Represents a single rule inside a style sheet with a list of [`Selector`]s which determines which entities
 /// should be applied the [`PropertyValues`].
 ///
+/// Each selector on the list, like `h1` and `h2` on `h1, h2 { ... }`, is matched independently but shares the same properties.
+///
 /// Note that this struct holds intermediate data, the final value is parsed by [`Property`](crate::Property) on
 /// the first time it's [`system`](crate::Property::apply_system) is invoked.
 #[derive(Debug, Clone)]
 pub struct StyleRule
 {
-    /// Selector used to match entities to apply properties.
-    pub selector: Selector,
+    /// Selectors used to match entities to apply properties.
+    pub selectors: DynArray<Selector>,
 
     /// Properties values to be applied on selected entities.
     pub properties: HashMap<String, PropertyValues>,
+
+    /// Custom properties declared for selected entities, like `--accent: #fff`, with their raw css values.
+    pub custom_properties: HashMap<String, String>,
+
+    /// Properties which references custom properties, like `color: var(--accent)`, with their raw css values.
+    ///
+    /// These are resolved for each selected entity, before being parsed by [`Property`](crate::Property).
+    pub var_properties: HashMap<String, String>,
+
+    /// Names of properties declared with `!important`, which wins over normal declarations regardless of specificity.
+    pub important: HashSet<String>,
+
+    /// Media queries of the `@media` rules this rule was declared in, which must all match for it to be applied.
+    pub media: Vec<MediaQueryList>,
 }
 
 impl StyleRule
 {
+    /// Creates a rule for the given selectors, which are made distinct from the same selectors on other rules.
     pub fn new(
-        selector: Selector
+        mut selectors: DynArray<Selector>
     ) -> Self {
+        for selector in selectors.iter_mut()
+        {
+            selector.restrict_to_rule();
+        }
+
         Self{
-            selector,
+            selectors,
             properties: Default::default(),
+            custom_properties: Default::default(),
+            var_properties: Default::default(),
+            important: Default::default(),
+            media: Default::default(),
+        }
+    }
+
+    /// Restricts this rule to the given media queries, like when it's declared inside `@media`.
+    ///
+    /// Selectors are also made distinct from the same selectors of unrestricted rules, since they're applied independently.
+    pub(crate) fn restrict_to_media(
+        &mut self,
+        media: &MediaQueryList
+    ) {
+        for selector in self.selectors.iter_mut()
+        {
+            selector.restrict_to_media(media);
         }
+        self.media.push(media.clone());
+    }
+
+    /// Checks if all media queries of this rule matches, so it can be applied.
+    pub(crate) fn matches_media(
+        &self,
+        context: &MediaContext
+    ) -> bool {
+        self.media.iter().all(|media| media.matches(context))
     }
 }
 
@@ -40,6 +96,18 @@ for StyleRule
         &self,
         formatter: &mut fmt::Formatter<'_>
     ) -> fmt::Result {
-        write!(formatter, "{}", self.selector)
+        let mut selectors = self.selectors.iter();
+
+        if let Some(first) = selectors.next()
+        {
+            write!(formatter, "{}", first)?;
+        }
+
+        for selector in selectors
+        {
+            write!(formatter, ", {}", selector)?;
+        }
+
+        Ok(())
     }
 }
@@ -1,4 +1,8 @@
-use super::StyleRule;
+use super::{
+    FontFace,
+    Keyframes,
+    StyleRule,
+};
 use crate::{
     DynArray,
     parser::StyleSheetParser,
@@ -10,7 +14,10 @@ use bevy::{
     log::trace,
     prelude::*,
     reflect::{TypePath},
-    utils::AHasher,
+    utils::{
+        AHasher,
+        HashMap,
+    },
 };
 use std::hash::{Hash, Hasher};
 
@@ -26,6 +33,9 @@ pub struct StyleSheetAsset
     path: String,
     hash: u64,
     rules: DynArray<StyleRule>,
+    imports: Vec<String>,
+    keyframes: HashMap<String, Keyframes>,
+    font_faces: Vec<FontFace>,
 }
 
 impl StyleSheetAsset
@@ -34,6 +44,9 @@ impl StyleSheetAsset
     ///
     /// This used by internal asset loader to keep track of where each asset came from.
     /// If you are creating this struct by hand, you can safely supply an  empty string as path.
+    ///
+    /// Imported sheets, like `@import "common.css";`, are only listed on [`imports`](StyleSheetAsset::imports),
+    /// since their rules are loaded by the asset loader.
     pub fn parse(
         path: &str,
         content: &str
@@ -44,22 +57,79 @@ impl StyleSheetAsset
         content.hash(&mut hasher);
         let hash = hasher.finish();
 
+        let sheet = StyleSheetParser::parse_sheet(content);
+
         Self{
             path: path.to_string(),
             hash,
-            rules: StyleSheetParser::parse(content),
+            rules: sheet.rules,
+            imports: sheet.imports,
+            keyframes: sheet.keyframes,
+            font_faces: sheet.font_faces,
         }
     }
 
+    /// Inserts the rules of the imported sheets before the rules of this one, so they're overridden by it, as on CSS.
+    ///
+    /// The same goes for keyframes, where the ones of this sheet replace imported ones with the same name,
+    /// and for fonts, where the ones of this sheet wins when equally good.
+    pub(crate) fn merge_imports(
+        &mut self,
+        imported: Vec<StyleSheetAsset>
+    ) {
+        if imported.is_empty()
+        {
+            return;
+        }
+
+        let mut hasher = AHasher::default();
+        self.hash.hash(&mut hasher);
+
+        let mut rules = DynArray::new();
+        let mut keyframes = HashMap::new();
+        let mut font_faces = Vec::new();
+        for sheet in imported
+        {
+            sheet.hash.hash(&mut hasher);
+            rules.extend(sheet.rules);
+            keyframes.extend(sheet.keyframes);
+            font_faces.extend(sheet.font_faces);
+        }
+        rules.extend(self.rules.drain(..));
+        keyframes.extend(self.keyframes.drain());
+        font_faces.append(&mut self.font_faces);
+
+        // Imported rules are parsed after this sheet, so their selectors must be sorted again by load order
+        for selector in rules.iter_mut().flat_map(|rule| rule.selectors.iter_mut())
+        {
+            selector.reset_load_order();
+        }
+
+        self.hash = hasher.finish();
+        self.rules = rules;
+        self.keyframes = keyframes;
+        self.font_faces = font_faces;
+    }
+
+    /// Gets the rule which owns the given [`Selector`], since selectors are distinct for each rule.
+    fn owning_rule(
+        &self,
+        selector: &Selector
+    ) -> Option<&StyleRule> {
+        self.rules.iter()
+            .find(|&rule| rule.selectors.contains(selector))
+    }
+
     /// Returns the list of properties defined by the given [`Selector`].
     pub fn get_property_names(
         &self,
         selector: &Selector
     ) -> Option<Vec<String>> {
-        self.rules.iter()
-            .find(|&rule| rule.selector == *selector)
-            .map(|rule| rule.properties.iter()
-                .map(|(prop, _val)| prop.clone())
+        self.owning_rule(selector)
+            .map(|rule| rule.properties.keys()
+                .chain(rule.custom_properties.keys())
+                .chain(rule.var_properties.keys())
+                .cloned()
                 .collect::<Vec<String>>()
             )
     }
@@ -70,11 +140,65 @@ impl StyleSheetAsset
         selector: &Selector,
         name: &str
     ) -> Option<&PropertyValues> {
-        self.rules.iter()
-            .find(|&rule| &rule.selector == selector)
+        self.owning_rule(selector)
             .and_then(|rule| rule.properties.get(name))
     }
 
+    /// Returns the raw value of the custom property on the given [`Selector`] with the given name, like `--accent`.
+    pub fn get_custom_property(
+        &self,
+        selector: &Selector,
+        name: &str
+    ) -> Option<&str> {
+        self.owning_rule(selector)
+            .and_then(|rule| rule.custom_properties.get(name))
+            .map(String::as_str)
+    }
+
+    /// Returns the raw value, still referencing custom properties, on the given [`Selector`] with the given name.
+    pub fn get_var_property(
+        &self,
+        selector: &Selector,
+        name: &str
+    ) -> Option<&str> {
+        self.owning_rule(selector)
+            .and_then(|rule| rule.var_properties.get(name))
+            .map(String::as_str)
+    }
+
+    /// Checks if the property on the given [`Selector`] with the given name was declared with `!important`.
+    pub fn is_important(
+        &self,
+        selector: &Selector,
+        name: &str
+    ) -> bool {
+        self.owning_rule(selector)
+            .is_some_and(|rule| rule.important.contains(name))
+    }
+
+    /// Paths of the sheets imported by this one, like `common.css` on `@import "common.css";`, relative to this sheet.
+    pub fn imports(
+        &self
+    ) -> &[String] {
+        &self.imports
+    }
+
+    /// Returns the [`Keyframes`] of the animation with the given name, like `fade` on `@keyframes fade { ... }`.
+    pub fn keyframes(
+        &self,
+        name: &str
+    ) -> Option<&Keyframes> {
+        self.keyframes.get(name)
+    }
+
+    /// Fonts declared by `@font-face` rules, like `@font-face { font-family: "Fira"; src: url("fonts/FiraSans-Bold.ttf"); }`,
+    /// including the ones of imported sheets.
+    pub fn font_faces(
+        &self
+    ) -> &[FontFace] {
+        &self.font_faces
+    }
+
     /// Iterates over all existing rules
     pub fn iter(
         &self
@@ -3,13 +3,14 @@ use super::StyleSheetAsset;
 use bevy::{
     asset::{
         io::Reader,
-        AssetLoader, AsyncReadExt,
+        AssetLoader, AssetPath, AsyncReadExt,
         LoadContext,
     },
-    prelude::*,
+    log::error,
     utils::{
         thiserror,
         BoxedFuture,
+        HashSet,
     },
 };
 use thiserror::Error;
@@ -47,10 +48,16 @@ for StyleSheetLoader
             reader.read_to_end(&mut bytes).await?;
             
             let content = std::str::from_utf8(&bytes)?;
-            let stylesheet = StyleSheetAsset::parse(
+            let mut stylesheet = StyleSheetAsset::parse(
                 load_context.path().to_str().unwrap_or_default(),
                 content
             );
+
+            let path = load_context.asset_path().clone();
+            let mut loaded = HashSet::from([path.clone()]);
+            let imported = load_imports(load_context, path, stylesheet.imports().to_vec(), &mut loaded).await;
+            stylesheet.merge_imports(imported);
+
             Ok(stylesheet)
         })
     }
@@ -61,3 +68,61 @@ for StyleSheetLoader
         &["css"]
     }
 }
+
+/// Loads the sheets imported by the sheet on the given path, with their own imports already merged,
+/// skipping the ones which were already loaded, so the same sheet isn't applied twice nor imported in a cycle.
+///
+/// Imported sheets are read as loader dependencies, so the importing sheet is reloaded whenever any of them changes.
+fn load_imports<'a>(
+    load_context: &'a mut LoadContext<'_>,
+    path: AssetPath<'static>,
+    imports: Vec<String>,
+    loaded: &'a mut HashSet<AssetPath<'static>>,
+) -> BoxedFuture<'a, Vec<StyleSheetAsset>> {
+    Box::pin(async move {
+        let mut sheets = Vec::new();
+
+        for import in imports
+        {
+            let import_path = match path.resolve_embed(&import)
+            {
+                Ok(import_path) => import_path,
+                Err(err) => {
+                    error!("Failed to import {} on {}. Error: {}", import, path, err);
+                    continue;
+                }
+            };
+
+            if !loaded.insert(import_path.clone())
+            {
+                continue;
+            }
+
+            let bytes = match load_context.read_asset_bytes(&import_path).await
+            {
+                Ok(bytes) => bytes,
+                Err(err) => {
+                    error!("Failed to import {} on {}. Error: {}", import, path, err);
+                    continue;
+                }
+            };
+
+            let content = match std::str::from_utf8(&bytes)
+            {
+                Ok(content) => content,
+                Err(err) => {
+                    error!("Failed to import {} on {}. Error: {}", import, path, err);
+                    continue;
+                }
+            };
+
+            let mut sheet = StyleSheetAsset::parse(import_path.path().to_str().unwrap_or_default(), content);
+            let imported = load_imports(load_context, import_path, sheet.imports().to_vec(), loaded).await;
+            sheet.merge_imports(imported);
+
+            sheets.push(sheet);
+        }
+
+        sheets
+    })
+}
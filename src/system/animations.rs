@@ -0,0 +1,113 @@
+use crate::{
+    component::{
+        Animation,
+        StyleSheet,
+    },
+    property::{
+        StyleSheetState,
+        StyleSource,
+    },
+    selector::Selector,
+    stylesheet::{
+        Keyframes,
+        StyleSheetAsset,
+    },
+};
+
+use bevy::prelude::{
+    Assets,
+    Entity,
+    Handle,
+    Parent,
+    Query,
+    Res, ResMut,
+    Time,
+};
+
+/// Advances every [`Animation`] and applies the values interpolated from its `@keyframes` rule, which overrides the ones
+/// of style rules, except for `!important` declarations.
+///
+/// When an animation stops applying its values, like when it ends without a forwards fill mode,
+/// the nearest [`StyleSheet`] is applied again to restore the underlying values.
+pub(crate) fn animate(
+    time: Res<Time>,
+    assets: Res<Assets<StyleSheetAsset>>,
+    mut state: ResMut<StyleSheetState>,
+    mut q_animations: Query<(Entity, &mut Animation)>,
+    mut q_sheets: Query<&mut StyleSheet>,
+    q_parents: Query<&Parent>,
+) {
+    for (entity, mut animation) in q_animations.iter_mut()
+    {
+        animation.elapsed += time.delta_seconds();
+
+        // Important declarations are only known when the style sheet is applied, so they're kept until the next time
+        if let Some(style) = state.get(&entity)
+        {
+            animation.important = style.iter()
+                .filter(|(name, source)| assets.get(&source.styleheet)
+                    .is_some_and(|sheet| sheet.is_important(&source.selector, name))
+                )
+                .map(|(name, _)| name.clone())
+                .collect();
+        }
+
+        let keyframes = animation.name.as_ref()
+            .and_then(|name| find_keyframes(entity, name, &assets, &q_sheets, &q_parents));
+
+        let (Some((handle, keyframes)), Some(progress)) = (keyframes, animation.progress())
+        else
+        {
+            if animation.applying
+            {
+                animation.applying = false;
+
+                let sheet = std::iter::successors(Some(entity), |entity| q_parents.get(*entity).ok().map(Parent::get))
+                    .find(|entity| q_sheets.contains(*entity));
+
+                if let Some(mut sheet) = sheet.and_then(|sheet| q_sheets.get_mut(sheet).ok())
+                {
+                    sheet.refresh();
+                }
+            }
+            continue;
+        };
+
+        let style = state.entry(entity).or_default();
+        for name in keyframes.property_names()
+        {
+            if animation.important.contains(name)
+            {
+                continue;
+            }
+
+            if let Some(values) = keyframes.sample(name, progress, animation.timing_function)
+            {
+                style.insert(name.to_string(), StyleSource {
+                    styleheet: handle.clone(),
+                    selector: Selector::default(),
+                    values: Some(values),
+                    animated: true,
+                });
+            }
+        }
+
+        animation.applying = true;
+    }
+}
+
+/// Finds the [`Keyframes`] with the given name on the [`StyleSheet`] of the entity or of its nearest ancestor declaring it.
+fn find_keyframes<'a>(
+    entity: Entity,
+    name: &str,
+    assets: &'a Assets<StyleSheetAsset>,
+    q_sheets: &Query<&mut StyleSheet>,
+    q_parents: &Query<&Parent>,
+) -> Option<(Handle<StyleSheetAsset>, &'a Keyframes)> {
+    std::iter::successors(Some(entity), |entity| q_parents.get(*entity).ok().map(Parent::get))
+        .filter_map(|entity| q_sheets.get(entity).ok())
+        .find_map(|sheet| assets.get(sheet.handle())
+            .and_then(|asset| asset.keyframes(name))
+            .map(|keyframes| (sheet.handle().clone(), keyframes))
+        )
+}
@@ -0,0 +1,215 @@
+use bevy::{
+    ecs::system::SystemState,
+    prelude::{
+        Changed,
+        Component,
+        Entity,
+        Node,
+        Query,
+        RemovedComponents,
+        With,
+        World,
+    },
+    reflect::{
+        Reflect,
+        ReflectRef,
+        TypeInfo,
+        VariantInfo,
+        VariantType,
+    },
+    utils::HashMap,
+};
+use std::borrow::Cow;
+
+use crate::DynArray;
+
+pub(crate) trait AttributeFilter
+{
+    fn filter(
+        &mut self,
+        world: &World
+    ) -> DynArray<(Entity, String)>;
+
+    /// Entities which attribute value changed since the last call, so attribute selectors must be matched again
+    fn changed(
+        &mut self,
+        world: &World
+    ) -> DynArray<Entity>;
+}
+
+type ChangesQuery<T> = (
+    Query<'static, 'static, (Entity, &'static T), (Changed<T>, With<Node>)>,
+    RemovedComponents<'static, 'static, T>,
+);
+
+/// Reads an attribute value from a component, using the getter given on
+/// [`register_attribute_selector`](crate::RegisterAttributeSelector::register_attribute_selector).
+pub(crate) struct AttributeGetter<T, F>
+where
+    T: Component,
+    F: Fn(&T) -> Option<String> + Send + Sync + 'static,
+{
+    pub state: SystemState<Query<'static, 'static, (Entity, &'static T)>>,
+    pub changes: SystemState<ChangesQuery<T>>,
+    /// Last value read for each entity, since the component may change without changing the attribute value,
+    /// like when a property applies a color on a [`Text`](bevy::prelude::Text)
+    pub values: HashMap<Entity, Option<String>>,
+    pub getter: F,
+}
+
+impl<T, F> AttributeGetter<T, F>
+where
+    T: Component,
+    F: Fn(&T) -> Option<String> + Send + Sync + 'static,
+{
+    pub fn new(
+        world: &mut World,
+        getter: F
+    ) -> Self {
+        Self {
+            state: SystemState::new(world),
+            changes: SystemState::new(world),
+            values: Default::default(),
+            getter,
+        }
+    }
+}
+
+impl<T, F> AttributeFilter
+for AttributeGetter<T, F>
+where
+    T: Component,
+    F: Fn(&T) -> Option<String> + Send + Sync + 'static,
+{
+    fn filter(
+        &mut self,
+        world: &World
+    ) -> DynArray<(Entity, String)> {
+        let getter = &self.getter;
+
+        self.state.get(world)
+            .iter()
+            .filter_map(|(entity, component)|
+                getter(component).map(|value| (entity, value))
+            )
+            .collect()
+    }
+
+    fn changed(
+        &mut self,
+        world: &World
+    ) -> DynArray<Entity> {
+        let getter = &self.getter;
+        let values = &mut self.values;
+        let (changes, mut removed) = self.changes.get(world);
+
+        let mut entities: DynArray<Entity> = changes.iter()
+            .filter(|&(entity, component)|
+            {
+                let value = getter(component);
+                values.insert(entity, value.clone()) != Some(value)
+            })
+            .map(|(entity, _component)| entity)
+            .collect();
+
+        for entity in removed.read()
+        {
+            if values.remove(&entity).is_some()
+            {
+                entities.push(entity);
+            }
+        }
+
+        entities
+    }
+}
+
+/// Reads an attribute value from a reflected component field, like `rarity` on `struct Slot { rarity: Rarity }`.
+///
+/// Attribute names are converted from `kebab-case` to `snake_case` before looking up the field.
+pub(crate) fn reflect_attribute(
+    component: &dyn Reflect,
+    name: &str
+) -> Option<String> {
+    let field_name = name.replace('-', "_");
+
+    let field = match component.reflect_ref()
+    {
+        ReflectRef::Struct(value) => value.field(&field_name),
+        ReflectRef::Enum(value) => value.field(&field_name),
+        _ => None,
+    };
+
+    field.and_then(reflect_to_string)
+}
+
+/// Checks if a reflected component has a field which can be read as the attribute, like `rarity` on `struct Slot { rarity: Rarity }`.
+pub(crate) fn has_reflected_attribute(
+    type_info: &TypeInfo,
+    name: &str
+) -> bool {
+    let field_name = name.replace('-', "_");
+
+    match type_info
+    {
+        TypeInfo::Struct(info) => info.field(&field_name).is_some(),
+        TypeInfo::Enum(info) => info.iter()
+            .any(|variant| matches!(variant, VariantInfo::Struct(variant) if variant.field(&field_name).is_some())),
+        _ => false,
+    }
+}
+
+/// Converts a reflected value into an attribute string, if the value represents a present attribute.
+///
+/// - Strings and numbers are converted as they are.
+/// - `true` is converted to `"true"`, while `false` means the attribute isn't present.
+/// - Unit enum variants are converted to `kebab-case`, like `VeryRare` into `"very-rare"`.
+/// - [`Option`] values are unwrapped, where [`None`] means the attribute isn't present.
+fn reflect_to_string(
+    value: &dyn Reflect
+) -> Option<String> {
+    macro_rules! downcast_to_string {
+        ($($ty:ty),+) => {
+            $(
+                if let Some(value) = value.downcast_ref::<$ty>()
+                {
+                    return Some(value.to_string());
+                }
+            )+
+        };
+    }
+
+    downcast_to_string!(
+        String, &'static str, Cow<'static, str>,
+        u8, u16, u32, u64, usize,
+        i8, i16, i32, i64, isize,
+        f32, f64
+    );
+
+    if let Some(value) = value.downcast_ref::<bool>()
+    {
+        return value.then(|| "true".to_string());
+    }
+
+    match value.reflect_ref()
+    {
+        ReflectRef::Enum(value) if value.reflect_type_path().starts_with("core::option::Option") => value
+            .field_at(0)
+            .and_then(reflect_to_string),
+
+        ReflectRef::Enum(value) if value.variant_type() == VariantType::Unit => {
+            let mut buffer = String::new();
+            for (idx, c) in value.variant_name().char_indices()
+            {
+                if c.is_ascii_uppercase() && idx > 0
+                {
+                    buffer.push('-');
+                }
+                buffer.push(c.to_ascii_lowercase());
+            }
+            Some(buffer)
+        }
+
+        _ => None,
+    }
+}
@@ -0,0 +1,40 @@
+use super::AttributeFilter;
+
+use bevy::{
+    prelude::{
+        Entity,
+        Resource,
+        World,
+    },
+    utils::HashMap,
+};
+
+use crate::DynArray;
+
+#[derive(Default, Resource)]
+pub(crate) struct AttributeFilterRegistry(
+    pub HashMap<&'static str, Vec<Box<dyn AttributeFilter + Send + Sync>>>,
+);
+
+impl AttributeFilterRegistry
+{
+    /// Collects entities which value of any registered attribute changed since the last call
+    pub fn changed(
+        &mut self,
+        world: &World
+    ) -> DynArray<Entity> {
+        let mut entities = DynArray::new();
+
+        for entity in self.0.values_mut()
+            .flatten()
+            .flat_map(|filter| filter.changed(world))
+        {
+            if !entities.contains(&entity)
+            {
+                entities.push(entity);
+            }
+        }
+
+        entities
+    }
+}
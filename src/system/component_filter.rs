@@ -1,14 +1,19 @@
 use bevy::{
     ecs::system::SystemState,
     prelude::{
+        Changed,
         Component,
         Entity,
+        Node,
         Query,
-        With, World,
+        RemovedComponents,
+        With,
+        World,
     },
 };
 
 use crate::DynArray;
+use std::any::TypeId;
 
 pub(crate) trait ComponentFilter
 {
@@ -16,15 +21,74 @@ pub(crate) trait ComponentFilter
         &mut self,
         world: &World
     ) -> DynArray<Entity>;
+
+    /// Entities which component changed or was removed since the last call, so reflected attributes must be matched again
+    fn changed(
+        &mut self,
+        world: &World
+    ) -> DynArray<Entity>;
+
+    /// [`TypeId`] of the component used by this filter, used to look up reflected component data
+    fn component_type_id(
+        &self
+    ) -> TypeId;
 }
 
-impl<'w, 's, T: Component> ComponentFilter
-for SystemState<Query<'w, 's, Entity, With<T>>>
+type ChangesQuery<T> = (
+    Query<'static, 'static, Entity, (Changed<T>, With<Node>)>,
+    RemovedComponents<'static, 'static, T>,
+);
+
+/// Selects entities with the component given on
+/// [`register_component_selector`](crate::RegisterComponentSelector::register_component_selector).
+pub(crate) struct ComponentSelector<T>
+where
+    T: Component,
+{
+    pub state: SystemState<Query<'static, 'static, Entity, With<T>>>,
+    pub changes: SystemState<ChangesQuery<T>>,
+}
+
+impl<T> ComponentSelector<T>
+where
+    T: Component,
+{
+    pub fn new(
+        world: &mut World
+    ) -> Self {
+        Self {
+            state: SystemState::new(world),
+            changes: SystemState::new(world),
+        }
+    }
+}
+
+impl<T> ComponentFilter
+for ComponentSelector<T>
+where
+    T: Component,
 {
     fn filter(
         &mut self,
         world: &World
     ) -> DynArray<Entity> {
-        self.get(world).iter().collect()
+        self.state.get(world).iter().collect()
+    }
+
+    fn changed(
+        &mut self,
+        world: &World
+    ) -> DynArray<Entity> {
+        let (changes, mut removed) = self.changes.get(world);
+
+        changes.iter()
+            .chain(removed.read())
+            .collect()
+    }
+
+    fn component_type_id(
+        &self
+    ) -> TypeId {
+        TypeId::of::<T>()
     }
 }
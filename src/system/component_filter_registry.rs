@@ -1,11 +1,98 @@
-use super::ComponentFilter;
+use super::{
+    ComponentFilter,
+    has_reflected_attribute,
+    reflect_attribute,
+};
 
 use bevy::{
-    prelude::Resource,
+    prelude::{
+        AppTypeRegistry,
+        Entity,
+        ReflectComponent,
+        Resource,
+        World,
+    },
     utils::HashMap,
 };
 
+use crate::DynArray;
+
 #[derive(Default, Resource)]
 pub(crate) struct ComponentFilterRegistry(
     pub HashMap<&'static str, Box<dyn ComponentFilter + Send + Sync>>,
+    /// Last reflected attribute values read for each entity, since components like `Style` are written by
+    /// property systems every time they are applied, without changing any attribute value
+    pub HashMap<Entity, DynArray<(String, String)>>,
 );
+
+impl ComponentFilterRegistry
+{
+    /// Collects entities which reflected attributes changed since the last call, like `[rarity="epic"]`.
+    ///
+    /// Only reflected components with a field named after one of the given attributes are tracked.
+    pub fn changed(
+        &mut self,
+        world: &World,
+        names: &[&str]
+    ) -> DynArray<Entity> {
+        let mut entities = DynArray::new();
+
+        if names.is_empty()
+        {
+            return entities;
+        }
+
+        let Some(type_registry) = world.get_resource::<AppTypeRegistry>() else
+        {
+            return entities;
+        };
+
+        let type_registry = type_registry.read();
+        let mut candidates = DynArray::<Entity>::new();
+        for entity in self.0.values_mut()
+            .filter(|component| type_registry.get(component.component_type_id())
+                .is_some_and(|registration| registration.data::<ReflectComponent>().is_some()
+                    && names.iter().any(|name| has_reflected_attribute(registration.type_info(), name))
+                )
+            )
+            .flat_map(|component| component.changed(world))
+        {
+            if !candidates.contains(&entity)
+            {
+                candidates.push(entity);
+            }
+        }
+
+        // Attributes may be read from any reflected component, not only the changed one
+        let reflected: DynArray<&ReflectComponent> = self.0.values()
+            .filter_map(|component| type_registry.get_type_data::<ReflectComponent>(component.component_type_id()))
+            .collect();
+
+        for entity in candidates
+        {
+            let values: DynArray<(String, String)> = world.get_entity(entity)
+                .map(|entity_ref| names.iter()
+                    .flat_map(|name| reflected.iter()
+                        .filter_map(|reflect| reflect.reflect(entity_ref))
+                        .filter_map(|component| reflect_attribute(component, name))
+                        .map(|value| (name.to_string(), value))
+                    )
+                    .collect()
+                )
+                .unwrap_or_default();
+
+            let changed = match values.is_empty()
+            {
+                true => self.1.remove(&entity).is_some(),
+                false => self.1.insert(entity, values.clone()).as_ref() != Some(&values),
+            };
+
+            if changed
+            {
+                entities.push(entity);
+            }
+        }
+
+        entities
+    }
+}
@@ -2,6 +2,12 @@ use super::query;
 
 use crate::prelude::StyleSheetAsset;
 
+#[cfg(feature = "pseudo_class")]
+use crate::prelude::{
+    Checked,
+    Disabled,
+};
+
 use bevy::{
     ecs::system::SystemParam,
     prelude::*,
@@ -20,6 +26,9 @@ pub(crate) struct CssQueryParam<'w, 's>
 
     #[cfg(feature = "pseudo_class")]
     pub pseudo_classes: PseudoClassParam<'w, 's>,
+
+    #[cfg(feature = "pseudo_prop")]
+    pub pseudo_elements: query::QueryEntityPseudoElements<'w, 's>,
 }
 
 #[derive(Deref, SystemParam)]
@@ -27,10 +36,22 @@ pub(crate) struct StyleSheetResource<'w>(
     Res<'w, Assets<StyleSheetAsset>>
 );
 
+/// Marker components which removal must also match styles again, since it can't be detected by [`QueryUiChanges`](query::QueryUiChanges)
+#[cfg(feature = "pseudo_class")]
+#[derive(SystemParam)]
+pub(crate) struct PseudoClassRemovals<'w, 's>
+{
+    pub disabled: RemovedComponents<'w, 's, Disabled>,
+    pub checked: RemovedComponents<'w, 's, Checked>,
+}
+
 #[cfg(feature = "pseudo_class")]
 #[derive(SystemParam)]
 pub(crate) struct PseudoClassParam<'w, 's>
 {
-    pub interaction: query::QueryEntityInteraction<'w, 's>,
-    pub _children: query::QueryEntityChildren<'w, 's>,
+    pub disabled: query::QueryEntityDisabled<'w, 's>,
+    pub checked: query::QueryEntityChecked<'w, 's>,
+    pub focusable: query::QueryEntityFocusable<'w, 's>,
+    pub text: query::QueryEntityText<'w, 's>,
+    pub removed: PseudoClassRemovals<'w, 's>,
 }
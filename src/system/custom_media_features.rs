@@ -0,0 +1,30 @@
+use bevy::{
+    prelude::{
+        Deref, DerefMut,
+        Resource,
+    },
+    utils::HashMap,
+};
+
+/// Values of custom media features, which can be used by `@media` rules like any builtin feature.
+///
+/// Features can be compared with their value, like `(platform: steam-deck)`, or as numbers, like `(ui-scale >= 2)`,
+/// and are matched by any value other than `0` or `none` when used alone, like `(touch)`.
+/// Changing this resource applies the style sheets again, if the result of any media query changes.
+///
+/// # Examples
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use tomt_bevycss::prelude::*;
+/// fn detect_platform(
+///     mut features: ResMut<CustomMediaFeatures>,
+/// ) {
+///     features.insert("platform".to_string(), "steam-deck".to_string());
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+#[derive(Deref, DerefMut, Resource)]
+pub struct CustomMediaFeatures(
+    pub HashMap<String, String>
+);
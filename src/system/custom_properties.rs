@@ -0,0 +1,138 @@
+use crate::{
+    component::CustomProperties,
+    parser::{
+        parse_property_values,
+        resolve_vars,
+    },
+    property::StyleSheetState,
+    stylesheet::StyleSheetAsset,
+};
+
+use bevy::{
+    log::error,
+    prelude::{
+        Assets,
+        Entity,
+        Parent,
+        World,
+    },
+    utils::HashSet,
+};
+
+/// Updates the [`CustomProperties`] of every entity on the given state, or matched again without being selected by any rule,
+/// then resolves the values of properties which references them, like `color: var(--accent)`.
+///
+/// Properties which can't be resolved, since there is no such custom property nor a fallback value, aren't applied.
+pub(crate) fn apply_custom_properties(
+    world: &mut World,
+    state: &mut StyleSheetState,
+    examined: &[Entity]
+) {
+    let assets = world.resource::<Assets<StyleSheetAsset>>();
+
+    let entities: HashSet<Entity> = examined.iter()
+        .chain(state.keys())
+        .copied()
+        .collect();
+
+    let declared: Vec<(Entity, CustomProperties)> = entities.into_iter()
+        .map(|entity|
+        {
+            let properties = state.get(&entity)
+                .into_iter()
+                .flat_map(|style| style.iter())
+                .filter(|(name, _source)| name.starts_with("--"))
+                .filter_map(|(name, source)| assets.get(&source.styleheet)
+                    .and_then(|sheet| sheet.get_custom_property(&source.selector, name))
+                    .map(|value| (name.clone(), value.to_string()))
+                )
+                .collect();
+
+            (entity, CustomProperties::new(properties))
+        })
+        .collect();
+
+    for (entity, properties) in declared
+    {
+        let mut entity = match world.get_entity_mut(entity)
+        {
+            Some(entity) => entity,
+            None => continue,
+        };
+
+        match (properties.is_empty(), entity.get::<CustomProperties>())
+        {
+            (true, None) => (),
+            (true, Some(_current)) => {
+                entity.remove::<CustomProperties>();
+            }
+            (false, Some(current)) if current == &properties => (),
+            (false, _current) => {
+                entity.insert(properties);
+            }
+        }
+    }
+
+    let assets = world.resource::<Assets<StyleSheetAsset>>();
+    let world: &World = world;
+
+    for (entity, style) in state.iter_mut()
+    {
+        style.retain(|name, source|
+        {
+            let raw = match assets.get(&source.styleheet)
+                .and_then(|sheet| sheet.get_var_property(&source.selector, name))
+            {
+                Some(raw) => raw,
+                None => return true,
+            };
+
+            let mut visiting = Vec::new();
+            match resolve_vars(raw, &mut |var| lookup_custom_property(world, *entity, var, &mut visiting))
+            {
+                Some(value) => {
+                    source.values = Some(parse_property_values(&value));
+                    true
+                }
+                None => {
+                    error!("Failed to resolve custom properties of property {}: {}", name, raw);
+                    false
+                }
+            }
+        });
+    }
+}
+
+/// Finds the custom property on the entity or on its nearest ancestor declaring it, then resolves its own `var()` references
+/// from there.
+///
+/// Custom properties referencing themselves, directly or not, are considered invalid.
+fn lookup_custom_property(
+    world: &World,
+    entity: Entity,
+    name: &str,
+    visiting: &mut Vec<(Entity, String)>
+) -> Option<String> {
+    let mut current = Some(entity);
+
+    while let Some(owner) = current
+    {
+        if let Some(value) = world.get::<CustomProperties>(owner).and_then(|properties| properties.get(name))
+        {
+            if visiting.iter().any(|(e, n)| *e == owner && n == name)
+            {
+                return None;
+            }
+
+            visiting.push((owner, name.to_string()));
+            let resolved = resolve_vars(value, &mut |var| lookup_custom_property(world, owner, var, visiting));
+            visiting.pop();
+
+            return resolved;
+        }
+
+        current = world.get::<Parent>(owner).map(Parent::get);
+    }
+
+    None
+}
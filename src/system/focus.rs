@@ -0,0 +1,270 @@
+//! A small focus subsystem, since `bevy_ui` has no focus concept.
+//!
+//! The focused entity is kept on [`FocusState`] resource and mirrored into [`Focusable`] components.
+//! Clicking a [`Focusable`] entity focuses it, while [`keyboard_navigation`] and [`gamepad_navigation`]
+//! systems can be added to move the focus across [`Focusable`] entities.
+
+use crate::component::{
+    Disabled,
+    Focusable,
+};
+
+use bevy::{
+    ecs::system::SystemParam,
+    input::{
+        gamepad::{
+            GamepadButton,
+            GamepadButtonType,
+            Gamepads,
+        },
+        keyboard::KeyCode,
+        ButtonInput,
+    },
+    prelude::{
+        Changed,
+        Children,
+        DetectChanges, DetectChangesMut,
+        Entity,
+        InheritedVisibility,
+        Interaction,
+        Node,
+        Parent,
+        Query,
+        Res, ResMut, Resource,
+        With, Without,
+    },
+};
+
+/// Holds which entity is focused, matched by `:focus`, `:focus-visible` and `:focus-within` selectors.
+///
+/// # Examples
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use tomt_bevycss::prelude::*;
+/// fn focus_play_button(
+///     mut focus: ResMut<FocusState>,
+///     q_buttons: Query<(Entity, &Name), With<Focusable>>,
+/// ) {
+///     if let Some((entity, _name)) = q_buttons.iter().find(|(_entity, name)| name.as_str() == "play")
+///     {
+///         focus.focus_visible(entity);
+///     }
+/// }
+/// ```
+#[derive(Debug, Default, Resource)]
+pub struct FocusState
+{
+    focused: Option<Entity>,
+    visible: bool,
+}
+
+impl FocusState
+{
+    /// The focused entity, if any
+    pub fn focused(
+        &self
+    ) -> Option<Entity> {
+        self.focused
+    }
+
+    /// Checks if the focus should be visible, which happens when it was moved by keyboard or gamepad
+    pub fn is_visible(
+        &self
+    ) -> bool {
+        self.focused.is_some() && self.visible
+    }
+
+    /// Focus the given entity, like when it's clicked. It won't match `:focus-visible`.
+    pub fn focus(
+        &mut self,
+        entity: Entity
+    ) {
+        self.focused = Some(entity);
+        self.visible = false;
+    }
+
+    /// Focus the given entity, like when navigating with keyboard or gamepad. It will match `:focus-visible`.
+    pub fn focus_visible(
+        &mut self,
+        entity: Entity
+    ) {
+        self.focused = Some(entity);
+        self.visible = true;
+    }
+
+    /// Removes the focus from any entity
+    pub fn blur(
+        &mut self
+    ) {
+        self.focused = None;
+        self.visible = false;
+    }
+}
+
+/// Helper [`SystemParam`] which moves the focus across [`Focusable`] entities, following the order of UI entities tree.
+///
+/// Hidden and [`Disabled`] entities are skipped.
+#[derive(SystemParam)]
+pub struct FocusNavigation<'w, 's>
+{
+    pub focus: ResMut<'w, FocusState>,
+    q_roots: Query<'w, 's, Entity, (With<Node>, Without<Parent>)>,
+    q_children: Query<'w, 's, &'static Children, With<Node>>,
+    q_focusable: Query<'w, 's, Option<&'static InheritedVisibility>, (With<Focusable>, Without<Disabled>)>,
+}
+
+impl FocusNavigation<'_, '_>
+{
+    /// Moves the focus to the next [`Focusable`] entity, wrapping around to the first one
+    pub fn focus_next(
+        &mut self
+    ) {
+        self.step(1);
+    }
+
+    /// Moves the focus to the previous [`Focusable`] entity, wrapping around to the last one
+    pub fn focus_previous(
+        &mut self
+    ) {
+        self.step(-1);
+    }
+
+    /// All [`Focusable`] entities which can receive focus, in the UI entities tree order
+    pub fn focus_order(
+        &self
+    ) -> Vec<Entity> {
+        let mut order = Vec::new();
+        let mut stack: Vec<Entity> = self.q_roots.iter().collect();
+        stack.reverse();
+
+        while let Some(entity) = stack.pop()
+        {
+            match self.q_focusable.get(entity)
+            {
+                Ok(visibility) if visibility.is_none_or(|visibility| visibility.get()) => order.push(entity),
+                _ => (),
+            }
+
+            if let Ok(children) = self.q_children.get(entity)
+            {
+                stack.extend(children.iter().rev());
+            }
+        }
+
+        order
+    }
+
+    fn step(
+        &mut self,
+        step: isize
+    ) {
+        let order = self.focus_order();
+        if order.is_empty()
+        {
+            return;
+        }
+
+        let len = order.len() as isize;
+        let next = match self.focus.focused().and_then(|focused| order.iter().position(|&e| e == focused))
+        {
+            Some(index) => (index as isize + step).rem_euclid(len),
+            None if step > 0 => 0,
+            None => len - 1,
+        };
+
+        self.focus.focus_visible(order[next as usize]);
+    }
+}
+
+/// Moves the focus with `Tab` and `Shift + Tab` keys, or arrow keys.
+///
+/// Add this system to your app to enable keyboard navigation, like `app.add_systems(Update, keyboard_navigation)`.
+pub fn keyboard_navigation(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut navigation: FocusNavigation,
+) {
+    let shift = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+
+    if keys.just_pressed(KeyCode::Tab)
+    {
+        match shift
+        {
+            true => navigation.focus_previous(),
+            false => navigation.focus_next(),
+        }
+    }
+    else if keys.any_just_pressed([KeyCode::ArrowDown, KeyCode::ArrowRight])
+    {
+        navigation.focus_next();
+    }
+    else if keys.any_just_pressed([KeyCode::ArrowUp, KeyCode::ArrowLeft])
+    {
+        navigation.focus_previous();
+    }
+}
+
+/// Moves the focus with the directional pad of any connected gamepad.
+///
+/// Add this system to your app to enable gamepad navigation, like `app.add_systems(Update, gamepad_navigation)`.
+pub fn gamepad_navigation(
+    gamepads: Res<Gamepads>,
+    buttons: Res<ButtonInput<GamepadButton>>,
+    mut navigation: FocusNavigation,
+) {
+    for gamepad in gamepads.iter()
+    {
+        let pressed = |button_types: [GamepadButtonType; 2]| button_types.into_iter()
+            .any(|button_type| buttons.just_pressed(GamepadButton::new(gamepad, button_type)));
+
+        if pressed([GamepadButtonType::DPadDown, GamepadButtonType::DPadRight])
+        {
+            navigation.focus_next();
+        }
+        else if pressed([GamepadButtonType::DPadUp, GamepadButtonType::DPadLeft])
+        {
+            navigation.focus_previous();
+        }
+    }
+}
+
+type QueryFocusableInteractions<'w, 's> = Query<
+    'w, 's,
+    (Entity, &'static Interaction),
+    (Changed<Interaction>, With<Focusable>, Without<Disabled>),
+>;
+
+/// Focus clicked [`Focusable`] entities and mirrors [`FocusState`] into [`Focusable`] components
+pub(crate) fn update_focus(
+    mut focus: ResMut<FocusState>,
+    q_interactions: QueryFocusableInteractions,
+    mut q_focusable: Query<(Entity, &mut Focusable)>,
+    q_disabled: Query<(), With<Disabled>>,
+) {
+    for (entity, interaction) in &q_interactions
+    {
+        if *interaction == Interaction::Pressed
+        {
+            focus.focus(entity);
+        }
+    }
+
+    // Focused entity may have been despawned, disabled or isn't focusable anymore
+    if focus.focused().is_some_and(|focused| !q_focusable.contains(focused) || q_disabled.contains(focused))
+    {
+        focus.blur();
+    }
+
+    if !focus.is_changed()
+    {
+        return;
+    }
+
+    for (entity, mut focusable) in &mut q_focusable
+    {
+        let focused = focus.focused() == Some(entity);
+
+        // Only mark as changed when the focus actually changed, to avoid matching styles again
+        focusable.set_if_neq(Focusable::new(focused, focus.is_visible()));
+    }
+}
@@ -0,0 +1,99 @@
+use crate::{
+    component::{
+        FontSelection,
+        StyleSheet,
+    },
+    stylesheet::{
+        FontFace,
+        StyleSheetAsset,
+    },
+};
+
+use bevy::{
+    log::error,
+    prelude::{
+        AssetEvent, AssetId, AssetServer,
+        Assets,
+        DetectChanges,
+        Entity, EventReader,
+        Parent,
+        Query,
+        Ref, Res,
+        Text,
+    },
+    utils::HashSet,
+};
+
+/// Resolves the changed [`FontSelection`] of each text entity into the font of the best matching `@font-face` rule,
+/// which is loaded and set on all sections of its [`Text`].
+///
+/// Each family is tried in order, so later ones are fallbacks, like `serif` on `font-family: Fira, serif`.
+/// When no family is declared by any `@font-face` rule, the current font is kept.
+///
+/// Selections are also resolved again when the sheet of the entity, or of any ancestor, is modified or loaded,
+/// since its `@font-face` rules may have changed, like when hot reloading.
+pub(crate) fn resolve_font_faces(
+    asset_server: Res<AssetServer>,
+    assets: Res<Assets<StyleSheetAsset>>,
+    mut assets_events: EventReader<AssetEvent<StyleSheetAsset>>,
+    mut q_texts: Query<(Entity, Ref<FontSelection>, &mut Text)>,
+    q_sheets: Query<&StyleSheet>,
+    q_parents: Query<&Parent>,
+) {
+    let modified: HashSet<AssetId<StyleSheetAsset>> = assets_events.read()
+        .filter_map(|event| match event
+        {
+            AssetEvent::Modified { id } | AssetEvent::LoadedWithDependencies { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+
+    for (entity, selection, mut text) in q_texts.iter_mut()
+    {
+        if selection.families.is_empty()
+        {
+            continue;
+        }
+
+        let sheet_modified = || std::iter::successors(Some(entity), |entity| q_parents.get(*entity).ok().map(Parent::get))
+            .filter_map(|entity| q_sheets.get(entity).ok())
+            .any(|sheet| modified.contains(&sheet.handle().id()));
+
+        if !selection.is_changed() && (modified.is_empty() || !sheet_modified())
+        {
+            continue;
+        }
+
+        let font_face = selection.families.iter()
+            .find_map(|family| find_font_face(entity, family, selection.weight, &assets, &q_sheets, &q_parents));
+
+        let Some(font_face) = font_face
+        else
+        {
+            error!("No @font-face found for font-family: {}", selection.families.join(", "));
+            continue;
+        };
+
+        let font = asset_server.load(&font_face.path);
+        for section in text.sections.iter_mut()
+        {
+            section.style.font = font.clone();
+        }
+    }
+}
+
+/// Finds the [`FontFace`] of the given family and weight on the [`StyleSheet`] of the entity or of its nearest ancestor declaring it.
+fn find_font_face<'a>(
+    entity: Entity,
+    family: &str,
+    weight: f32,
+    assets: &'a Assets<StyleSheetAsset>,
+    q_sheets: &Query<&StyleSheet>,
+    q_parents: &Query<&Parent>,
+) -> Option<&'a FontFace> {
+    std::iter::successors(Some(entity), |entity| q_parents.get(*entity).ok().map(Parent::get))
+        .filter_map(|entity| q_sheets.get(entity).ok())
+        .find_map(|sheet| assets.get(sheet.handle())
+            .and_then(|asset| FontFace::find(asset.font_faces(), family, weight))
+        )
+}
@@ -0,0 +1,75 @@
+use super::{
+    CustomMediaFeatures,
+    RootFontSize,
+};
+use crate::{
+    component::StyleSheet,
+    stylesheet::{
+        MediaContext,
+        StyleSheetAsset,
+    },
+};
+
+use bevy::{
+    prelude::{
+        Assets,
+        DetectChanges,
+        Query,
+        Res, ResMut,
+        Vec2,
+        With,
+    },
+    window::{
+        PrimaryWindow,
+        Window,
+    },
+};
+
+/// Updates the [`MediaContext`] from the primary window and the [`CustomMediaFeatures`], and applies again any
+/// [`StyleSheet`] which has a `@media` rule whose queries result changed, like when the window is resized past a breakpoint.
+pub(crate) fn update_media_context(
+    mut context: ResMut<MediaContext>,
+    q_window: Query<&Window, With<PrimaryWindow>>,
+    root_font_size: Res<RootFontSize>,
+    custom_features: Res<CustomMediaFeatures>,
+    assets: Res<Assets<StyleSheetAsset>>,
+    mut q_sheets: Query<&mut StyleSheet>,
+) {
+    let window = q_window.get_single().ok();
+    let viewport = window
+        .map(|window| Vec2::new(window.width(), window.height()))
+        .unwrap_or_default();
+    let scale_factor = window
+        .map(|window| window.scale_factor())
+        .unwrap_or(1.0);
+
+    // Avoid cloning custom features and checking all rules every frame
+    if context.viewport == viewport
+        && context.scale_factor == scale_factor
+        && context.root_font_size == **root_font_size
+        && !custom_features.is_changed()
+    {
+        return;
+    }
+
+    let previous = std::mem::replace(&mut *context, MediaContext {
+        viewport,
+        scale_factor,
+        root_font_size: **root_font_size,
+        custom_features: custom_features.0.clone(),
+    });
+
+    for mut sheet in q_sheets.iter_mut()
+    {
+        let changed = assets.get(sheet.handle())
+            .is_some_and(|asset| asset.iter()
+                .filter(|rule| !rule.media.is_empty())
+                .any(|rule| rule.matches_media(&previous) != rule.matches_media(&context))
+            );
+
+        if changed
+        {
+            sheet.refresh();
+        }
+    }
+}
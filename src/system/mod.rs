@@ -1,5 +1,28 @@
-mod component_filter;
+mod animations;
+pub(crate) use animations::*;
+
+mod attribute_filter;
+pub(crate) use attribute_filter::*;
 
+mod attribute_filter_registry;
+pub(crate) use attribute_filter_registry::*;
+
+#[cfg(feature = "pseudo_class")]
+mod pseudo_class_filter;
+#[cfg(feature = "pseudo_class")]
+pub(crate) use pseudo_class_filter::*;
+
+#[cfg(feature = "pseudo_class")]
+mod pseudo_class_filter_registry;
+#[cfg(feature = "pseudo_class")]
+pub(crate) use pseudo_class_filter_registry::*;
+
+#[cfg(feature = "pseudo_prop")]
+mod pseudo_element_changes;
+#[cfg(feature = "pseudo_prop")]
+pub(crate) use pseudo_element_changes::*;
+
+mod component_filter;
 pub(crate) use component_filter::*;
 
 mod component_filter_registry;
@@ -8,24 +31,63 @@ pub(crate) use component_filter_registry::*;
 mod css_query_param;
 pub(crate) use css_query_param::*;
 
+mod custom_media_features;
+pub use custom_media_features::CustomMediaFeatures;
+
+mod custom_properties;
+use custom_properties::apply_custom_properties;
+
+mod font_faces;
+pub(crate) use font_faces::*;
+
+mod media_queries;
+pub(crate) use media_queries::*;
+
 pub(crate) mod query;
 
+mod relative_lengths;
+pub(crate) use relative_lengths::*;
+
+mod root_font_size;
+pub use root_font_size::RootFontSize;
+
+#[cfg(feature = "pseudo_class")]
+pub mod focus;
+
 pub mod sets;
 
 mod style_tree;
 use style_tree::StyleTree;
 
+#[cfg(test)]
+mod tests;
+
 use crate::{
-    DynArray, component::{
+    DynArray,
+    component::{
         MatchSelectorElement,
         StyleSheet,
-    }, property::{StyleSheetState, StyleSheetStateBuilder}, selector::{Selector, SelectorElement}, stylesheet::StyleSheetAsset
+    },
+    property::{
+        StyleSheetState,
+        StyleSheetStateBuilder,
+    },
+    selector::{
+        AttributeMatch,
+        Selector,
+        SelectorElement,
+    },
+    stylesheet::{
+        MediaContext,
+        StyleSheetAsset,
+    },
 };
 
 use bevy::{
     ecs::system::SystemState,
     log::{error, debug, trace},
     prelude::{
+        AppTypeRegistry,
         AssetEvent, Assets,
         Children, Component,
         Deref, DerefMut,
@@ -33,6 +95,7 @@ use bevy::{
         Mut,
         Parent,
         Query,
+        ReflectComponent,
         ResMut, Resource,
         World,
     },
@@ -60,41 +123,113 @@ pub(crate) fn prepare(
     {
         world.resource_scope(|world, mut registry: Mut<ComponentFilterRegistry>|
         {
-            let assets = world.resource::<Assets<StyleSheetAsset>>();
-            let css_query = params.get(world);
-            let state = prepare_state(world, assets, css_query, &mut registry);
-
-            if !state.is_empty()
+            world.resource_scope(|world, mut attributes: Mut<AttributeFilterRegistry>|
             {
-                let mut state_res = world
-                    .get_resource_mut::<StyleSheetState>()
-                    .expect("Should be added by plugin");
+                #[cfg(feature = "pseudo_class")]
+                world.resource_scope(|world, mut pseudo_classes: Mut<PseudoClassFilterRegistry>|
+                {
+                    prepare_scoped(world, &mut params, &mut registry, &mut attributes, &mut pseudo_classes);
+                });
 
-                *state_res = state;
-            }
+                #[cfg(not(feature = "pseudo_class"))]
+                prepare_scoped(world, &mut params, &mut registry, &mut attributes);
+            });
         });
     });
 }
 
-/// Prepare state to be used by [`Property`](crate::Property) systems
+/// Selects all entities with the registries taken out of the [`World`], so they can be used mutably while selecting
+fn prepare_scoped(
+    world: &mut World,
+    params: &mut PrepareParams,
+    registry: &mut ComponentFilterRegistry,
+    attributes: &mut AttributeFilterRegistry,
+    #[cfg(feature = "pseudo_class")]
+    pseudo_classes: &mut PseudoClassFilterRegistry
+) {
+    let assets = world.resource::<Assets<StyleSheetAsset>>();
+    let css_query = params.get(world);
+
+    #[cfg(not(feature = "pseudo_prop"))]
+    let (mut state, examined) = prepare_state(
+        world,
+        assets,
+        css_query,
+        registry,
+        attributes,
+        #[cfg(feature = "pseudo_class")]
+        pseudo_classes
+    );
+
+    #[cfg(feature = "pseudo_prop")]
+    let (mut state, examined) = {
+        let mut pseudo_elements = PseudoElementChanges::default();
+        let (mut state, examined) = prepare_state(
+            world,
+            assets,
+            css_query,
+            registry,
+            attributes,
+            #[cfg(feature = "pseudo_class")]
+            pseudo_classes,
+            &mut pseudo_elements
+        );
+
+        // Despawned pseudo-elements must not be styled anymore
+        for entity in pseudo_elements.apply(world)
+        {
+            state.remove(&entity);
+        }
+        (state, examined)
+    };
+
+    apply_custom_properties(world, &mut state, &examined);
+
+    if !state.is_empty()
+    {
+        let mut state_res = world
+            .get_resource_mut::<StyleSheetState>()
+            .expect("Should be added by plugin");
+
+        *state_res = state;
+    }
+}
+
+/// Prepare state to be used by [`Property`](crate::Property) systems, along with all entities which were matched again,
+/// even the ones which aren't selected by any rule anymore
 pub(crate) fn prepare_state(
     world: &World,
     assets: &Assets<StyleSheetAsset>,
-    params: CssQueryParam,
-    registry: &mut ComponentFilterRegistry
-) -> StyleSheetState {
+    mut params: CssQueryParam,
+    registry: &mut ComponentFilterRegistry,
+    attributes: &mut AttributeFilterRegistry,
+    #[cfg(feature = "pseudo_class")]
+    pseudo_classes: &mut PseudoClassFilterRegistry,
+    #[cfg(feature = "pseudo_prop")]
+    pseudo_elements: &mut PseudoElementChanges
+) -> (StyleSheetState, DynArray<Entity>) {
     let mut state = StyleSheetStateBuilder::default();
     let mut style_tree: StyleTree = Default::default();
+    let mut examined = DynArray::<Entity>::new();
+    let media = world.resource::<MediaContext>();
 
     // Find only changed components
-    for updated_entity in &params.ui_changes
+    for updated_entity in get_updated_entities(
+        world,
+        &mut params,
+        registry,
+        attributes,
+        #[cfg(feature = "pseudo_class")]
+        pseudo_classes
+    )
     {
         debug!("Update detected for entity {}", updated_entity.index());
 
         // Find list of stylesheets that apply to this component (and cache in style_tree for next iterations)
-        for (root_entity, sheet_handle) in style_tree
+        for (depth, (root_entity, sheet_handle)) in style_tree
             .get_style_roots_for(updated_entity, &params.ui_nodes)
             .iter()
+            .enumerate()
         {
             let style_sheet = match params.assets.get(sheet_handle)
             {
@@ -106,26 +241,62 @@ pub(crate) fn prepare_state(
             };
 
             debug!("Applying style {}", style_sheet.path());
-            for rule in style_sheet.iter()
+
+            let filter = build_entity_filter(*root_entity, updated_entity, &params).unwrap_or_default();
+
+            // Entities under this root have the same chain of nested sheets up to it
+            state.set_depth(sheet_handle, depth, filter.iter().copied());
+
+            // Pseudo-elements of any entity matched again are despawned, unless a rule still wants them
+            #[cfg(feature = "pseudo_prop")]
+            pseudo_elements.examine(*root_entity, filter.iter().copied());
+
+            examined.extend(filter);
+
+            // Rules of `@media` rules which doesn't match aren't applied, until the media changes
+            for selector in style_sheet.iter()
+                .filter(|rule| rule.matches_media(media))
+                .flat_map(|rule| rule.selectors.iter())
             {
+                #[cfg(feature = "pseudo_prop")]
+                if let Some((originating, element)) = selector.split_pseudo_element()
+                {
+                    let entities = select_entities(
+                        *root_entity,
+                        updated_entity,
+                        &originating,
+                        world,
+                        &params,
+                        registry,
+                        attributes,
+                        #[cfg(feature = "pseudo_class")]
+                        pseudo_classes,
+                    );
+
+                    pseudo_elements.want(element, entities);
+                }
+
                 let mut entities = select_entities(
                     *root_entity,
                     updated_entity,
-                    &rule.selector,
+                    selector,
                     world,
                     &params,
                     registry,
+                    attributes,
+                    #[cfg(feature = "pseudo_class")]
+                    pseudo_classes,
                 );
 
                 trace!(
                     "Applying rule '{}' on {} entities",
-                    rule.selector.to_string(),
+                    selector.to_string(),
                     entities.len()
                 );
 
                 let existing_state = state.entry(sheet_handle.clone())
                     .or_default()
-                    .entry(rule.selector.clone())
+                    .entry(selector.clone())
                     .or_default();
 
                 entities = entities.into_iter()
@@ -140,7 +311,62 @@ pub(crate) fn prepare_state(
     {
         trace!("PreProcess result: {state:?}");
     }
-    state.build(assets)
+
+    // Nested sheets aren't evaluated when an ancestor above their style root changes, so their pseudo-elements are kept
+    #[cfg(feature = "pseudo_prop")]
+    pseudo_elements.retain_examined(|entity| style_tree.get_style_roots_for(entity, &params.ui_nodes)
+        .into_iter()
+        .map(|(root_entity, _sheet_handle)| root_entity)
+        .collect()
+    );
+
+    (state.build(assets), examined)
+}
+
+/// Collects all entities which changed since the last run, so styles must be matched again
+fn get_updated_entities(
+    world: &World,
+    params: &mut CssQueryParam,
+    registry: &mut ComponentFilterRegistry,
+    attributes: &mut AttributeFilterRegistry,
+    #[cfg(feature = "pseudo_class")]
+    pseudo_classes: &mut PseudoClassFilterRegistry
+) -> DynArray<Entity> {
+    let mut updated_entities: DynArray<Entity> = params.ui_changes.iter().collect();
+
+    // Attribute values are read from components, so selectors like `[value="ok"]` must be matched again when they change
+    updated_entities.extend(attributes.changed(world));
+
+    // Fields of reflected components are also used as attributes, like `[rarity="epic"]`, unless a getter is registered
+    let mut reflected_attributes = DynArray::<&str>::new();
+    for name in params.assets.iter()
+        .flat_map(|(_id, sheet)| sheet.iter())
+        .flat_map(|rule| rule.selectors.iter())
+        .flat_map(Selector::attribute_names)
+        .filter(|name| !attributes.0.contains_key(name))
+    {
+        if !reflected_attributes.contains(&name)
+        {
+            reflected_attributes.push(name);
+        }
+    }
+    updated_entities.extend(registry.changed(world, &reflected_attributes));
+
+    // Components used by registered pseudo-classes are tracked too, since the predicate may depend on them
+    #[cfg(feature = "pseudo_class")]
+    updated_entities.extend(pseudo_classes.changed(world));
+
+    // Removing a marker component, like `Disabled`, must also match styles again
+    #[cfg(feature = "pseudo_class")]
+    {
+        let removed = &mut params.pseudo_classes.removed;
+        updated_entities.extend(removed.disabled.read());
+        updated_entities.extend(removed.checked.read());
+    }
+
+    updated_entities.sort();
+    updated_entities.dedup();
+    updated_entities
 }
 
 fn build_entity_filter(
@@ -151,8 +377,9 @@ fn build_entity_filter(
     css_query.ui_nodes.get(updated_node)
         .map(|(_entity, parent, children, _stylesheet)|
         {
-            // Add parents recursively
-            parent.map_or_else(DynArray::default, |parent| 
+            // Add parents recursively, but never above the style root, since its sheet can't select them
+            parent.filter(|_parent| updated_node != root)
+                .map_or_else(DynArray::default, |parent|
                     get_parents_recursively(root, parent, &css_query.parent)
                 )
                 .into_iter()
@@ -170,15 +397,19 @@ fn build_entity_filter(
 /// Select all entities using the given [`Selector`](crate::selector::Selector).
 ///
 /// If no [`Children`] is supplied, then the selector is applied only on root entity.
+#[allow(clippy::too_many_arguments)]
 fn select_entities(
     root_node: Entity,
     updated_node: Entity,
     selector: &Selector,
     world: &World,
     css_query: &CssQueryParam,
-    registry: &mut ComponentFilterRegistry
+    registry: &mut ComponentFilterRegistry,
+    attributes: &mut AttributeFilterRegistry,
+    #[cfg(feature = "pseudo_class")]
+    pseudo_classes: &mut PseudoClassFilterRegistry
 ) -> DynArray<Entity> {
-    let mut parent_tree = selector.get_parent_tree();
+    let parent_tree = selector.get_combinator_tree();
 
     if parent_tree.is_empty()
     {
@@ -186,12 +417,158 @@ fn select_entities(
     }
 
     let mut filter = build_entity_filter(root_node, updated_node, css_query);
+
+    // Siblings of the updated node, or of any of its ancestors, may also be the start of a sibling relation
+    let sibling_combinator = has_sibling_combinator(&parent_tree);
+
+    // Relational selectors depends on entities related to the selected ones, like descendants of `.slot:has(.item)`,
+    // so a change on the updated node may change which of its ancestors, or their previous siblings, are selected
+    #[cfg(feature = "pseudo_class")]
+    let relational = selector.is_relational();
+    #[cfg(not(feature = "pseudo_class"))]
+    let relational = false;
+
+    if sibling_combinator || relational
+    {
+        filter = filter.map(|filter|
+        {
+            // Entities are ordered as ancestors, then updated node, then descendants
+            let lineage = filter.iter()
+                .position(|&e| e == updated_node)
+                .map_or(0, |idx| idx + 1);
+
+            let siblings: DynArray<Entity> = filter[..lineage].iter()
+                .filter(|&&e| e != root_node)
+                .flat_map(|&e| match sibling_combinator
+                {
+                    true => get_siblings(e, css_query),
+                    false => get_previous_siblings(e, css_query),
+                })
+                .filter(|e| !filter.contains(e))
+                .collect();
+
+            filter.into_iter()
+                .chain(siblings)
+                .collect()
+        });
+    }
+
+    // On nested style sheets `:root` is an ancestor of the style root, so the lineage up to it is also considered,
+    // but only entities under the style root are selected
+    #[cfg(feature = "pseudo_class")]
+    if selector.has_pseudo_class("root")
+    {
+        let outermost = get_outermost_style_root(root_node, css_query);
+
+        if outermost != root_node
+        {
+            let ancestors = css_query.parent.get(root_node)
+                .map(|(_entity, parent)| get_parents_recursively(outermost, parent, &css_query.parent))
+                .unwrap_or_default();
+
+            filter = filter.map(|filter|
+            {
+                let ancestors: DynArray<Entity> = ancestors.into_iter()
+                    .filter(|e| !filter.contains(e))
+                    .collect();
+
+                ancestors.into_iter()
+                    .chain(filter)
+                    .collect()
+            });
+
+            let mut entities = select_entities_tree(
+                root_node,
+                parent_tree,
+                filter,
+                world,
+                css_query,
+                registry,
+                attributes,
+                pseudo_classes
+            );
+            entities.retain(|entity| is_in_scope(*entity, root_node, css_query));
+            return entities;
+        }
+    }
+
+    select_entities_tree(
+        root_node,
+        parent_tree,
+        filter,
+        world,
+        css_query,
+        registry,
+        attributes,
+        #[cfg(feature = "pseudo_class")]
+        pseudo_classes
+    )
+}
+
+/// Checks if the entity is the given style root or any of its descendants
+#[cfg(feature = "pseudo_class")]
+fn is_in_scope(
+    entity: Entity,
+    root_node: Entity,
+    css_query: &CssQueryParam
+) -> bool {
+    let mut current = entity;
+
+    loop
+    {
+        if current == root_node
+        {
+            break true;
+        }
+
+        match css_query.parent.get(current)
+        {
+            Ok((_entity, parent)) => current = parent.get(),
+            Err(_err) => break false,
+        }
+    }
+}
+
+/// Checks if any node of the selector tree is related to the next one by a sibling combinator
+fn has_sibling_combinator(
+    parent_tree: &[(Option<&SelectorElement>, DynArray<&SelectorElement>)]
+) -> bool {
+    parent_tree.iter()
+        .any(|(combinator, _node)| matches!(
+            combinator,
+            Some(SelectorElement::AdjacentSibling | SelectorElement::GeneralSibling)
+        ))
+}
+
+/// Walks down the selector tree, starting with entities on the given filter, and returns the entities matched by the last node.
+#[allow(clippy::too_many_arguments)]
+fn select_entities_tree(
+    root_node: Entity,
+    mut parent_tree: DynArray<(Option<&SelectorElement>, DynArray<&SelectorElement>)>,
+    mut filter: Option<DynArray<Entity>>,
+    world: &World,
+    css_query: &CssQueryParam,
+    registry: &mut ComponentFilterRegistry,
+    attributes: &mut AttributeFilterRegistry,
+    #[cfg(feature = "pseudo_class")]
+    pseudo_classes: &mut PseudoClassFilterRegistry
+) -> DynArray<Entity> {
     loop
     {
         // TODO: Rework this to use a index to avoid recreating parent_tree every time the systems runs.
         // This is has little to no impact on performance, since this system doesn't runs often.
-        let node = parent_tree.remove(0);
-        let entities = select_entities_node(node, world, css_query, registry, filter.clone());
+        let (_combinator, node) = parent_tree.remove(0);
+        let entities = select_entities_node(
+            root_node,
+            node,
+            world,
+            css_query,
+            registry,
+            attributes,
+            #[cfg(feature = "pseudo_class")]
+            pseudo_classes,
+            filter.clone()
+        );
 
         if parent_tree.is_empty()
         {
@@ -199,25 +576,55 @@ fn select_entities(
         }
         else
         {
-            let children = entities.into_iter()
-                .filter_map(|e| css_query.children.get(e).ok())
-                .flat_map(|(_e, ch)|
-                    get_children_recursively(ch, &css_query.children)
-                )
-                .collect();
-
-            filter = Some(children);
+            filter = Some(get_related_entities(parent_tree[0].0, entities, css_query));
         }
     }
 }
 
+/// Collects all entities related to the given ones by the combinator between two nodes of a selector tree.
+///
+/// When no combinator is given, the relation defaults to [`Child`](SelectorElement::Child).
+fn get_related_entities(
+    combinator: Option<&SelectorElement>,
+    entities: DynArray<Entity>,
+    css_query: &CssQueryParam
+) -> DynArray<Entity> {
+    match combinator
+    {
+        Some(SelectorElement::DirectChild) => entities.into_iter()
+            .filter_map(|e| css_query.children.get(e).ok())
+            .flat_map(|(_e, ch)| ch.iter().copied())
+            .collect(),
+
+        Some(SelectorElement::AdjacentSibling) => entities.into_iter()
+            .filter_map(|e| get_next_siblings(e, css_query).first().copied())
+            .collect(),
+
+        Some(SelectorElement::GeneralSibling) => entities.into_iter()
+            .flat_map(|e| get_next_siblings(e, css_query))
+            .collect(),
+
+        _ => entities.into_iter()
+            .filter_map(|e| css_query.children.get(e).ok())
+            .flat_map(|(_e, ch)|
+                get_children_recursively(ch, &css_query.children)
+            )
+            .collect(),
+    }
+}
+
 /// Filter entities matching the given selectors.
 /// This function is called once per node on tree returned by [`get_parent_tree`](Selector::get_parent_tree)
+#[allow(clippy::too_many_arguments)]
 fn select_entities_node(
+    root_node: Entity,
     node: DynArray<&SelectorElement>,
     world: &World,
     css_query: &CssQueryParam,
     registry: &mut ComponentFilterRegistry,
+    attributes: &mut AttributeFilterRegistry,
+    #[cfg(feature = "pseudo_class")]
+    pseudo_classes: &mut PseudoClassFilterRegistry,
     filter: Option<DynArray<Entity>>
 ) -> DynArray<Entity> {
     let fold_fn = |
@@ -238,16 +645,75 @@ fn select_entities_node(
                 filter.as_ref()
             ),
 
+            // Any entity under the style root is selected, so there is nothing else to filter
+            SelectorElement::Universal => filter.unwrap_or_else(|| get_scope_entities(root_node, css_query)),
+
+            SelectorElement::Attribute(name, matching) => get_entities_with_attribute(
+                name.as_str(),
+                matching,
+                world,
+                registry,
+                attributes,
+                filter.as_ref()
+            ),
+
             #[cfg(feature = "pseudo_class")]
             SelectorElement::PseudoClass(class) => get_entities_with_pseudo_class(
                 class.as_str(),
-                &css_query.pseudo_classes,
+                root_node,
+                world,
+                css_query,
+                pseudo_classes,
                 filter.as_ref()
             ),
 
+            #[cfg(feature = "pseudo_class")]
+            SelectorElement::NthChild(nth) => get_entities_with_structure(
+                root_node,
+                css_query,
+                filter.as_ref(),
+                |index, _count| nth.matches(index as i32 + 1)
+            ),
+
+            #[cfg(feature = "pseudo_class")]
+            SelectorElement::NthLastChild(nth) => get_entities_with_structure(
+                root_node,
+                css_query,
+                filter.as_ref(),
+                |index, count| nth.matches((count - index) as i32)
+            ),
+
+            #[cfg(feature = "pseudo_class")]
+            SelectorElement::Not(selectors) => {
+                let candidates = get_candidate_entities(root_node, css_query, filter.as_ref());
+                let matched = get_entities_matching_any(root_node, selectors, &candidates, world, css_query, registry, attributes, pseudo_classes);
+
+                candidates.into_iter()
+                    .filter(|e| !matched.contains(e))
+                    .collect()
+            }
+
+            #[cfg(feature = "pseudo_class")]
+            SelectorElement::Is(selectors)
+            | SelectorElement::Where(selectors) => {
+                let candidates = get_candidate_entities(root_node, css_query, filter.as_ref());
+                get_entities_matching_any(root_node, selectors, &candidates, world, css_query, registry, attributes, pseudo_classes)
+            }
+
+            #[cfg(feature = "pseudo_class")]
+            SelectorElement::Has(selectors) => get_candidate_entities(root_node, css_query, filter.as_ref())
+                .into_iter()
+                .filter(|&entity| selectors.iter().any(|selector|
+                    has_relative_match(root_node, entity, selector, world, css_query, registry, attributes, pseudo_classes)
+                ))
+                .collect(),
+
             #[cfg(feature = "pseudo_prop")]
-            SelectorElement::PseudoProp(_prop) => todo!(
-                "Implement PseudoProperty selection"
+            SelectorElement::PseudoProp(element) => get_pseudo_elements(
+                root_node,
+                *element,
+                css_query,
+                filter.as_ref()
             ),
 
             SelectorElement::Component(component) => get_entities_with_component(
@@ -257,49 +723,399 @@ fn select_entities_node(
                 filter.as_ref()
             ),
 
-            // All child elements are filtered by [`get_parent_tree`](Selector::get_parent_tree)
-            SelectorElement::Child => unreachable!(),
+            // All combinator elements are filtered by [`get_combinator_tree`](Selector::get_combinator_tree)
+            SelectorElement::Child
+            | SelectorElement::DirectChild
+            | SelectorElement::AdjacentSibling
+            | SelectorElement::GeneralSibling => unreachable!(),
         };
 
         Some(result)
     };
 
+    // Pseudo-elements are only selected by pseudo-element selectors, like `.item::before`
+    #[cfg(feature = "pseudo_prop")]
+    if node.iter().any(|element| matches!(element, SelectorElement::PseudoProp(_)))
+    {
+        return node.into_iter()
+            .fold(filter, fold_fn)
+            .unwrap_or_default();
+    }
+
     node.into_iter()
         .fold(filter, fold_fn)
         .unwrap_or_default()
+        .into_iter()
+        .filter(|&entity| !is_pseudo_element(entity, css_query))
+        .collect()
+}
+
+/// Checks if the given entity is a [`PseudoElement`](crate::component::PseudoElement) managed by `tomt_bevycss`
+#[cfg(feature = "pseudo_prop")]
+fn is_pseudo_element(
+    entity: Entity,
+    css_query: &CssQueryParam
+) -> bool {
+    css_query.pseudo_elements.contains(entity)
+}
+
+/// Without `pseudo_prop` feature there are no pseudo-elements
+#[cfg(not(feature = "pseudo_prop"))]
+fn is_pseudo_element(
+    _entity: Entity,
+    _css_query: &CssQueryParam
+) -> bool {
+    false
+}
+
+/// Collects pseudo-elements, which are children of the given entities
+#[cfg(feature = "pseudo_prop")]
+fn get_pseudo_elements(
+    root_node: Entity,
+    element: crate::component::PseudoElement,
+    css_query: &CssQueryParam,
+    filter: Option<&DynArray<Entity>>
+) -> DynArray<Entity> {
+    get_candidate_entities(root_node, css_query, filter)
+        .into_iter()
+        .filter_map(|entity| css_query.children.get(entity).ok())
+        .flat_map(|(_entity, children)| children.iter().copied())
+        .filter(|&child| css_query.pseudo_elements.get(child)
+            .is_ok_and(|(_child, child_element)| *child_element == element)
+        )
+        .collect()
+}
+
+/// Filters the candidate entities, keeping only the ones matched by at least one of the given selectors.
+///
+/// Each selector is matched relative to the candidates ancestors (and their siblings), so complex selectors
+/// like `:is(.inventory > .slot)` are supported.
+#[cfg(feature = "pseudo_class")]
+#[allow(clippy::too_many_arguments)]
+fn get_entities_matching_any(
+    root_node: Entity,
+    selectors: &[Selector],
+    candidates: &DynArray<Entity>,
+    world: &World,
+    css_query: &CssQueryParam,
+    registry: &mut ComponentFilterRegistry,
+    attributes: &mut AttributeFilterRegistry,
+    pseudo_classes: &mut PseudoClassFilterRegistry
+) -> DynArray<Entity> {
+    let mut lineage: DynArray<Entity> = candidates.clone();
+
+    for &entity in candidates
+    {
+        let mut current = entity;
+        while let Ok((_entity, parent)) = css_query.parent.get(current)
+        {
+            current = parent.get();
+            if lineage.contains(&current)
+            {
+                break;
+            }
+
+            lineage.push(current);
+        }
+    }
+
+    let mut matched = DynArray::new();
+    for selector in selectors
+    {
+        let parent_tree = selector.get_combinator_tree();
+        if parent_tree.is_empty()
+        {
+            continue;
+        }
+
+        let filter = match has_sibling_combinator(&parent_tree)
+        {
+            true => lineage.iter()
+                .flat_map(|&e| get_siblings(e, css_query))
+                .chain(lineage.iter().copied())
+                .fold(DynArray::new(), |mut filter, e|
+                {
+                    if !filter.contains(&e)
+                    {
+                        filter.push(e);
+                    }
+                    filter
+                }),
+            false => lineage.clone(),
+        };
+
+        for entity in select_entities_tree(root_node, parent_tree, Some(filter), world, css_query, registry, attributes, pseudo_classes)
+        {
+            if candidates.contains(&entity) && !matched.contains(&entity)
+            {
+                matched.push(entity);
+            }
+        }
+    }
+
+    matched
+}
+
+/// Checks if any entity related to the given one matches the relative selector, like `> .item` on `:has(> .item)`.
+///
+/// Descendants and siblings are walked through [`Children`] components, starting from the given entity.
+#[cfg(feature = "pseudo_class")]
+#[allow(clippy::too_many_arguments)]
+fn has_relative_match(
+    root_node: Entity,
+    entity: Entity,
+    selector: &Selector,
+    world: &World,
+    css_query: &CssQueryParam,
+    registry: &mut ComponentFilterRegistry,
+    attributes: &mut AttributeFilterRegistry,
+    pseudo_classes: &mut PseudoClassFilterRegistry
+) -> bool {
+    let mut parent_tree = selector.get_combinator_tree();
+
+    // Relative selectors starts with a combinator, so the first node is always empty and the entity takes its place
+    parent_tree.remove(0);
+    if parent_tree.is_empty()
+    {
+        return false;
+    }
+
+    let filter = get_related_entities(parent_tree[0].0, DynArray::from_elem(entity, 1), css_query);
+    !select_entities_tree(root_node, parent_tree, Some(filter), world, css_query, registry, attributes, pseudo_classes).is_empty()
 }
 
 #[cfg(feature = "pseudo_class")]
 fn get_entities_with_pseudo_class(
     name: &str,
-    query: &PseudoClassParam,
+    root_node: Entity,
+    world: &World,
+    css_query: &CssQueryParam,
+    pseudo_classes: &mut PseudoClassFilterRegistry,
     filter: Option<&DynArray<Entity>>
 ) -> DynArray<Entity> {
-    use bevy::prelude::Interaction;
+    // Registered pseudo-classes takes precedence over builtin ones
+    let registered = pseudo_classes.0.get_mut(name);
 
-    let mut buffer: DynArray<Entity> = Default::default();
-    for (entity, action) in query.interaction.iter()
+    let query = &css_query.pseudo_classes;
+
+    if let Some(pseudo_classes) = registered
     {
-        match (name, *action)
+        let entities = get_entities_with_registered_pseudo_class(name, pseudo_classes, world, filter);
+
+        // Disabled entities doesn't react to pointer interactions, like on browsers
+        return match name
         {
-            ("hover", Interaction::Hovered) => trace!("Entity[{entity:?}]:hover"),
-            ("click", Interaction::Pressed) => trace!("Entity[{entity:?}]:click"),
-            _ => continue,
+            "hover" | "active" | "click" => entities.into_iter()
+                .filter(|&entity| !query.disabled.contains(entity))
+                .collect(),
+            _ => entities,
         };
+    }
+
+    match name
+    {
+        "scope" => get_candidate_entities(root_node, css_query, filter)
+            .into_iter()
+            .filter(|&entity| entity == root_node)
+            .collect(),
 
-        match &filter
+        "root" => {
+            let root = get_outermost_style_root(root_node, css_query);
+            get_candidate_entities(root_node, css_query, filter)
+                .into_iter()
+                .filter(|&entity| entity == root)
+                .collect()
+        }
+
+        "disabled" => get_candidate_entities(root_node, css_query, filter)
+            .into_iter()
+            .filter(|&entity| query.disabled.contains(entity))
+            .collect(),
+
+        "enabled" => get_candidate_entities(root_node, css_query, filter)
+            .into_iter()
+            .filter(|&entity| !query.disabled.contains(entity))
+            .collect(),
+
+        "checked" => get_candidate_entities(root_node, css_query, filter)
+            .into_iter()
+            .filter(|&entity| query.checked.contains(entity))
+            .collect(),
+
+        "first-child" => get_entities_with_structure(root_node, css_query, filter, |index, _count| index == 0),
+        "last-child" => get_entities_with_structure(root_node, css_query, filter, |index, count| index + 1 == count),
+        "only-child" => get_entities_with_structure(root_node, css_query, filter, |_index, count| count == 1),
+
+        "focus" => get_candidate_entities(root_node, css_query, filter)
+            .into_iter()
+            .filter(|&entity| query.focusable.get(entity).is_ok_and(|(_e, focusable)| focusable.is_focused()))
+            .collect(),
+
+        "focus-visible" => get_candidate_entities(root_node, css_query, filter)
+            .into_iter()
+            .filter(|&entity| query.focusable.get(entity).is_ok_and(|(_e, focusable)| focusable.is_focus_visible()))
+            .collect(),
+
+        "focus-within" => get_entities_with_focus_within(css_query, filter),
+
+        // Pseudo-elements aren't counted as children, while text counts as content
+        "empty" => get_candidate_entities(root_node, css_query, filter)
+            .into_iter()
+            .filter(|&entity| css_query.children.get(entity)
+                .map_or(true, |(_e, children)| children.iter()
+                    .all(|&child| is_pseudo_element(child, css_query))
+                )
+            )
+            .filter(|&entity| query.text.get(entity)
+                .map_or(true, |(_e, text)| text.sections.iter().all(|section| section.value.is_empty()))
+            )
+            .collect(),
+
+        _ => {
+            error!("Unsupported pseudo class :{}", name);
+            DynArray::new()
+        }
+    }
+}
+
+/// Filters entities matched by any of the pseudo-classes registered with
+/// [`register_pseudo_class`](crate::RegisterPseudoClass::register_pseudo_class).
+#[cfg(feature = "pseudo_class")]
+fn get_entities_with_registered_pseudo_class(
+    name: &str,
+    pseudo_classes: &mut [Box<dyn PseudoClassFilter + Send + Sync>],
+    world: &World,
+    filter: Option<&DynArray<Entity>>
+) -> DynArray<Entity> {
+    let mut buffer: DynArray<Entity> = Default::default();
+    for entity in pseudo_classes.iter_mut().flat_map(|pseudo_class| pseudo_class.filter(world))
+    {
+        match filter
         {
             Some(f) if !f.contains(&entity) => {
                 trace!("Entity {entity:?} discarded by filter");
                 continue;
             }
-            _ => buffer.push(entity),
+            _ if buffer.contains(&entity) => continue,
+            _ => {
+                trace!("Entity[{entity:?}]:{name}");
+                buffer.push(entity);
+            }
+        }
+    }
+
+    buffer
+}
+
+/// Finds the outermost entity with a [`StyleSheet`], starting from the given style root up to its ancestors
+#[cfg(feature = "pseudo_class")]
+fn get_outermost_style_root(
+    root_node: Entity,
+    css_query: &CssQueryParam
+) -> Entity {
+    let mut root = root_node;
+    let mut current = root_node;
+
+    while let Ok((_entity, parent)) = css_query.parent.get(current)
+    {
+        current = parent.get();
+
+        if let Ok((_entity, _parent, _children, Some(_sheet))) = css_query.ui_nodes.get(current)
+        {
+            root = current;
+        }
+    }
+
+    root
+}
+
+/// Filters entities which are focused or have any focused descendant
+#[cfg(feature = "pseudo_class")]
+fn get_entities_with_focus_within(
+    css_query: &CssQueryParam,
+    filter: Option<&DynArray<Entity>>
+) -> DynArray<Entity> {
+    let mut buffer: DynArray<Entity> = Default::default();
+
+    let focused = css_query.pseudo_classes.focusable.iter()
+        .filter(|(_entity, focusable)| focusable.is_focused())
+        .map(|(entity, _focusable)| entity);
+
+    for entity in focused
+    {
+        let mut current = Some(entity);
+        while let Some(entity) = current
+        {
+            if filter.is_none_or(|filter| filter.contains(&entity)) && !buffer.contains(&entity)
+            {
+                buffer.push(entity);
+            }
+
+            current = css_query.parent.get(entity)
+                .ok()
+                .map(|(_entity, parent)| parent.get());
         }
     }
 
     buffer
 }
 
+/// Filters entities by their position among siblings, on the parent [`Children`] component.
+///
+/// The predicate receives the 0-based index of the entity and the number of siblings, including the entity itself.
+/// Entities without an UI parent are considered the only child.
+#[cfg(feature = "pseudo_class")]
+fn get_entities_with_structure(
+    root_node: Entity,
+    css_query: &CssQueryParam,
+    filter: Option<&DynArray<Entity>>,
+    predicate: impl Fn(usize, usize) -> bool
+) -> DynArray<Entity> {
+    get_candidate_entities(root_node, css_query, filter)
+        .into_iter()
+        .filter(|&entity|
+        {
+            // Pseudo-elements aren't counted as siblings
+            let siblings: DynArray<Entity> = get_siblings(entity, css_query)
+                .into_iter()
+                .filter(|&sibling| !is_pseudo_element(sibling, css_query))
+                .collect();
+
+            siblings.iter()
+                .position(|&sibling| sibling == entity)
+                .is_some_and(|index| predicate(index, siblings.len()))
+        })
+        .collect()
+}
+
+/// Entities which may be selected, which are the filtered ones or all entities under the style root when there is no filter.
+#[cfg(feature = "pseudo_class")]
+fn get_candidate_entities(
+    root_node: Entity,
+    css_query: &CssQueryParam,
+    filter: Option<&DynArray<Entity>>
+) -> DynArray<Entity> {
+    match filter
+    {
+        Some(filter) => filter.clone(),
+        None => get_scope_entities(root_node, css_query),
+    }
+}
+
+/// The given style root and all its descendants, which are the only entities its sheet may select
+fn get_scope_entities(
+    root_node: Entity,
+    css_query: &CssQueryParam
+) -> DynArray<Entity> {
+    std::iter::once(root_node)
+        .chain(css_query.children.get(root_node)
+            .map_or_else(|_err| DynArray::new(), |(_e, children)|
+                get_children_recursively(children, &css_query.children)
+            )
+        )
+        .collect()
+}
+
 /// Utility function to filter any entities by using a component with implements [`MatchSelectorElement`]
 fn get_entities_with<T>(
     name: &str,
@@ -352,6 +1168,88 @@ fn get_entities_with_component(
     }
 }
 
+/// Filters entities which have an attribute matching the attribute selector, like `[disabled]` or `[rarity="epic"]`.
+///
+/// Attributes registered on [`AttributeFilterRegistry`] are used when available, otherwise the attribute is looked
+/// up as a field on any reflected component which is registered on [`ComponentFilterRegistry`].
+fn get_entities_with_attribute(
+    name: &str,
+    matching: &AttributeMatch,
+    world: &World,
+    components: &mut ComponentFilterRegistry,
+    attributes: &mut AttributeFilterRegistry,
+    filter: Option<&DynArray<Entity>>
+) -> DynArray<Entity> {
+    let mut buffer = DynArray::new();
+
+    if let Some(getters) = attributes.0.get_mut(name)
+    {
+        for (entity, value) in getters.iter_mut().flat_map(|getter| getter.filter(world))
+        {
+            let selected = matching.matches(&value)
+                && filter.is_none_or(|f| f.contains(&entity))
+                && !buffer.contains(&entity);
+
+            if selected
+            {
+                buffer.push(entity);
+            }
+        }
+
+        return buffer;
+    }
+
+    let Some(type_registry) = world.get_resource::<AppTypeRegistry>() else
+    {
+        trace!("Unable to look up reflected attribute {}", name);
+        return buffer;
+    };
+
+    let type_registry = type_registry.read();
+    let reflected: DynArray<&ReflectComponent> = components.0.values()
+        .filter_map(|component| type_registry.get_type_data::<ReflectComponent>(component.component_type_id()))
+        .collect();
+
+    // Without a filter, any entity with a reflected component may have the attribute
+    let candidates = match filter
+    {
+        Some(filter) => filter.clone(),
+        None => {
+            let mut candidates = DynArray::new();
+            for entity in components.0.values_mut()
+                .filter(|component| type_registry.get_type_data::<ReflectComponent>(component.component_type_id()).is_some())
+                .flat_map(|component| component.filter(world))
+            {
+                if !candidates.contains(&entity)
+                {
+                    candidates.push(entity);
+                }
+            }
+            candidates
+        }
+    };
+
+    for entity in candidates
+    {
+        let Some(entity_ref) = world.get_entity(entity) else
+        {
+            continue;
+        };
+
+        let selected = reflected.iter()
+            .filter_map(|reflect| reflect.reflect(entity_ref))
+            .filter_map(|component| reflect_attribute(component, name))
+            .any(|value| matching.matches(&value));
+
+        if selected
+        {
+            buffer.push(entity);
+        }
+    }
+
+    buffer
+}
+
 /// Starting with the provided [Parent], collect all UI parent entities, recurisevely up the entity tree
 /// # Arguments
 /// `root` - The top-level [Entity] which contains the stylesheet, passed in to provide early stop when root hit
@@ -366,7 +1264,7 @@ fn get_parents_recursively(
     {
         Ok((entity, parent)) => match entity == root
         {
-            true => DynArray::from_elem(entity, 1),
+            true => DynArray::new(),
             false => get_parents_recursively(root, parent, query_parent),
         },
         Err(_err) => Default::default(),
@@ -397,7 +1295,54 @@ fn get_children_recursively(
         .collect()
 }
 
-/// Auto reapply style sheets when hot reloading is enabled
+/// Collect all siblings of the given entity, including itself, following the order of the parent [Children] component
+/// # Arguments
+/// `entity` - The [Entity] to find siblings of
+/// `css_query` - Bevy [Query] parameters to perform the parent and children lookups
+fn get_siblings(
+    entity: Entity,
+    css_query: &CssQueryParam
+) -> DynArray<Entity> {
+    css_query.parent.get(entity)
+        .and_then(|(_e, parent)| css_query.children.get(parent.get()))
+        .map_or_else(
+            |_err| DynArray::from_elem(entity, 1),
+            |(_e, children)| children.iter().copied().collect()
+        )
+}
+
+/// Collect all siblings which comes after the given entity, following the order of the parent [Children] component
+/// # Arguments
+/// `entity` - The [Entity] to start searching siblings from, which isn't included in returned list
+/// `css_query` - Bevy [Query] parameters to perform the parent and children lookups
+fn get_next_siblings(
+    entity: Entity,
+    css_query: &CssQueryParam
+) -> DynArray<Entity> {
+    get_siblings(entity, css_query)
+        .into_iter()
+        .skip_while(|&sibling| sibling != entity)
+        .skip(1)
+        .collect()
+}
+
+/// Collect all siblings which comes before the given entity, following the order of the parent [Children] component
+/// # Arguments
+/// `entity` - The [Entity] to start searching siblings from, which isn't included in returned list
+/// `css_query` - Bevy [Query] parameters to perform the parent and children lookups
+fn get_previous_siblings(
+    entity: Entity,
+    css_query: &CssQueryParam
+) -> DynArray<Entity> {
+    get_siblings(entity, css_query)
+        .into_iter()
+        .take_while(|&sibling| sibling != entity)
+        .collect()
+}
+
+/// Auto reapply style sheets when hot reloading is enabled.
+///
+/// Sheets importing a modified sheet are reloaded by the asset server too, since imports are loader dependencies.
 pub(crate) fn hot_reload_style_sheets(
     mut assets_events: EventReader<AssetEvent<StyleSheetAsset>>,
     mut q_sheets: Query<&mut StyleSheet>,
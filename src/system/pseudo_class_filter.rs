@@ -0,0 +1,94 @@
+use bevy::{
+    ecs::system::SystemState,
+    prelude::{
+        Changed,
+        Component,
+        Entity,
+        Node,
+        Query,
+        RemovedComponents,
+        With,
+        World,
+    },
+};
+use crate::DynArray;
+
+pub(crate) trait PseudoClassFilter
+{
+    /// Entities which currently match the pseudo-class
+    fn filter(
+        &mut self,
+        world: &World
+    ) -> DynArray<Entity>;
+
+    /// Entities which component changed or was removed since the last call, so the pseudo-class may need to be matched again
+    fn changed(
+        &mut self,
+        world: &World
+    ) -> DynArray<Entity>;
+}
+
+type ChangesQuery<T> = (
+    Query<'static, 'static, Entity, (Changed<T>, With<Node>)>,
+    RemovedComponents<'static, 'static, T>,
+);
+
+/// Matches a pseudo-class using the predicate given on
+/// [`register_pseudo_class`](crate::RegisterPseudoClass::register_pseudo_class).
+pub(crate) struct PseudoClassPredicate<T, F>
+where
+    T: Component,
+    F: Fn(&T) -> bool + Send + Sync + 'static,
+{
+    pub state: SystemState<Query<'static, 'static, (Entity, &'static T)>>,
+    pub changes: SystemState<ChangesQuery<T>>,
+    pub predicate: F,
+}
+
+impl<T, F> PseudoClassPredicate<T, F>
+where
+    T: Component,
+    F: Fn(&T) -> bool + Send + Sync + 'static,
+{
+    pub fn new(
+        world: &mut World,
+        predicate: F
+    ) -> Self {
+        Self {
+            state: SystemState::new(world),
+            changes: SystemState::new(world),
+            predicate,
+        }
+    }
+}
+
+impl<T, F> PseudoClassFilter
+for PseudoClassPredicate<T, F>
+where
+    T: Component,
+    F: Fn(&T) -> bool + Send + Sync + 'static,
+{
+    fn filter(
+        &mut self,
+        world: &World
+    ) -> DynArray<Entity> {
+        let predicate = &self.predicate;
+
+        self.state.get(world)
+            .iter()
+            .filter(|(_entity, component)| predicate(component))
+            .map(|(entity, _component)| entity)
+            .collect()
+    }
+
+    fn changed(
+        &mut self,
+        world: &World
+    ) -> DynArray<Entity> {
+        let (changes, mut removed) = self.changes.get(world);
+
+        changes.iter()
+            .chain(removed.read())
+            .collect()
+    }
+}
@@ -0,0 +1,147 @@
+use crate::{
+    component::PseudoElement,
+    DynArray,
+};
+
+use bevy::{
+    hierarchy::{
+        BuildWorldChildren,
+        DespawnRecursiveExt,
+    },
+    log::trace,
+    prelude::{
+        Children,
+        Entity,
+        TextBundle,
+        TextStyle,
+        World,
+    },
+    utils::{
+        HashMap,
+        HashSet,
+    },
+};
+
+/// Pseudo-elements wanted by rules matched while preparing the state, which are spawned or despawned afterwards,
+/// since entities can't be changed while selecting them.
+#[derive(Debug, Default)]
+pub(crate) struct PseudoElementChanges
+{
+    /// Entities which pseudo-elements were matched again, so any pseudo-element which isn't wanted anymore is despawned,
+    /// with the style roots which rules were evaluated on them
+    examined: HashMap<Entity, HashSet<Entity>>,
+
+    /// Pseudo-elements wanted for each originating entity
+    wanted: HashSet<(Entity, PseudoElement)>,
+}
+
+impl PseudoElementChanges
+{
+    /// Marks the given entities as matched again by the rules of the given style root
+    pub fn examine(
+        &mut self,
+        root_entity: Entity,
+        entities: impl IntoIterator<Item = Entity>
+    ) {
+        for entity in entities
+        {
+            self.examined.entry(entity)
+                .or_default()
+                .insert(root_entity);
+        }
+    }
+
+    /// Keeps only the entities which rules of all their style roots, given by `style_roots`, were evaluated,
+    /// since any other style root may still want their pseudo-elements
+    pub fn retain_examined(
+        &mut self,
+        mut style_roots: impl FnMut(Entity) -> Vec<Entity>
+    ) {
+        self.examined.retain(|&entity, evaluated| style_roots(entity)
+            .iter()
+            .all(|root_entity| evaluated.contains(root_entity))
+        );
+    }
+
+    /// Marks the given pseudo-element as wanted by a matching rule, on each originating entity
+    pub fn want(
+        &mut self,
+        element: PseudoElement,
+        entities: impl IntoIterator<Item = Entity>
+    ) {
+        self.wanted.extend(entities.into_iter().map(|entity| (entity, element)));
+    }
+
+    /// Spawns all wanted pseudo-elements which doesn't exists yet and despawns the ones which aren't wanted anymore.
+    ///
+    /// Returns the despawned pseudo-elements.
+    pub fn apply(
+        self,
+        world: &mut World
+    ) -> DynArray<Entity> {
+        let mut despawned = DynArray::new();
+
+        let originating: HashSet<Entity> = self.examined.keys()
+            .copied()
+            .chain(self.wanted.iter().map(|(entity, _element)| *entity))
+            .collect();
+
+        for entity in originating
+        {
+            if world.get_entity(entity).is_none()
+            {
+                continue;
+            }
+
+            let existing: DynArray<(Entity, PseudoElement)> = world.get::<Children>(entity)
+                .map(|children| children.iter()
+                    .filter_map(|&child| world.get::<PseudoElement>(child).map(|element| (child, *element)))
+                    .collect()
+                )
+                .unwrap_or_default();
+
+            for &(child, element) in &existing
+            {
+                if !self.wanted.contains(&(entity, element))
+                {
+                    trace!("Despawning {element} of entity {entity:?}");
+                    world.entity_mut(child).despawn_recursive();
+                    despawned.push(child);
+                }
+            }
+
+            for element in PseudoElement::ALL
+            {
+                let exists = existing.iter().any(|(_child, existing)| *existing == element);
+                if exists || !self.wanted.contains(&(entity, element))
+                {
+                    continue;
+                }
+
+                trace!("Spawning {element} of entity {entity:?}");
+                let child = world.spawn((TextBundle::from_section("", TextStyle::default()), element)).id();
+
+                match element
+                {
+                    PseudoElement::After => {
+                        world.entity_mut(entity).push_children(&[child]);
+                    }
+                    _ => {
+                        // Keep pseudo-elements in order, like `::marker` before `::before`
+                        let index = world.get::<Children>(entity)
+                            .map_or(0, |children| children.iter()
+                                .take_while(|&&sibling| world.get::<PseudoElement>(sibling)
+                                    .is_some_and(|sibling| *sibling < element)
+                                )
+                                .count()
+                            );
+
+                        world.entity_mut(entity).insert_children(index, &[child]);
+                    }
+                }
+            }
+        }
+
+        despawned
+    }
+}
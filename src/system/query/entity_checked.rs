@@ -0,0 +1,15 @@
+use crate::prelude::Checked;
+use bevy::prelude::{
+    Entity,
+    Query,
+    With,
+};
+
+pub type QueryEntityChecked<'w, 's> = Query<
+    'w, 's,
+    WorldQuery,
+    ReadOnlyWorldQuery,
+>;
+
+pub type WorldQuery = Entity;
+pub type ReadOnlyWorldQuery = With<Checked>;
@@ -0,0 +1,15 @@
+use crate::prelude::Disabled;
+use bevy::prelude::{
+    Entity,
+    Query,
+    With,
+};
+
+pub type QueryEntityDisabled<'w, 's> = Query<
+    'w, 's,
+    WorldQuery,
+    ReadOnlyWorldQuery,
+>;
+
+pub type WorldQuery = Entity;
+pub type ReadOnlyWorldQuery = With<Disabled>;
@@ -0,0 +1,14 @@
+use crate::prelude::Focusable;
+use bevy::prelude::{
+    Entity,
+    Query,
+};
+
+pub type QueryEntityFocusable<'w, 's> = Query<
+    'w, 's,
+    WorldQuery,
+    ReadOnlyWorldQuery,
+>;
+
+pub type WorldQuery = (Entity, &'static Focusable);
+pub type ReadOnlyWorldQuery = ();
@@ -0,0 +1,14 @@
+use crate::prelude::PseudoElement;
+use bevy::prelude::{
+    Entity,
+    Query,
+};
+
+pub type QueryEntityPseudoElements<'w, 's> = Query<
+    'w, 's,
+    WorldQuery,
+    ReadOnlyWorldQuery,
+>;
+
+pub type WorldQuery = (Entity, &'static PseudoElement);
+pub type ReadOnlyWorldQuery = ();
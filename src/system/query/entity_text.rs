@@ -0,0 +1,16 @@
+use bevy::prelude::{
+    Entity,
+    Node,
+    Query,
+    Text,
+    With,
+};
+
+pub type QueryEntityText<'w, 's> = Query<
+    'w, 's,
+    WorldQuery,
+    ReadOnlyWorldQuery,
+>;
+
+pub type WorldQuery = (Entity, &'static Text);
+pub type ReadOnlyWorldQuery = With<Node>;
@@ -1,17 +1,39 @@
+#[cfg(feature = "pseudo_class")]
+pub mod entity_checked;
+#[cfg(feature = "pseudo_class")]
+pub use entity_checked::QueryEntityChecked;
+
 pub mod entity_children;
 pub use entity_children::QueryEntityChildren;
 
+#[cfg(feature = "pseudo_class")]
+pub mod entity_disabled;
+#[cfg(feature = "pseudo_class")]
+pub use entity_disabled::QueryEntityDisabled;
+
 pub mod entity_parent;
 pub use entity_parent::QueryEntityParent;
 
 pub mod entity_classes;
 pub use entity_classes::QueryEntityClasses;
 
+#[cfg(feature = "pseudo_class")]
+pub mod entity_focusable;
+#[cfg(feature = "pseudo_class")]
+pub use entity_focusable::QueryEntityFocusable;
+
 pub mod entity_names;
 pub use entity_names::QueryEntityNames;
 
-pub mod entity_interaction;
-pub use entity_interaction::QueryEntityInteraction;
+#[cfg(feature = "pseudo_prop")]
+pub mod entity_pseudo_elements;
+#[cfg(feature = "pseudo_prop")]
+pub use entity_pseudo_elements::QueryEntityPseudoElements;
+
+#[cfg(feature = "pseudo_class")]
+pub mod entity_text;
+#[cfg(feature = "pseudo_class")]
+pub use entity_text::QueryEntityText;
 
 pub mod ui_changes;
 pub use ui_changes::QueryUiChanges;
@@ -10,6 +10,11 @@ use bevy::prelude::{
     With,
 };
 
+/// Entities which changed since the last run and must be matched again.
+///
+/// With `monitor_changes`, changes on [`Class`](crate::prelude::Class) and [`Children`](bevy::prelude::Children) are tracked,
+/// so when a descendant changes, or is added or removed, relational selectors like `.slot:has(.item)` are matched again
+/// on the ancestors of the changed entity (and their siblings), but not on the rest of the style root.
 pub type QueryUiChanges<'w, 's> = Query<
     'w, 's,
     WorldQuery,
@@ -59,6 +64,11 @@ mod monitor_changes
     mod pseudo_class
     {
         use super::*;
+        use crate::prelude::{
+            Checked,
+            Disabled,
+            Focusable,
+        };
         use bevy::prelude::Interaction;
 
         pub type ReadOnlyWorldQuery = (
@@ -68,6 +78,8 @@ mod monitor_changes
                 Added<Children>,    Changed<Children>,
                 Added<Class>,       Changed<Class>,
                 Added<Interaction>, Changed<Interaction>,
+                Added<Focusable>,   Changed<Focusable>,
+                Added<Disabled>,    Added<Checked>,
             )>,
             With<Node>
         );
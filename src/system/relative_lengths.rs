@@ -0,0 +1,273 @@
+use super::RootFontSize;
+use crate::{
+    component::{
+        LengthTarget,
+        PercentBase,
+        RelativeLengths,
+    },
+    property::LengthContext,
+};
+
+use bevy::{
+    prelude::{
+        DetectChanges, DetectChangesMut,
+        Entity,
+        Mut,
+        Node,
+        ParamSet,
+        Parent,
+        Query,
+        Ref,
+        Res,
+        Style,
+        Text,
+        Vec2,
+        With,
+    },
+    ui::Val,
+    window::{
+        PrimaryWindow,
+        Window,
+    },
+};
+
+/// Resolves [`RelativeLengths`] with percents, like `calc(100% - 20px)`, whenever any size they depend on changes,
+/// like the size of the parent node, or of the primary window for root nodes, and the inherited font size.
+///
+/// The size of the parent node is only known after [`UiSystem::Layout`](bevy::ui::UiSystem::Layout), so this runs after it,
+/// which means lengths resolved here lags one frame behind, and are laid out again on the next frame.
+/// Lengths without percents are resolved before layout by [`resolve_viewport_and_font_lengths`] instead.
+pub(crate) fn resolve_relative_lengths(
+    mut q_lengths: Query<(Entity, Ref<RelativeLengths>, Option<&Parent>, &mut Style)>,
+    q_nodes: Query<Ref<Node>>,
+    q_fonts: Query<(Option<Ref<Text>>, Option<&Parent>)>,
+    q_window: Query<Ref<Window>, With<PrimaryWindow>>,
+    root_font_size: Res<RootFontSize>,
+) {
+    let window = q_window.get_single().ok();
+    let viewport = window
+        .as_ref()
+        .map(|window| Vec2::new(window.width(), window.height()))
+        .unwrap_or_default();
+    let viewport_changed = window.is_some_and(|window| window.is_changed());
+
+    for (entity, lengths, parent, mut style) in q_lengths.iter_mut()
+    {
+        if !lengths.values().any(|relative| relative.expression.has_percentage())
+        {
+            continue;
+        }
+
+        let (size, size_changed) = match parent
+        {
+            Some(parent) => match q_nodes.get(parent.get())
+            {
+                Ok(node) => (node.size(), node.is_changed()),
+                Err(_) => continue,
+            },
+
+            None => (viewport, false),
+        };
+
+        let font_changed = q_fonts.get(entity)
+            .is_ok_and(|(text, _)| text.is_some_and(|text| text.is_changed()));
+
+        if !lengths.is_changed()
+            && !size_changed
+            && !viewport_changed
+            && !font_changed
+            && !root_font_size.is_changed()
+        {
+            continue;
+        }
+
+        let context = LengthContext {
+            percent_base: 0.0,
+            viewport,
+            font_size: inherited_font_size(entity, &q_fonts).unwrap_or(**root_font_size),
+            root_font_size: **root_font_size,
+        };
+
+        apply_lengths(&lengths, &mut style, size, context, true);
+    }
+}
+
+/// Resolves [`RelativeLengths`] without percents, like `50vw` or `calc(2em + 4px)`, whenever the size of the primary window,
+/// the inherited font size or the root font size changes.
+///
+/// They doesn't depend on the size of the parent node, so they are resolved before [`UiSystem::Layout`](bevy::ui::UiSystem::Layout)
+/// and laid out on the same frame.
+pub(crate) fn resolve_viewport_and_font_lengths(
+    mut q_lengths: Query<(Entity, Ref<RelativeLengths>, &mut Style)>,
+    q_fonts: Query<(Option<Ref<Text>>, Option<&Parent>)>,
+    q_window: Query<Ref<Window>, With<PrimaryWindow>>,
+    root_font_size: Res<RootFontSize>,
+) {
+    let window = q_window.get_single().ok();
+    let viewport = window
+        .as_ref()
+        .map(|window| Vec2::new(window.width(), window.height()))
+        .unwrap_or_default();
+    let viewport_changed = window.is_some_and(|window| window.is_changed());
+
+    for (entity, lengths, mut style) in q_lengths.iter_mut()
+    {
+        let font_changed = q_fonts.get(entity)
+            .is_ok_and(|(text, _)| text.is_some_and(|text| text.is_changed()));
+
+        if !lengths.is_changed()
+            && !viewport_changed
+            && !font_changed
+            && !root_font_size.is_changed()
+        {
+            continue;
+        }
+
+        let context = LengthContext {
+            percent_base: 0.0,
+            viewport,
+            font_size: inherited_font_size(entity, &q_fonts).unwrap_or(**root_font_size),
+            root_font_size: **root_font_size,
+        };
+
+        apply_lengths(&lengths, &mut style, viewport, context, false);
+    }
+}
+
+/// Applies the [`Style`] lengths of the [`RelativeLengths`] which has percents or not, resolving percents against the given size.
+fn apply_lengths(
+    lengths: &RelativeLengths,
+    style: &mut Mut<Style>,
+    size: Vec2,
+    mut context: LengthContext,
+    percents: bool,
+) {
+    let mut resolved = (**style).clone();
+    for relative in lengths.values()
+    {
+        // Font sizes are resolved by `resolve_relative_font_sizes` instead
+        let LengthTarget::Style(base, apply) = relative.target else
+        {
+            continue;
+        };
+
+        if relative.expression.has_percentage() != percents
+        {
+            continue;
+        }
+
+        context.percent_base = match base
+        {
+            PercentBase::Width => size.x,
+            PercentBase::Height => size.y,
+        };
+
+        if let Some(px) = relative.expression.resolve(&context)
+        {
+            apply(&mut resolved, Val::Px(px));
+        }
+    }
+
+    // Avoid triggering a new layout when nothing changed, like when the window changes for any other reason
+    style.set_if_neq(resolved);
+}
+
+/// Gets the font size of the first [`Text`] section on the entity or on its nearest ancestor with one.
+fn inherited_font_size(
+    entity: Entity,
+    q_fonts: &Query<(Option<Ref<Text>>, Option<&Parent>)>,
+) -> Option<f32> {
+    let (text, parent) = q_fonts.get(entity).ok()?;
+
+    match text.and_then(|text| text.sections.first().map(|section| section.style.font_size))
+    {
+        Some(font_size) => Some(font_size),
+        None => inherited_font_size(parent?.get(), q_fonts),
+    }
+}
+
+type FontSizesQuery<'w, 's> = Query<
+    'w, 's,
+    (Entity, Option<&'static Text>, Option<&'static RelativeLengths>, Option<&'static Parent>),
+>;
+
+/// Resolves relative `font-size` values of [`RelativeLengths`], like `2em` or `1.5rem`, on all sections of the [`Text`].
+///
+/// `em` and percents are resolved against the font size of the parent, which is resolved first when it's also relative,
+/// so nested relative font sizes are resolved on the same update. Font sizes are only set when they changed.
+pub(crate) fn resolve_relative_font_sizes(
+    mut q_fonts: ParamSet<(
+        FontSizesQuery,
+        Query<&mut Text>,
+    )>,
+    q_window: Query<&Window, With<PrimaryWindow>>,
+    root_font_size: Res<RootFontSize>,
+) {
+    let context = LengthContext {
+        percent_base: 0.0,
+        viewport: q_window.get_single()
+            .map(|window| Vec2::new(window.width(), window.height()))
+            .unwrap_or_default(),
+        font_size: **root_font_size,
+        root_font_size: **root_font_size,
+    };
+
+    let q_relative = q_fonts.p0();
+    let resolved: Vec<(Entity, f32)> = q_relative.iter()
+        .filter(|(_entity, text, lengths, _parent)| text.is_some()
+            && lengths.is_some_and(|lengths| lengths.contains_key(RelativeLengths::FONT_SIZE))
+        )
+        .filter_map(|(entity, ..)| resolved_font_size(entity, &q_relative, &context)
+            .map(|font_size| (entity, font_size))
+        )
+        .collect();
+
+    let mut q_text = q_fonts.p1();
+    for (entity, font_size) in resolved
+    {
+        let Ok(mut text) = q_text.get_mut(entity) else
+        {
+            continue;
+        };
+
+        // Avoid triggering a new layout when nothing changed
+        if text.sections.iter().any(|section| section.style.font_size != font_size)
+        {
+            for section in text.sections.iter_mut()
+            {
+                section.style.font_size = font_size;
+            }
+        }
+    }
+}
+
+/// Gets the font size of the entity, resolving its relative `font-size` against the font size of its parent,
+/// otherwise the font size of its first [`Text`] section, or of its nearest ancestor with one.
+fn resolved_font_size(
+    entity: Entity,
+    q_fonts: &FontSizesQuery,
+    context: &LengthContext,
+) -> Option<f32> {
+    let (_entity, text, lengths, parent) = q_fonts.get(entity).ok()?;
+
+    match lengths.and_then(|lengths| lengths.get(RelativeLengths::FONT_SIZE))
+    {
+        Some(relative) => {
+            let parent_font_size = parent
+                .and_then(|parent| resolved_font_size(parent.get(), q_fonts, context))
+                .unwrap_or(context.root_font_size);
+
+            relative.expression.resolve(&LengthContext {
+                percent_base: parent_font_size,
+                font_size: parent_font_size,
+                ..*context
+            })
+        }
+
+        None => match text.and_then(|text| text.sections.first().map(|section| section.style.font_size))
+        {
+            Some(font_size) => Some(font_size),
+            None => resolved_font_size(parent?.get(), q_fonts, context),
+        },
+    }
+}
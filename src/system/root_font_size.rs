@@ -0,0 +1,35 @@
+use bevy::prelude::{
+    Deref, DerefMut,
+    Resource,
+};
+
+/// Font size used to resolve `rem` lengths, and `em` lengths of nodes without any [`Text`](bevy::text::Text) on them or their ancestors.
+///
+/// Defaults to `16.0`, like most browsers.
+///
+/// # Examples
+///
+/// ```rust
+/// # use bevy::prelude::*;
+/// # use tomt_bevycss::prelude::*;
+/// fn scale_ui(
+///     mut root_font_size: ResMut<RootFontSize>,
+/// ) {
+///     **root_font_size = 24.0;
+/// }
+/// ```
+#[derive(Clone, Copy, Debug)]
+#[derive(Deref, DerefMut, Resource)]
+pub struct RootFontSize(
+    pub f32
+);
+
+impl Default
+for RootFontSize
+{
+    fn default(
+        // no args
+    ) -> Self {
+        Self(16.0)
+    }
+}
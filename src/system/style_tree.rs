@@ -16,13 +16,14 @@ pub(super) struct StyleTreeNode
 {
     pub entity: Entity,
     pub sheet_handle: Handle<StyleSheetAsset>,
-    pub parent: Option<Handle<StyleSheetAsset>>,
+    pub parent: Option<Entity>,
 }
 
+/// Maps each style root entity to its node, since the same sheet may be attached to many entities
 #[derive(Default, Deref, DerefMut)]
 pub(super) struct StyleTree(
     HashMap<
-        Handle<StyleSheetAsset>,
+        Entity,
         StyleTreeNode
     >
 );
@@ -31,7 +32,7 @@ impl StyleTree
 {
     fn resolve(
         &self,
-        child_node: &Handle<StyleSheetAsset>,
+        child_node: &Entity,
     ) -> Vec<(Entity, Handle<StyleSheetAsset>)> {
         match self.get(child_node)
         {
@@ -74,7 +75,7 @@ impl<'me, 'w, 's> StyleTree
         {
             (Some(style), _p) => {
                 trace!("Stylesheet found on entity {entity_idx}");
-                let result = if let Some(node) = self.get(style.handle())
+                let result = if let Some(node) = self.get(&entity)
                 {
                     trace!("Entity {entity_idx} is already in the tree, returning early");
                     node
@@ -90,10 +91,10 @@ impl<'me, 'w, 's> StyleTree
                             None
                         }
                     }
-                    .map(|p| p.sheet_handle);
+                    .map(|p| p.entity);
 
                     self.insert_unique_unchecked(
-                        style.handle().clone(),
+                        entity,
                         StyleTreeNode
                         {
                             entity,
@@ -122,7 +123,7 @@ impl<'me, 'w, 's> StyleTree
         let root_node = self.get_or_find_root(entity, query);
         match root_node
         {
-            Some(node) => self.resolve(&node.sheet_handle),
+            Some(node) => self.resolve(&node.entity),
             None => vec![],
         }
     }
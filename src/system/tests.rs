@@ -0,0 +1,805 @@
+use super::prepare;
+use crate::{
+    prelude::*,
+    property::StyleSheetState,
+};
+
+use bevy::{
+    asset::AssetPlugin,
+    prelude::*,
+    time::TimeUpdateStrategy,
+};
+use std::time::Duration;
+
+/// Creates an app with the plugin, where each update advances the time by `100ms`,
+/// and a root node with a sheet parsed from the given `css`.
+fn app_with_sheet(
+    css: &str
+) -> (App, Entity) {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AssetPlugin::default(), HierarchyPlugin))
+        .init_asset::<Font>()
+        .init_asset::<Image>()
+        .add_plugins(BevyCssPlugin::default())
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+
+    let sheet = app.world.resource_mut::<Assets<StyleSheetAsset>>().add(StyleSheetAsset::parse("", css));
+    let root = app.world.spawn((NodeBundle::default(), StyleSheet::new(sheet), Class::new("root"))).id();
+
+    (app, root)
+}
+
+/// Spawns a node with the given classes as the last child of the given parent.
+fn spawn_node(
+    app: &mut App,
+    parent: Entity,
+    classes: &'static str
+) -> Entity {
+    let entity = app.world.spawn((NodeBundle::default(), Class::new(classes))).id();
+    app.world.entity_mut(parent).push_children(&[entity]);
+    entity
+}
+
+/// Runs [`prepare`] once, returning the state of the entities matched again.
+fn select(
+    app: &mut App
+) -> StyleSheetState {
+    *app.world.resource_mut::<StyleSheetState>() = StyleSheetState::default();
+    prepare(&mut app.world);
+
+    std::mem::take(&mut *app.world.resource_mut::<StyleSheetState>())
+}
+
+/// Entities on the given state which the given property is applied on, sorted.
+fn matched(
+    state: &StyleSheetState,
+    property: &str
+) -> Vec<Entity> {
+    let mut entities: Vec<Entity> = state.iter()
+        .filter(|(_entity, style)| style.contains_key(property))
+        .map(|(entity, _style)| *entity)
+        .collect();
+
+    entities.sort();
+    entities
+}
+
+/// Sorts the given entities, so they can be compared with [`matched`] ones.
+fn sorted<const N: usize>(
+    mut entities: [Entity; N]
+) -> Vec<Entity> {
+    entities.sort();
+    entities.to_vec()
+}
+
+#[test]
+fn select_combinators(
+    // no args
+) {
+    let (mut app, root) = app_with_sheet(r#"
+        .root .item { width: 1px; }
+        .root > .item { height: 1px; }
+        .a + .item { left: 1px; }
+        .a ~ .item { top: 1px; }
+    "#);
+
+    let a = spawn_node(&mut app, root, "a");
+    let first = spawn_node(&mut app, root, "item");
+    let second = spawn_node(&mut app, root, "item");
+    let nested = spawn_node(&mut app, a, "item");
+
+    let state = select(&mut app);
+    assert_eq!(matched(&state, "width"), sorted([first, second, nested]), "Should select all descendants");
+    assert_eq!(matched(&state, "height"), sorted([first, second]), "Should select only direct children");
+    assert_eq!(matched(&state, "left"), sorted([first]), "Should select only the next sibling");
+    assert_eq!(matched(&state, "top"), sorted([first, second]), "Should select all next siblings");
+
+    // Only changed entities are matched again, along with their relatives
+    let state = select(&mut app);
+    assert!(matched(&state, "width").is_empty());
+    app.world.entity_mut(second).insert(Class::new("a"));
+    let state = select(&mut app);
+    assert!(!matched(&state, "width").contains(&second), "Should match changed classes again");
+}
+
+#[test]
+fn select_attributes(
+    // no args
+) {
+    let (mut app, root) = app_with_sheet(r#"
+        [value^="Warn"] { height: 1px; }
+    "#);
+
+    spawn_node(&mut app, root, "");
+    let text = app.world.spawn((TextBundle::from_section("Warning", TextStyle::default()), Class::new(""))).id();
+    app.world.entity_mut(root).push_children(&[text]);
+
+    let state = select(&mut app);
+    assert_eq!(matched(&state, "height"), sorted([text]));
+}
+
+#[test]
+fn select_universal(
+    // no args
+) {
+    let (mut app, root) = app_with_sheet(r#"
+        .root > * { width: 1px; }
+    "#);
+
+    let node = spawn_node(&mut app, root, "");
+    let text = app.world.spawn((TextBundle::from_section("Warning", TextStyle::default()), Class::new(""))).id();
+    app.world.entity_mut(root).push_children(&[text]);
+    spawn_node(&mut app, node, "");
+
+    let state = select(&mut app);
+    assert_eq!(matched(&state, "width"), sorted([node, text]));
+}
+
+#[test]
+fn select_universal_in_nested_sheet(
+    // no args
+) {
+    let (mut app, root) = app_with_sheet("");
+    let panel = spawn_node(&mut app, root, "panel");
+    let nested = app.world.resource_mut::<Assets<StyleSheetAsset>>().add(StyleSheetAsset::parse("", "* { width: 1px; }"));
+    app.world.entity_mut(panel).insert(StyleSheet::new(nested));
+
+    let inner = spawn_node(&mut app, panel, "");
+    let sibling = spawn_node(&mut app, root, "");
+    let cousin = spawn_node(&mut app, sibling, "");
+
+    let state = select(&mut app);
+    assert_eq!(matched(&state, "width"), sorted([panel, inner]), "Should not select entities of sibling trees");
+    assert!(!matched(&state, "width").contains(&cousin));
+}
+
+#[test]
+fn select_changed_attributes(
+    // no args
+) {
+    let (mut app, root) = app_with_sheet(r#"
+        [value="ok"] { width: 1px; }
+        text { color: red; }
+    "#);
+
+    let text = app.world.spawn((TextBundle::from_section("no", TextStyle::default()), Class::new(""))).id();
+    app.world.entity_mut(root).push_children(&[text]);
+    app.update();
+    assert_eq!(app.world.get::<Style>(text).unwrap().width, Val::Auto);
+    assert_eq!(app.world.get::<Text>(text).unwrap().sections[0].style.color, Color::RED);
+
+    app.world.get_mut::<Text>(text).unwrap().sections[0].value = "ok".to_string();
+    app.update();
+    assert_eq!(app.world.get::<Style>(text).unwrap().width, Val::Px(1.0), "Should match changed attribute values again");
+
+    app.world.get_mut::<Text>(text).unwrap().sections[0].style.color = Color::BLUE;
+    assert!(matched(&select(&mut app), "width").is_empty(), "Should not match again when the value is the same");
+}
+
+#[test]
+fn select_changed_reflected_attributes(
+    // no args
+) {
+    #[derive(Component, Default, Reflect)]
+    #[reflect(Component)]
+    struct Slot
+    {
+        rarity: u8,
+    }
+
+    let (mut app, root) = app_with_sheet(r#"
+        .item { height: 1px; }
+        slot[rarity="2"] { width: 1px; }
+    "#);
+    app.register_type::<Slot>()
+        .register_component_selector::<Slot>("slot");
+
+    let item = spawn_node(&mut app, root, "item");
+    app.world.entity_mut(item).insert(Slot { rarity: 1 });
+    assert!(matched(&select(&mut app), "width").is_empty());
+
+    app.world.get_mut::<Slot>(item).unwrap().rarity = 2;
+    let state = select(&mut app);
+    assert_eq!(matched(&state, "width"), sorted([item]), "Should match changed reflected fields again");
+
+    app.world.entity_mut(item).remove::<Slot>();
+    let state = select(&mut app);
+    assert_eq!(matched(&state, "height"), sorted([item]), "Should match removed reflected components again");
+    assert!(matched(&state, "width").is_empty());
+}
+
+#[test]
+fn select_unchanged_reflected_attributes(
+    // no args
+) {
+    let (mut app, root) = app_with_sheet(r#"
+        .item { width: 1px; background-color: red; }
+        .item[display="flex"] { height: 1px; }
+    "#);
+    app.register_type::<Style>()
+        .register_type::<BackgroundColor>()
+        .register_type::<Node>()
+        .register_type::<Text>()
+        .register_type::<Interaction>()
+        .register_type::<UiImage>()
+        .register_component_selector::<Style>("style")
+        .register_component_selector::<BackgroundColor>("background")
+        .register_component_selector::<Node>("node")
+        .register_component_selector::<Text>("text")
+        .register_component_selector::<Interaction>("interaction")
+        .register_component_selector::<UiImage>("image");
+
+    let item = spawn_node(&mut app, root, "item");
+    app.update();
+    assert_eq!(app.world.get::<Style>(item).unwrap().height, Val::Px(1.0));
+
+    for _ in 0..3
+    {
+        app.update();
+        assert!(select(&mut app).is_empty(), "Should not match again when applied properties doesn't change reflected attributes");
+    }
+}
+
+#[test]
+fn select_specificity(
+    // no args
+) {
+    let (mut app, root) = app_with_sheet(r#"
+        .root .item.special { width: 2px; }
+        .item { width: 1px; }
+    "#);
+
+    let item = spawn_node(&mut app, root, "item special");
+    app.update();
+
+    assert_eq!(app.world.get::<Style>(item).unwrap().width, Val::Px(2.0), "Should apply the most specific rule");
+}
+
+#[test]
+fn select_repeated_selectors(
+    // no args
+) {
+    let (mut app, root) = app_with_sheet(r#"
+        .a, .b { width: 1px; height: 1px; }
+        .a { height: 2px; }
+        .root .b { left: 1px; }
+        .b { left: 2px; top: 2px; }
+        .a, .b { top: 1px; }
+    "#);
+
+    let a = spawn_node(&mut app, root, "a");
+    let b = spawn_node(&mut app, root, "b");
+    app.update();
+
+    let style = app.world.get::<Style>(a).unwrap();
+    assert_eq!((style.width, style.height), (Val::Px(1.0), Val::Px(2.0)), "Should apply rules repeating a selector");
+    let style = app.world.get::<Style>(b).unwrap();
+    assert_eq!(style.height, Val::Px(1.0));
+    assert_eq!(style.left, Val::Px(1.0), "Should keep the specificity of each rule");
+    assert_eq!(style.top, Val::Px(1.0), "Should keep the load order of each rule");
+}
+
+#[test]
+fn select_sheet_reused_at_many_depths(
+    // no args
+) {
+    let (mut app, root) = app_with_sheet(".item { width: 1px; }");
+    let outer = app.world.get::<StyleSheet>(root).unwrap().handle().clone();
+
+    // Chain of nested sheets is [outer, nested, outer]
+    let panel = spawn_node(&mut app, root, "");
+    let nested = app.world.resource_mut::<Assets<StyleSheetAsset>>().add(StyleSheetAsset::parse("", ".item { width: 2px; }"));
+    app.world.entity_mut(panel).insert(StyleSheet::new(nested));
+    let inner = spawn_node(&mut app, panel, "");
+    app.world.entity_mut(inner).insert(StyleSheet::new(outer));
+
+    let top = spawn_node(&mut app, root, "item");
+    let middle = spawn_node(&mut app, panel, "item");
+    let bottom = spawn_node(&mut app, inner, "item");
+    app.update();
+
+    let width = |entity: Entity| app.world.get::<Style>(entity).unwrap().width;
+    assert_eq!(width(top), Val::Px(1.0));
+    assert_eq!(width(middle), Val::Px(2.0), "Should sort by the depth of the sheet above each entity");
+    assert_eq!(width(bottom), Val::Px(1.0), "Should sort by the deepest attachment of a reused sheet");
+}
+
+#[cfg(feature = "pseudo_class")]
+#[test]
+fn select_structural_pseudo_classes(
+    // no args
+) {
+    let (mut app, root) = app_with_sheet(r#"
+        .item:first-child { width: 1px; }
+        .item:last-child { height: 1px; }
+        .item:nth-child(2n) { left: 1px; }
+        .item:empty { top: 1px; }
+    "#);
+
+    let first = spawn_node(&mut app, root, "item");
+    let second = spawn_node(&mut app, root, "item");
+    let third = spawn_node(&mut app, root, "item");
+    spawn_node(&mut app, third, "child");
+
+    let state = select(&mut app);
+    assert_eq!(matched(&state, "width"), sorted([first]));
+    assert_eq!(matched(&state, "height"), sorted([third]));
+    assert_eq!(matched(&state, "left"), sorted([second]));
+    assert_eq!(matched(&state, "top"), sorted([first, second]));
+}
+
+#[cfg(feature = "pseudo_class")]
+#[test]
+fn select_empty_text(
+    // no args
+) {
+    let (mut app, root) = app_with_sheet(".root > :empty { width: 1px; }");
+
+    let label = app.world.spawn(TextBundle::from_section("label", TextStyle::default())).id();
+    let blank = app.world.spawn(TextBundle::from_sections([TextSection::default(), TextSection::default()])).id();
+    app.world.entity_mut(root).push_children(&[label, blank]);
+
+    let state = select(&mut app);
+    assert_eq!(matched(&state, "width"), sorted([blank]), "Should not be empty with text content");
+
+    app.world.get_mut::<Text>(label).unwrap().sections[0].value.clear();
+    let state = select(&mut app);
+    assert!(matched(&state, "width").contains(&label), "Should match again when the text changes");
+}
+
+#[cfg(feature = "pseudo_class")]
+#[test]
+fn select_logical_pseudo_classes(
+    // no args
+) {
+    let (mut app, root) = app_with_sheet(r#"
+        .item:not(.a) { width: 1px; }
+        :is(.a, .b) { height: 1px; }
+        :where(.root > .b) { left: 1px; }
+        .item:has(> .a) { top: 1px; }
+    "#);
+
+    let a = spawn_node(&mut app, root, "item a");
+    let b = spawn_node(&mut app, root, "item b");
+    let parent = spawn_node(&mut app, root, "item");
+    let nested = spawn_node(&mut app, parent, "a");
+
+    let state = select(&mut app);
+    assert_eq!(matched(&state, "width"), sorted([b, parent]));
+    assert_eq!(matched(&state, "height"), sorted([a, b, nested]));
+    assert_eq!(matched(&state, "left"), sorted([b]));
+    assert_eq!(matched(&state, "top"), sorted([parent]));
+
+    // Removing the matching descendant must match the relational selector again on its ancestors
+    app.world.entity_mut(nested).despawn_recursive();
+    let state = select(&mut app);
+    assert!(!matched(&state, "top").contains(&parent));
+}
+
+#[cfg(feature = "pseudo_class")]
+#[test]
+fn select_relational_on_ancestors(
+    // no args
+) {
+    let (mut app, root) = app_with_sheet(r#"
+        .slot:has(.item) { width: 1px; }
+        .slot:has(+ .item) { height: 1px; }
+    "#);
+
+    let slot = spawn_node(&mut app, root, "slot");
+    let inner = spawn_node(&mut app, slot, "");
+    let leaf = spawn_node(&mut app, inner, "");
+    let next = spawn_node(&mut app, root, "");
+    let other = spawn_node(&mut app, root, "slot");
+    spawn_node(&mut app, other, "item");
+
+    let state = select(&mut app);
+    assert_eq!(matched(&state, "width"), sorted([other]));
+    assert!(matched(&state, "height").is_empty());
+
+    app.world.entity_mut(leaf).insert(Class::new("item"));
+    let state = select(&mut app);
+    assert_eq!(matched(&state, "width"), sorted([slot]), "Should match again only the ancestors of the changed entity");
+
+    app.world.entity_mut(next).insert(Class::new("item"));
+    let state = select(&mut app);
+    assert_eq!(matched(&state, "height"), sorted([slot]), "Should match again the previous siblings of the changed entity");
+}
+
+#[cfg(feature = "pseudo_class")]
+#[test]
+fn select_registered_pseudo_classes(
+    // no args
+) {
+    #[derive(Component)]
+    struct Toggle
+    {
+        on: bool,
+    }
+
+    let (mut app, root) = app_with_sheet(r#"
+        .item { height: 1px; }
+        .item:toggled { width: 1px; }
+    "#);
+    app.register_pseudo_class::<Toggle>("toggled", |toggle| toggle.on);
+
+    let item = spawn_node(&mut app, root, "item");
+    app.world.entity_mut(item).insert(Toggle { on: true });
+
+    let state = select(&mut app);
+    assert_eq!(matched(&state, "width"), sorted([item]));
+
+    app.world.entity_mut(item).remove::<Toggle>();
+    let state = select(&mut app);
+    assert_eq!(matched(&state, "height"), sorted([item]), "Should match removed components again");
+    assert!(matched(&state, "width").is_empty(), "Should not match without the component");
+}
+
+#[cfg(feature = "pseudo_class")]
+#[test]
+fn select_state_pseudo_classes(
+    // no args
+) {
+    let (mut app, root) = app_with_sheet(r#"
+        .item:hover { width: 1px; }
+        .item:active { height: 1px; }
+        .item:disabled { left: 1px; }
+        .item:checked { top: 1px; }
+        .item:enabled { right: 1px; }
+    "#);
+
+    let hovered = spawn_node(&mut app, root, "item");
+    let pressed = spawn_node(&mut app, root, "item");
+    let disabled = spawn_node(&mut app, root, "item");
+    let plain = spawn_node(&mut app, root, "item");
+    app.world.entity_mut(hovered).insert((Interaction::Hovered, Checked));
+    app.world.entity_mut(pressed).insert(Interaction::Pressed);
+    app.world.entity_mut(disabled).insert((Interaction::Hovered, Disabled));
+
+    let state = select(&mut app);
+    assert_eq!(matched(&state, "width"), sorted([hovered]), "Disabled entities shouldn't be hovered");
+    assert_eq!(matched(&state, "height"), sorted([pressed]));
+    assert_eq!(matched(&state, "left"), sorted([disabled]));
+    assert_eq!(matched(&state, "top"), sorted([hovered]));
+    assert_eq!(matched(&state, "right"), sorted([hovered, pressed, plain]), "Should enable any node which isn't disabled");
+
+    app.world.entity_mut(hovered).insert(Interaction::None);
+    let state = select(&mut app);
+    assert!(!matched(&state, "width").contains(&hovered), "Should match changed interactions again");
+
+    app.world.entity_mut(disabled).remove::<Disabled>();
+    let state = select(&mut app);
+    assert!(!matched(&state, "left").contains(&disabled), "Should match removed markers again");
+    assert!(matched(&state, "right").contains(&disabled));
+}
+
+#[cfg(feature = "pseudo_class")]
+#[test]
+fn select_enabled(
+    // no args
+) {
+    let (mut app, root) = app_with_sheet(r#"
+        .item { height: 1px; }
+        .item:enabled { width: 1px; }
+    "#);
+
+    let item = spawn_node(&mut app, root, "item");
+
+    let state = select(&mut app);
+    assert_eq!(matched(&state, "width"), sorted([item]), "Should enable nodes which aren't disabled");
+
+    app.world.entity_mut(item).insert(Disabled);
+    let state = select(&mut app);
+    assert_eq!(matched(&state, "height"), sorted([item]), "Should match inserted markers again");
+    assert!(matched(&state, "width").is_empty(), "Should not enable disabled nodes");
+}
+
+#[cfg(feature = "pseudo_class")]
+#[test]
+fn select_root_and_scope(
+    // no args
+) {
+    let (mut app, root) = app_with_sheet(r#"
+        :root { width: 1px; }
+        :scope > .item { height: 1px; }
+    "#);
+
+    let panel = spawn_node(&mut app, root, "panel");
+    let sheet = app.world.resource_mut::<Assets<StyleSheetAsset>>().add(StyleSheetAsset::parse("", r#"
+        :root .item { left: 1px; }
+        :scope > .item { top: 1px; }
+    "#));
+    app.world.entity_mut(panel).insert(StyleSheet::new(sheet));
+
+    let outer = spawn_node(&mut app, root, "item");
+    let inner = spawn_node(&mut app, panel, "item");
+
+    let state = select(&mut app);
+    assert_eq!(matched(&state, "width"), sorted([root]));
+    assert_eq!(matched(&state, "height"), sorted([outer]));
+    assert_eq!(matched(&state, "left"), sorted([inner]), "Should select from the outermost root, but only under the nested sheet");
+    assert_eq!(matched(&state, "top"), sorted([inner]), "Should scope to the nested sheet");
+}
+
+#[cfg(feature = "pseudo_class")]
+#[test]
+fn focus_navigation(
+    // no args
+) {
+    use bevy::ecs::system::SystemState;
+    use crate::system::focus::FocusNavigation;
+
+    let (mut app, root) = app_with_sheet(r#"
+        .item:focus { width: 1px; }
+        .item:focus-visible { height: 1px; }
+        .root:focus-within { left: 1px; }
+    "#);
+
+    let first = spawn_node(&mut app, root, "item");
+    let disabled = spawn_node(&mut app, root, "item");
+    let last = spawn_node(&mut app, root, "item");
+    // No visibility propagation runs without rendering, so mark them as visible
+    app.world.entity_mut(first).insert((Focusable::default(), InheritedVisibility::VISIBLE));
+    app.world.entity_mut(disabled).insert((Focusable::default(), InheritedVisibility::VISIBLE, Disabled));
+    app.world.entity_mut(last).insert((Focusable::default(), InheritedVisibility::VISIBLE));
+    app.update();
+
+    let mut navigation = SystemState::<FocusNavigation>::new(&mut app.world);
+    navigation.get_mut(&mut app.world).focus_next();
+    navigation.get_mut(&mut app.world).focus_next();
+    app.update();
+
+    assert_eq!(app.world.resource::<FocusState>().focused(), Some(last), "Should skip disabled entities");
+    assert_eq!(app.world.get::<Style>(last).unwrap().width, Val::Px(1.0));
+    assert_eq!(app.world.get::<Style>(last).unwrap().height, Val::Px(1.0));
+    assert_eq!(app.world.get::<Style>(root).unwrap().left, Val::Px(1.0));
+    assert_eq!(app.world.get::<Style>(first).unwrap().width, Val::Auto);
+
+    app.world.resource_mut::<FocusState>().focus(first);
+    app.update();
+    assert_eq!(app.world.get::<Style>(first).unwrap().width, Val::Px(1.0));
+    assert_eq!(app.world.get::<Style>(first).unwrap().height, Val::Auto, "Should not be visible when focused by clicking");
+}
+
+#[test]
+fn inherit_custom_properties(
+    // no args
+) {
+    let (mut app, root) = app_with_sheet(r#"
+        .root { --size: 3px; }
+        .panel { --size: 5px; }
+        .item { width: var(--size); height: var(--missing, 7px); }
+    "#);
+
+    let outer = spawn_node(&mut app, root, "item");
+    let panel = spawn_node(&mut app, root, "panel");
+    let inner = spawn_node(&mut app, panel, "item");
+    app.update();
+
+    assert_eq!(app.world.get::<Style>(outer).unwrap().width, Val::Px(3.0));
+    assert_eq!(app.world.get::<Style>(inner).unwrap().width, Val::Px(5.0), "Should inherit from the nearest ancestor");
+    assert_eq!(app.world.get::<Style>(inner).unwrap().height, Val::Px(7.0), "Should use the fallback");
+
+    let plain = spawn_node(&mut app, root, "");
+    let state = select(&mut app);
+    assert!(!state.contains_key(&plain), "Should not style entities which aren't selected by any rule");
+
+    // Entities matched again without declaring custom properties anymore must stop overriding their ancestors
+    #[cfg(feature = "monitor_changes")]
+    {
+        app.world.entity_mut(panel).insert(Class::new(""));
+        app.update();
+        assert!(app.world.get::<CustomProperties>(panel).is_none());
+        assert_eq!(app.world.get::<Style>(inner).unwrap().width, Val::Px(3.0));
+    }
+}
+
+#[test]
+fn resolve_viewport_and_font_lengths(
+    // no args
+) {
+    use bevy::window::PrimaryWindow;
+
+    let (mut app, root) = app_with_sheet(".item { width: calc(50vw + 1em); height: calc(2rem + 4px); }");
+    app.world.spawn((Window { resolution: (800.0, 600.0).into(), ..default() }, PrimaryWindow));
+
+    let item = spawn_node(&mut app, root, "item");
+    app.update();
+
+    let style = app.world.get::<Style>(item).unwrap();
+    assert_eq!(style.width, Val::Px(416.0), "Should resolve against the primary window and the root font size");
+    assert_eq!(style.height, Val::Px(36.0));
+}
+
+#[test]
+fn resolve_relative_font_sizes(
+    // no args
+) {
+    let (mut app, root) = app_with_sheet(r#"
+        .panel { font-size: 20px; }
+        .title { font-size: 2em; }
+        .label { font-size: 1.5rem; }
+    "#);
+    **app.world.resource_mut::<RootFontSize>() = 10.0;
+
+    let panel = app.world.spawn((TextBundle::from_section("Panel", TextStyle::default()), Class::new("panel"))).id();
+    let title = app.world.spawn((TextBundle::from_section("Title", TextStyle::default()), Class::new("title"))).id();
+    let label = app.world.spawn((TextBundle::from_section("Label", TextStyle::default()), Class::new("label"))).id();
+    app.world.entity_mut(root).push_children(&[panel]);
+    app.world.entity_mut(panel).push_children(&[title, label]);
+
+    let font_size = |app: &App, entity: Entity| app.world.get::<Text>(entity).unwrap().sections[0].style.font_size;
+
+    app.update();
+    assert_eq!(font_size(&app, panel), 20.0);
+    assert_eq!(font_size(&app, title), 40.0, "Should resolve em against the parent font size");
+    assert_eq!(font_size(&app, label), 15.0, "Should resolve rem against the root font size");
+
+    **app.world.resource_mut::<RootFontSize>() = 20.0;
+    app.update();
+    assert_eq!(font_size(&app, label), 30.0, "Should resolve again when the root font size changes");
+}
+
+#[test]
+fn reevaluate_media_on_resize(
+    // no args
+) {
+    use bevy::window::PrimaryWindow;
+
+    let (mut app, root) = app_with_sheet(r#"
+        .item { width: 1px; }
+        @media (min-width: 1000px) { .item { width: 2px; } }
+    "#);
+
+    let window = app.world.spawn((Window { resolution: (800.0, 600.0).into(), ..default() }, PrimaryWindow)).id();
+    let item = spawn_node(&mut app, root, "item");
+    app.update();
+    assert_eq!(app.world.get::<Style>(item).unwrap().width, Val::Px(1.0));
+
+    app.world.get_mut::<Window>(window).unwrap().resolution.set(1200.0, 600.0);
+    app.update();
+    assert_eq!(app.world.get::<Style>(item).unwrap().width, Val::Px(2.0), "Should apply the @media rule once it matches");
+}
+
+#[cfg(feature = "pseudo_prop")]
+#[test]
+fn keep_pseudo_elements_of_nested_sheets(
+    // no args
+) {
+    let (mut app, root) = app_with_sheet(".panel { width: 1px; }");
+    let panel = spawn_node(&mut app, root, "panel");
+    let nested_root = spawn_node(&mut app, panel, "");
+    let nested = app.world.resource_mut::<Assets<StyleSheetAsset>>().add(StyleSheetAsset::parse("", r#".item::before { content: "x"; }"#));
+    app.world.entity_mut(nested_root).insert(StyleSheet::new(nested));
+    let item = spawn_node(&mut app, nested_root, "item");
+
+    let pseudo_elements = |app: &App| app.world.get::<Children>(item)
+        .map(|children| children.iter()
+            .copied()
+            .filter(|&child| app.world.get::<PseudoElement>(child).is_some())
+            .collect::<Vec<_>>()
+        )
+        .unwrap_or_default();
+
+    // Spawned pseudo-elements are styled on the next updates, until nothing changes anymore
+    for _ in 0..4
+    {
+        app.update();
+    }
+    let spawned = pseudo_elements(&app);
+    assert_eq!(spawned.len(), 1);
+
+    // The nested sheet isn't evaluated when an ancestor above its style root changes
+    app.world.entity_mut(panel).insert(Class::new("panel wide"));
+    app.update();
+    assert_eq!(pseudo_elements(&app), spawned, "Should not despawn pseudo-elements wanted by nested sheets");
+    app.update();
+    assert_eq!(pseudo_elements(&app), spawned);
+}
+
+#[test]
+fn tick_animations(
+    // no args
+) {
+    let (mut app, root) = app_with_sheet(r#"
+        @keyframes grow { from { width: 0px; } to { width: 100px; } }
+        .item {
+            animation-name: grow;
+            animation-duration: 1s;
+            animation-timing-function: linear;
+            animation-fill-mode: forwards;
+        }
+    "#);
+
+    let item = spawn_node(&mut app, root, "item");
+    let mut widths = Vec::new();
+    for _ in 0..15
+    {
+        app.update();
+        widths.push(app.world.get::<Style>(item).unwrap().width);
+    }
+
+    assert!(widths.contains(&Val::Px(50.0)), "Should interpolate between keyframes: {widths:?}");
+    assert_eq!(widths.last(), Some(&Val::Px(100.0)), "Should hold the last keyframe");
+}
+
+#[cfg(feature = "monitor_changes")]
+#[test]
+fn tick_transitions(
+    // no args
+) {
+    let (mut app, root) = app_with_sheet(r#"
+        .item { width: 0px; transition: width 1s linear, height 1s; }
+        .item.big { width: 100px; height: 10px; }
+    "#);
+
+    let item = spawn_node(&mut app, root, "item");
+    app.update();
+
+    app.world.entity_mut(item).insert(Class::new("item big"));
+    let mut widths = Vec::new();
+    for _ in 0..15
+    {
+        app.update();
+        widths.push(app.world.get::<Style>(item).unwrap().width);
+    }
+
+    assert!(widths.contains(&Val::Px(50.0)), "Should interpolate the transitioned property: {widths:?}");
+    assert_eq!(widths.last(), Some(&Val::Px(100.0)));
+}
+
+#[test]
+fn resolve_fonts(
+    // no args
+) {
+    let (mut app, root) = app_with_sheet(r#"
+        @font-face { font-family: "Fira"; src: url("fonts/FiraSans-Bold.ttf"); font-weight: 700; }
+        @font-face { font-family: "Fira"; src: url("fonts/FiraSans-Regular.ttf"); }
+        .title { font-family: Unknown, "Fira", serif; font-weight: bold; }
+        .title.light { font-weight: 300; }
+    "#);
+
+    let title = app.world.spawn((TextBundle::from_section("Title", TextStyle::default()), Class::new("title"))).id();
+    app.world.entity_mut(root).push_children(&[title]);
+
+    let font = |app: &App| app.world.get::<Text>(title).unwrap().sections[0].style.font.path().map(ToString::to_string);
+
+    app.update();
+    app.update();
+    assert_eq!(font(&app).as_deref(), Some("fonts/FiraSans-Bold.ttf"), "Should fallback to the next family");
+
+    // Class changes are only tracked with `monitor_changes`
+    #[cfg(feature = "monitor_changes")]
+    {
+        app.world.entity_mut(title).insert(Class::new("title light"));
+        app.update();
+        app.update();
+        assert_eq!(font(&app).as_deref(), Some("fonts/FiraSans-Regular.ttf"), "Should pick the nearest weight");
+    }
+}
+
+#[test]
+fn resolve_fonts_of_modified_sheets(
+    // no args
+) {
+    let (mut app, root) = app_with_sheet(".title { font-family: Late; }");
+    let title = app.world.spawn((TextBundle::from_section("Title", TextStyle::default()), Class::new("title"))).id();
+    app.world.entity_mut(root).push_children(&[title]);
+
+    let font = |app: &App| app.world.get::<Text>(title).unwrap().sections[0].style.font.path().map(ToString::to_string);
+
+    app.update();
+    app.update();
+    assert_eq!(font(&app), None, "Should keep the current font of missing families");
+
+    let handle = app.world.get::<StyleSheet>(root).unwrap().handle().clone();
+    app.world.resource_mut::<Assets<StyleSheetAsset>>().insert(&handle, StyleSheetAsset::parse("", r#"
+        @font-face { font-family: Late; src: url("fonts/Late.ttf"); }
+        .title { font-family: Late; }
+    "#));
+
+    // Asset events are sent at the end of the update
+    app.update();
+    app.update();
+    assert_eq!(font(&app).as_deref(), Some("fonts/Late.ttf"), "Should resolve again when the sheet is modified");
+}